@@ -0,0 +1,88 @@
+//! Procedural macros for [archunit_rs](https://docs.rs/archunit_rs), use them through the
+//! `archunit_rs::test` and `archunit_rs::archunit_test` re-exports.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, ItemFn, ReturnType};
+
+/// Turn a function returning an architecture rule into a test evaluating it.
+///
+/// The generated test is named after the function and panics with the rule report when the
+/// rule is violated. All tests share the module tree loaded by archunit.
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand(args, item)
+}
+
+/// Alias of [`macro@test`], for crates that do not want to shadow the builtin `#[test]`.
+#[proc_macro_attribute]
+pub fn archunit_test(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand(args, item)
+}
+
+fn expand(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    let rule = parse_macro_input!(item as ItemFn);
+
+    match rule_test(args, rule) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn rule_test(
+    args: proc_macro2::TokenStream,
+    rule: ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if !args.is_empty() {
+        return Err(syn::Error::new(
+            args.span(),
+            "unexpected attribute arguments",
+        ));
+    }
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = rule;
+
+    if !sig.inputs.is_empty() {
+        return Err(syn::Error::new(
+            sig.inputs.span(),
+            "architecture rule functions cannot take arguments",
+        ));
+    }
+
+    if sig.asyncness.is_some() || !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.span(),
+            "architecture rule functions cannot be async or generic",
+        ));
+    }
+
+    let output = match &sig.output {
+        ReturnType::Default => {
+            return Err(syn::Error::new(
+                sig.ident.span(),
+                "architecture rule functions must return the rule to evaluate",
+            ))
+        }
+        output => output,
+    };
+
+    let name = &sig.ident;
+    let rule_name = name.to_string();
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() {
+            fn rule() #output #block
+
+            ::archunit_rs::harness::run(#rule_name, rule())
+        }
+    })
+}
@@ -1,21 +1,36 @@
 use crate::ast::structs::Field;
-use crate::ast::CodeSpan;
+use crate::ast::{CodeSpan, ItemPath, LineColumn};
+use crate::report::text::ReportConfig;
+use crate::ModuleTree;
 use miette::{ErrReport, SourceSpan};
 use std::fmt;
 use std::fmt::Formatter;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub struct AssertionResult {
     pub expected: String,
-    pub actual: Vec<ErrReport>,
+    pub rationale: Option<String>,
+    pub actual: Vec<Violation>,
+    pub(crate) success: bool,
+}
+
+/// A single rule violation along with the location of the offending code.
+#[derive(Debug)]
+pub struct Violation {
+    pub module: ItemPath,
+    pub location: PathBuf,
+    pub span: CodeSpan,
+    pub report: ErrReport,
 }
 
 impl AssertionResult {
     pub(crate) fn new() -> Self {
         AssertionResult {
             expected: "".to_owned(),
+            rationale: None,
             actual: vec![],
+            success: true,
         }
     }
 
@@ -23,23 +38,84 @@ impl AssertionResult {
         self.expected.push_str(expected.as_ref());
     }
 
-    pub(crate) fn push_actual<E: Into<ErrReport>>(&mut self, actual: E) {
-        self.actual.push(actual.into());
+    pub(crate) fn push_actual<E: Into<ErrReport>>(
+        &mut self,
+        module: &ItemPath,
+        location: &Path,
+        span: CodeSpan,
+        actual: E,
+    ) {
+        self.actual.push(Violation {
+            module: module.clone(),
+            location: location.to_path_buf(),
+            span,
+            report: actual.into(),
+        });
+    }
+
+    /// Record an error preventing the rule from being checked, reported at the top of the
+    /// crate root.
+    pub(crate) fn push_error<E: Into<ErrReport>>(&mut self, tree: &ModuleTree, error: E) {
+        self.success = false;
+        let span = CodeSpan {
+            start: LineColumn::from((1, 0)),
+            end: LineColumn::from((1, 0)),
+        };
+        self.push_actual(&tree.path, &tree.real_path, span, error);
+    }
+
+    /// Sort violations in source order (file path, then line and column).
+    pub(crate) fn sort_violations(&mut self) {
+        self.actual
+            .sort_by(|a, b| (&a.location, a.span).cmp(&(&b.location, b.span)));
+    }
+
+    /// Returns true if the rule assertions were satisfied.
+    pub fn is_success(&self) -> bool {
+        self.success
+    }
+
+    /// A stable identifier for this rule, derived from its description.
+    pub fn rule_id(&self) -> String {
+        let mut id = String::new();
+        for word in self
+            .expected
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .filter(|word| !word.is_empty())
+        {
+            if !id.is_empty() {
+                id.push('-');
+            }
+            id.push_str(&word.to_ascii_lowercase());
+        }
+        id
+    }
+}
+
+impl Violation {
+    /// The violation message, without its diagnostic decorations.
+    pub fn message(&self) -> String {
+        self.report.to_string()
+    }
+
+    pub fn help(&self) -> Option<String> {
+        self.report.help().map(|help| help.to_string())
+    }
+
+    /// Location of the violation relative to the current directory if possible.
+    pub fn relative_location(&self) -> String {
+        std::env::current_dir()
+            .ok()
+            .and_then(|base| self.location.strip_prefix(base).ok())
+            .unwrap_or(&self.location)
+            .to_string_lossy()
+            .to_string()
     }
 }
 
 impl fmt::Display for AssertionResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        writeln!(
-            f,
-            "Expected {} but found {} violations",
-            self.expected,
-            self.actual.len()
-        )?;
-        for report in &self.actual {
-            writeln!(f, "{report:?}")?;
-        }
-        Ok(())
+        write!(f, "{}", self.render(&ReportConfig::default()))
     }
 }
 
@@ -62,11 +138,27 @@ pub(crate) fn get_field_span(field: &Field, sample: &str) -> SourceSpan {
         .unwrap_or_else(|| SourceSpan::from(field.span))
 }
 
+/// Location relative to the current directory, trees loaded from elsewhere keep their full path.
 pub(crate) fn get_relative_location(location: &Path) -> String {
-    let base = std::env::current_dir().expect("current dir");
-    location
-        .strip_prefix(base)
-        .expect("should be in current_dir")
+    std::env::current_dir()
+        .ok()
+        .and_then(|base| location.strip_prefix(base).ok())
+        .unwrap_or(location)
         .to_string_lossy()
         .to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use crate::assertion_result::AssertionResult;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_derive_rule_id_from_expected() {
+        let mut result = AssertionResult::new();
+        result.push_expected("Structs that have simple name 'Foo' to be public");
+
+        assert_that!(result.rule_id())
+            .is_equal_to("structs-that-have-simple-name-foo-to-be-public".to_owned());
+    }
+}
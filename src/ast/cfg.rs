@@ -0,0 +1,385 @@
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// A configuration predicate, as written in `#[cfg(...)]` attributes, see
+/// <https://doc.rust-lang.org/reference/conditional-compilation.html>.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CfgPredicate {
+    Option(CfgOption),
+    All(Vec<CfgPredicate>),
+    Any(Vec<CfgPredicate>),
+    Not(Box<CfgPredicate>),
+}
+
+/// A configuration option, either a name like `test` or a key-value pair like
+/// `feature = "serde"`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CfgOption {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// The configuration options set for a build, every other option is unset.
+///
+/// **Example:**
+/// ```rust
+/// use archunit_rs::CfgSet;
+///
+/// let linux_with_serde = CfgSet::new()
+///     .flag("unix")
+///     .value("target_os", "linux")
+///     .feature("serde");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct CfgSet {
+    options: BTreeSet<CfgOption>,
+}
+
+impl CfgPredicate {
+    /// Evaluate the predicate, `known` gives the state of the options that are known to be
+    /// set or unset. Returns `None` if the result depends on unknown options.
+    pub fn evaluate<F>(&self, known: &F) -> Option<bool>
+    where
+        F: Fn(&CfgOption) -> Option<bool>,
+    {
+        match self {
+            CfgPredicate::Option(option) => known(option),
+            CfgPredicate::All(predicates) => {
+                let mut result = Some(true);
+                for predicate in predicates {
+                    match predicate.evaluate(known) {
+                        Some(false) => return Some(false),
+                        None => result = None,
+                        Some(true) => {}
+                    }
+                }
+                result
+            }
+            CfgPredicate::Any(predicates) => {
+                let mut result = Some(false);
+                for predicate in predicates {
+                    match predicate.evaluate(known) {
+                        Some(true) => return Some(true),
+                        None => result = None,
+                        Some(false) => {}
+                    }
+                }
+                result
+            }
+            CfgPredicate::Not(predicate) => predicate.evaluate(known).map(|value| !value),
+        }
+    }
+
+    /// The cargo features mentioned in this predicate.
+    pub fn features(&self) -> Vec<&str> {
+        match self {
+            CfgPredicate::Option(CfgOption {
+                name,
+                value: Some(feature),
+            }) if name == "feature" => vec![feature.as_str()],
+            CfgPredicate::Option(_) => vec![],
+            CfgPredicate::All(predicates) | CfgPredicate::Any(predicates) => {
+                predicates.iter().flat_map(CfgPredicate::features).collect()
+            }
+            CfgPredicate::Not(predicate) => predicate.features(),
+        }
+    }
+
+    /// Whether the item compiled on this predicate is only compiled when `feature` is enabled.
+    pub fn requires_feature(&self, feature: &str) -> bool {
+        let disabled = |option: &CfgOption| {
+            (option.name == "feature" && option.value.as_deref() == Some(feature)).then_some(false)
+        };
+
+        self.evaluate(&disabled) == Some(false)
+    }
+
+    fn from_nested(nested: &NestedMeta) -> Option<Self> {
+        match nested {
+            NestedMeta::Meta(Meta::Path(path)) => Some(CfgPredicate::Option(CfgOption::new(
+                path.get_ident()?,
+                None,
+            ))),
+            NestedMeta::Meta(Meta::NameValue(name_value)) => match &name_value.lit {
+                Lit::Str(value) => Some(CfgPredicate::Option(CfgOption::new(
+                    name_value.path.get_ident()?,
+                    Some(value.value()),
+                ))),
+                _ => None,
+            },
+            NestedMeta::Meta(Meta::List(list)) => {
+                let predicates = list
+                    .nested
+                    .iter()
+                    .map(CfgPredicate::from_nested)
+                    .collect::<Option<Vec<_>>>()?;
+
+                match list.path.get_ident()?.to_string().as_str() {
+                    "all" => Some(CfgPredicate::All(predicates)),
+                    "any" => Some(CfgPredicate::Any(predicates)),
+                    "not" if predicates.len() == 1 => {
+                        let predicate = predicates.into_iter().next()?;
+                        Some(CfgPredicate::Not(Box::new(predicate)))
+                    }
+                    _ => None,
+                }
+            }
+            NestedMeta::Lit(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for CfgPredicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let join = |predicates: &[CfgPredicate]| {
+            predicates
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match self {
+            CfgPredicate::Option(option) => write!(f, "{option}"),
+            CfgPredicate::All(predicates) => write!(f, "all({})", join(predicates)),
+            CfgPredicate::Any(predicates) => write!(f, "any({})", join(predicates)),
+            CfgPredicate::Not(predicate) => write!(f, "not({predicate})"),
+        }
+    }
+}
+
+impl CfgOption {
+    fn new(name: &syn::Ident, value: Option<String>) -> Self {
+        CfgOption {
+            name: name.to_string(),
+            value,
+        }
+    }
+}
+
+impl fmt::Display for CfgOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{} = \"{value}\"", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl FromStr for CfgOption {
+    type Err = String;
+
+    /// Parse an option written like in `--cfg` flags: `unix` or `feature = "serde"`.
+    fn from_str(option: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid cfg option '{option}', expected 'name' or 'name = \"value\"'");
+        let is_ident = |name: &str| {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        };
+
+        let (name, value) = match option.split_once('=') {
+            Some((name, value)) => {
+                let value = value
+                    .trim()
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .ok_or_else(invalid)?;
+                (name.trim(), Some(value.to_owned()))
+            }
+            None => (option.trim(), None),
+        };
+
+        if !is_ident(name) {
+            return Err(invalid());
+        }
+
+        Ok(CfgOption {
+            name: name.to_owned(),
+            value,
+        })
+    }
+}
+
+impl TryFrom<String> for CfgOption {
+    type Error = String;
+
+    fn try_from(option: String) -> Result<Self, Self::Error> {
+        option.parse()
+    }
+}
+
+impl CfgSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a name option like `test` or `unix`.
+    pub fn flag<S: Into<String>>(self, name: S) -> Self {
+        self.option(CfgOption {
+            name: name.into(),
+            value: None,
+        })
+    }
+
+    /// Set a key-value option like `target_os = "linux"`.
+    pub fn value<K: Into<String>, V: Into<String>>(self, key: K, value: V) -> Self {
+        self.option(CfgOption {
+            name: key.into(),
+            value: Some(value.into()),
+        })
+    }
+
+    /// Enable a cargo feature, a shorthand for `value("feature", name)`.
+    pub fn feature<S: Into<String>>(self, name: S) -> Self {
+        self.value("feature", name)
+    }
+
+    pub fn option(mut self, option: CfgOption) -> Self {
+        self.options.insert(option);
+        self
+    }
+
+    pub fn is_set(&self, option: &CfgOption) -> bool {
+        self.options.contains(option)
+    }
+}
+
+/// Whether an item compiled on all of `predicates` requires `feature`.
+pub(crate) fn requires_feature<'a, I>(predicates: I, feature: &str) -> bool
+where
+    I: IntoIterator<Item = &'a CfgPredicate>,
+{
+    predicates
+        .into_iter()
+        .any(|predicate| predicate.requires_feature(feature))
+}
+
+/// The predicate an item is conditionally compiled on: its `#[cfg]` attributes and the `cfg`
+/// attributes nested in `#[cfg_attr]`, or `None` if it is always compiled.
+pub(crate) fn attributes_cfg<'a, I>(attrs: I) -> Option<CfgPredicate>
+where
+    I: IntoIterator<Item = &'a Attribute>,
+{
+    let mut predicates = vec![];
+
+    for attr in attrs {
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            continue;
+        };
+
+        if list.path.is_ident("cfg") {
+            predicates.extend(list.nested.first().and_then(CfgPredicate::from_nested));
+        } else if list.path.is_ident("cfg_attr") {
+            let mut nested = list.nested.iter();
+            let Some(condition) = nested.next().and_then(CfgPredicate::from_nested) else {
+                continue;
+            };
+
+            // `#[cfg_attr(a, cfg(b))]` only compiles the item when `a` implies `b`
+            for attr in nested {
+                if let NestedMeta::Meta(Meta::List(cfg)) = attr {
+                    if cfg.path.is_ident("cfg") {
+                        if let Some(predicate) =
+                            cfg.nested.first().and_then(CfgPredicate::from_nested)
+                        {
+                            predicates.push(CfgPredicate::Any(vec![
+                                CfgPredicate::Not(Box::new(condition.clone())),
+                                predicate,
+                            ]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    match predicates.len() {
+        0 => None,
+        1 => predicates.pop(),
+        _ => Some(CfgPredicate::All(predicates)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::cfg::{attributes_cfg, CfgOption, CfgPredicate, CfgSet};
+    use speculoos::prelude::*;
+    use syn::ItemStruct;
+
+    fn cfg_of(item: &str) -> Option<CfgPredicate> {
+        let item: ItemStruct = syn::parse_str(item).expect("valid struct");
+        attributes_cfg(&item.attrs)
+    }
+
+    #[test]
+    fn should_parse_cfg_predicates() {
+        let cfg = cfg_of(
+            r#"
+            #[cfg(all(test, feature = "x"))]
+            #[cfg_attr(unix, cfg(not(target_os = "macos")))]
+            #[cfg_attr(test, derive(Debug))]
+            struct Foo;
+            "#,
+        );
+
+        assert_that!(cfg.map(|cfg| cfg.to_string()))
+            .is_some()
+            .is_equal_to(
+                r#"all(all(test, feature = "x"), any(not(unix), not(target_os = "macos")))"#
+                    .to_owned(),
+            );
+        assert_that!(cfg_of("#[derive(Debug)] struct Foo;")).is_none();
+    }
+
+    #[test]
+    fn should_evaluate_with_unknown_options() {
+        let cfg = cfg_of(r#"#[cfg(any(test, feature = "x"))] struct Foo;"#).expect("cfg");
+        let test_unset = |option: &CfgOption| (option.name == "test").then_some(false);
+        let test_set = |option: &CfgOption| (option.name == "test").then_some(true);
+
+        assert_that!(cfg.evaluate(&test_unset)).is_none();
+        assert_that!(cfg.evaluate(&test_set)).is_some().is_true();
+    }
+
+    #[test]
+    fn should_evaluate_against_cfg_set() {
+        let cfg = cfg_of(r#"#[cfg(all(unix, not(feature = "x")))] struct Foo;"#).expect("cfg");
+        let evaluate = |set: CfgSet| cfg.evaluate(&|option: &CfgOption| Some(set.is_set(option)));
+
+        assert_that!(evaluate(CfgSet::new().flag("unix"))).is_equal_to(Some(true));
+        assert_that!(evaluate(CfgSet::new().flag("unix").feature("x"))).is_equal_to(Some(false));
+        assert_that!(evaluate(CfgSet::new())).is_equal_to(Some(false));
+    }
+
+    #[test]
+    fn should_require_features() {
+        let cfg =
+            cfg_of(r#"#[cfg(all(unix, any(feature = "a", not(feature = "b"))))] struct Foo;"#)
+                .expect("cfg");
+        let gated = cfg_of(r#"#[cfg(all(unix, feature = "a"))] struct Foo;"#).expect("cfg");
+
+        assert_that!(cfg.features()).is_equal_to(vec!["a", "b"]);
+        assert_that!(cfg.requires_feature("a")).is_false();
+        assert_that!(gated.requires_feature("a")).is_true();
+        assert_that!(gated.requires_feature("unix")).is_false();
+    }
+
+    #[test]
+    fn should_parse_cfg_options() {
+        assert_that!("feature = \"serde\"".parse::<CfgOption>())
+            .is_ok()
+            .is_equal_to(CfgOption {
+                name: "feature".to_owned(),
+                value: Some("serde".to_owned()),
+            });
+        assert_that!("unix".parse::<CfgOption>()).is_ok();
+        assert_that!("feature = serde".parse::<CfgOption>()).is_err();
+    }
+}
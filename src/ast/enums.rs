@@ -1,4 +1,8 @@
+use crate::ast::cfg::{attributes_cfg, CfgPredicate};
+use crate::ast::structs::{Field, StructKind};
+use crate::ast::types::Imports;
 use crate::ast::{CodeSpan, ItemPath, Visibility};
+use quote::ToTokens;
 use std::path::{Path, PathBuf};
 use syn::spanned::Spanned;
 use syn::{ItemEnum, Meta, NestedMeta};
@@ -12,10 +16,68 @@ pub struct Enum {
     pub derives: Vec<String>,
     pub visibility: Visibility,
     pub path: ItemPath,
+    pub cfg: Option<CfgPredicate>,
+    pub variants: Vec<Variant>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Variant {
+    pub ident: String,
+    pub span: CodeSpan,
+    /// Unit, tuple or named variant, using the struct terminology.
+    pub kind: StructKind,
+    pub fields: Vec<Field>,
+    /// The explicit discriminant as written, e.g. `0x10`.
+    pub discriminant: Option<String>,
+    /// Paths of the attributes on the variant, e.g. `error` or `serde`.
+    pub attributes: Vec<String>,
+}
+
+impl Variant {
+    fn from_syn(variant: &syn::Variant, imports: &Imports) -> Self {
+        Self {
+            ident: variant.ident.to_string(),
+            span: variant.ident.span().into(),
+            kind: StructKind::from(&variant.fields),
+            fields: variant
+                .fields
+                .iter()
+                .map(|field| Field::from_syn(field, imports))
+                .collect(),
+            discriminant: variant
+                .discriminant
+                .as_ref()
+                .map(|(_, expr)| expr.to_token_stream().to_string()),
+            attributes: variant
+                .attrs
+                .iter()
+                .map(|attr| {
+                    attr.path
+                        .segments
+                        .iter()
+                        .map(|segment| segment.ident.to_string())
+                        .collect::<Vec<_>>()
+                        .join("::")
+                })
+                .collect(),
+        }
+    }
+
+    /// The first field whose type, or one of the types it refers to, matches `pattern`.
+    pub fn carries_type_matching(&self, pattern: &str) -> Option<&Field> {
+        self.fields
+            .iter()
+            .find(|field| field.has_type_matching(pattern))
+    }
 }
 
 impl Enum {
-    pub(crate) fn from_syn(enum_: &ItemEnum, path: &ItemPath, real_path: &Path) -> Self {
+    pub(crate) fn from_syn(
+        enum_: &ItemEnum,
+        path: &ItemPath,
+        real_path: &Path,
+        imports: &Imports,
+    ) -> Self {
         let ident = enum_.ident.to_string();
         let path = path.join(&ident);
         let derives = enum_
@@ -28,31 +90,27 @@ impl Enum {
                     .map(|ident| ident == "derive")
                     .unwrap_or(false)
             })
-            .filter_map(|attr| {
-                let attr = attr.parse_meta().expect("failed to parse derive attribute");
-
-                match attr {
-                    Meta::List(list) => {
-                        let derives: Vec<String> = list
-                            .nested
-                            .iter()
-                            .filter_map(|nested| match nested {
-                                NestedMeta::Meta(meta) => match meta {
-                                    Meta::Path(path) => Some(
-                                        path.segments
-                                            .iter()
-                                            .map(|segment| segment.ident.to_string()),
-                                    ),
-                                    _ => None,
-                                },
-                                NestedMeta::Lit(_) => None,
-                            })
-                            .flatten()
-                            .collect();
-                        Some(derives)
-                    }
-                    _ => None,
+            .filter_map(|attr| match attr.parse_meta().ok()? {
+                Meta::List(list) => {
+                    let derives: Vec<String> = list
+                        .nested
+                        .iter()
+                        .filter_map(|nested| match nested {
+                            NestedMeta::Meta(meta) => match meta {
+                                Meta::Path(path) => Some(
+                                    path.segments
+                                        .iter()
+                                        .map(|segment| segment.ident.to_string()),
+                                ),
+                                _ => None,
+                            },
+                            NestedMeta::Lit(_) => None,
+                        })
+                        .flatten()
+                        .collect();
+                    Some(derives)
                 }
+                _ => None,
             })
             .flatten()
             .collect();
@@ -64,6 +122,12 @@ impl Enum {
             derives,
             visibility: Visibility::from_syn(&enum_.vis),
             path,
+            cfg: attributes_cfg(&enum_.attrs),
+            variants: enum_
+                .variants
+                .iter()
+                .map(|variant| Variant::from_syn(variant, imports))
+                .collect(),
         }
     }
 }
@@ -80,4 +144,43 @@ impl Enum {
     pub fn derives(&self, trait_: &str) -> bool {
         self.derives.contains(&trait_.to_owned())
     }
+
+    /// Whether none of the variants carry data, like a C-like enum.
+    pub fn is_fieldless(&self) -> bool {
+        self.variants
+            .iter()
+            .all(|variant| variant.fields.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::enums::Enum;
+    use crate::ast::structs::StructKind;
+    use crate::ast::types::Imports;
+    use crate::ast::ItemPath;
+    use speculoos::prelude::*;
+    use std::path::Path;
+
+    #[test]
+    fn should_record_variants() {
+        let module = ItemPath::new("app".to_owned());
+        let item = syn::parse_str(
+            "enum Code {
+                #[default]
+                Ok = 0,
+                #[error(\"io\")]
+                Io(std::io::Error),
+            }",
+        )
+        .expect("valid enum");
+
+        let enum_ = Enum::from_syn(&item, &module, Path::new(""), &Imports::of(&[], &module));
+
+        assert_that!(enum_.is_fieldless()).is_false();
+        assert_that!(enum_.variants[0].discriminant).is_equal_to(Some("0".to_owned()));
+        assert_that!(enum_.variants[0].attributes).is_equal_to(vec!["default".to_owned()]);
+        assert_that!(enum_.variants[1].kind).is_equal_to(StructKind::Tuple);
+        assert_that!(enum_.variants[1].carries_type_matching("std::io::*")).is_some();
+    }
 }
@@ -0,0 +1,125 @@
+use crate::ast::cfg::{attributes_cfg, CfgPredicate};
+use crate::ast::{CodeSpan, ItemPath, Visibility};
+use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::{Block, ImplItem, ItemFn, ItemImpl, Signature, Type};
+
+/// A free function or a method of an impl block.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Function {
+    pub span: CodeSpan,
+    pub real_path: PathBuf,
+    pub ident: String,
+    /// `module::function` for free functions, `module::Type::method` for methods.
+    pub path: ItemPath,
+    /// Name of the implemented type for methods.
+    pub self_ty: Option<String>,
+    pub visibility: Visibility,
+    /// Number of parameters, including the `self` receiver.
+    pub parameters: usize,
+    /// Number of lines from the signature to the end of the body.
+    pub lines: usize,
+    pub cfg: Option<CfgPredicate>,
+}
+
+impl Function {
+    pub(crate) fn from_syn(item_fn: &ItemFn, path: &ItemPath, real_path: &Path) -> Self {
+        Self::new(
+            &item_fn.sig,
+            &item_fn.block,
+            &item_fn.vis,
+            path,
+            None,
+            real_path,
+            attributes_cfg(&item_fn.attrs),
+        )
+    }
+
+    /// The methods of an impl block, they are compiled on the cfg of the block as well.
+    pub(crate) fn from_impl(imp: &ItemImpl, path: &ItemPath, real_path: &Path) -> Vec<Self> {
+        let self_ty = match &*imp.self_ty {
+            Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string()),
+            _ => None,
+        };
+
+        imp.items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Method(method) => Some(Self::new(
+                    &method.sig,
+                    &method.block,
+                    &method.vis,
+                    path,
+                    self_ty.clone(),
+                    real_path,
+                    attributes_cfg(imp.attrs.iter().chain(&method.attrs)),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn new(
+        sig: &Signature,
+        block: &Block,
+        vis: &syn::Visibility,
+        path: &ItemPath,
+        self_ty: Option<String>,
+        real_path: &Path,
+        cfg: Option<CfgPredicate>,
+    ) -> Self {
+        let ident = sig.ident.to_string();
+        let path = match &self_ty {
+            Some(self_ty) => path.join(self_ty).join(&ident),
+            None => path.join(&ident),
+        };
+        let lines = block.span().end().line + 1 - sig.span().start().line;
+
+        Self {
+            span: sig.ident.span().into(),
+            real_path: real_path.to_path_buf(),
+            ident,
+            path,
+            self_ty,
+            visibility: Visibility::from_syn(vis),
+            parameters: sig.inputs.len(),
+            lines,
+            cfg,
+        }
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.visibility == Visibility::Public
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::functions::Function;
+    use crate::ast::ItemPath;
+    use speculoos::prelude::*;
+    use std::path::Path;
+
+    #[test]
+    fn should_count_parameters_and_lines() {
+        let imp: syn::ItemImpl = syn::parse_str(
+            "impl Order {
+                pub fn save(&self, db: &mut Db) {
+                    db.insert(self.id);
+                }
+            }",
+        )
+        .expect("valid impl");
+
+        let functions = Function::from_impl(&imp, &ItemPath::new("app".to_owned()), Path::new(""));
+
+        assert_that!(functions).has_length(1);
+        assert_that!(functions[0].path.as_str()).is_equal_to("app::Order::save");
+        assert_that!(functions[0].parameters).is_equal_to(2);
+        assert_that!(functions[0].lines).is_equal_to(3);
+    }
+}
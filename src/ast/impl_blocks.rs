@@ -1,5 +1,7 @@
-use crate::ast::ItemPath;
+use crate::ast::cfg::{attributes_cfg, CfgPredicate};
+use crate::ast::{CodeSpan, ItemPath};
 use std::fmt::Debug;
+use syn::spanned::Spanned;
 use syn::{ItemImpl, Type};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -8,6 +10,9 @@ pub struct Impl {
     pub is_unsafe: bool,
     pub self_ty: ItemPath,
     pub trait_impl: Option<ItemPath>,
+    pub cfg: Option<CfgPredicate>,
+    /// Span of the implemented type.
+    pub span: CodeSpan,
 }
 
 impl From<(&ItemImpl, &ItemPath)> for Impl {
@@ -46,6 +51,8 @@ impl From<(&ItemImpl, &ItemPath)> for Impl {
             self_ty,
             path,
             trait_impl,
+            cfg: attributes_cfg(&imp.attrs),
+            span: imp.self_ty.span().into(),
         }
     }
 }
@@ -0,0 +1,163 @@
+use crate::ast::{CodeSpan, LineColumn};
+use miette::Diagnostic;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use thiserror::Error;
+use toml::Spanned;
+
+/// Error raised while reading a `Cargo.toml` manifest.
+#[derive(Error, Debug, Diagnostic)]
+pub enum ManifestError {
+    #[error("Unable to read {path}")]
+    #[diagnostic(code(archunit::manifest::io))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("No Cargo.toml found for {dir}")]
+    #[diagnostic(code(archunit::manifest::missing))]
+    Missing { dir: PathBuf },
+    #[error("Invalid manifest {path}: {message}")]
+    #[diagnostic(code(archunit::manifest::invalid))]
+    Invalid { path: PathBuf, message: String },
+}
+
+pub(crate) type RawDependencies = BTreeMap<Spanned<String>, toml::Value>;
+
+/// The parts of a manifest archunit cares about, unknown keys are ignored.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct RawManifest {
+    pub(crate) package: Option<RawPackage>,
+    pub(crate) lib: Option<RawTarget>,
+    #[serde(default)]
+    pub(crate) bin: Vec<RawTarget>,
+    #[serde(default)]
+    pub(crate) example: Vec<RawTarget>,
+    #[serde(default)]
+    pub(crate) test: Vec<RawTarget>,
+    pub(crate) workspace: Option<RawWorkspace>,
+    #[serde(default)]
+    pub(crate) dependencies: RawDependencies,
+    #[serde(default)]
+    pub(crate) dev_dependencies: RawDependencies,
+    #[serde(default)]
+    pub(crate) build_dependencies: RawDependencies,
+    #[serde(default)]
+    pub(crate) target: BTreeMap<String, RawPlatform>,
+    #[serde(default)]
+    pub(crate) features: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct RawPackage {
+    pub(crate) name: String,
+    pub(crate) autobins: Option<bool>,
+    pub(crate) autoexamples: Option<bool>,
+    pub(crate) autotests: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct RawTarget {
+    pub(crate) name: Option<String>,
+    pub(crate) path: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct RawWorkspace {
+    #[serde(default)]
+    pub(crate) members: Vec<String>,
+    #[serde(default)]
+    pub(crate) exclude: Vec<String>,
+}
+
+/// Platform specific dependencies, declared under `[target.'cfg(...)'.dependencies]`.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct RawPlatform {
+    #[serde(default)]
+    pub(crate) dependencies: RawDependencies,
+    #[serde(default)]
+    pub(crate) dev_dependencies: RawDependencies,
+    #[serde(default)]
+    pub(crate) build_dependencies: RawDependencies,
+}
+
+pub(crate) fn read_to_string(path: &Path) -> Result<String, ManifestError> {
+    fs::read_to_string(path).map_err(|source| ManifestError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub(crate) fn read_manifest(path: &Path) -> Result<RawManifest, ManifestError> {
+    parse_manifest(path, &read_to_string(path)?)
+}
+
+pub(crate) fn parse_manifest(path: &Path, content: &str) -> Result<RawManifest, ManifestError> {
+    toml::from_str(content).map_err(|err| ManifestError::Invalid {
+        path: path.to_path_buf(),
+        message: err.message().to_owned(),
+    })
+}
+
+/// The features of the crate located at `manifest_dir`: the `[features]` table and the
+/// implicit features of optional dependencies not referenced with the `dep:` syntax.
+pub(crate) fn declared_features(manifest_dir: &Path) -> Result<BTreeSet<String>, ManifestError> {
+    let manifest = read_manifest(&manifest_dir.join("Cargo.toml"))?;
+    let explicit_deps: BTreeSet<&str> = manifest
+        .features
+        .values()
+        .flatten()
+        .filter_map(|enabled| enabled.strip_prefix("dep:"))
+        .collect();
+
+    let optional_deps = [&manifest.dependencies]
+        .into_iter()
+        .chain(
+            manifest
+                .target
+                .values()
+                .map(|platform| &platform.dependencies),
+        )
+        .flatten()
+        .filter(|(_, dependency)| {
+            dependency
+                .get("optional")
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false)
+        })
+        .map(|(name, _)| name.get_ref().as_str())
+        .filter(|name| !explicit_deps.contains(name));
+
+    Ok(manifest
+        .features
+        .keys()
+        .map(String::as_str)
+        .chain(optional_deps)
+        .map(str::to_owned)
+        .collect())
+}
+
+/// Convert a byte range into a line/column span, lines start at 1 and columns at 0 like
+/// the spans of the parsed sources.
+pub(crate) fn code_span(content: &str, range: Range<usize>) -> CodeSpan {
+    let line_column = |offset: usize| {
+        let before = &content[..offset];
+        LineColumn {
+            line: before.matches('\n').count() + 1,
+            column: before
+                .rfind('\n')
+                .map_or(before.len(), |newline| before.len() - newline - 1),
+        }
+    };
+
+    CodeSpan {
+        start: line_column(range.start),
+        end: line_column(range.end),
+    }
+}
@@ -2,24 +2,36 @@ use miette::SourceSpan;
 use std::fmt;
 use std::fmt::Formatter;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use crate::ast::cfg::CfgPredicate;
 use crate::ast::enums::Enum;
+use crate::ast::functions::Function;
 use impl_blocks::Impl;
 use once_cell::sync::OnceCell;
 use structs::Struct;
 use syn::__private::Span;
 use syn::spanned::Spanned;
-use syn::{Item, ItemMod, ItemUse, Meta, UseTree};
+use syn::{Item, ItemMod, ItemUse, UseTree};
+use traits::Trait;
 
-use crate::ast::parse::ModuleAst;
+use crate::ast::parse::{cargo_env, LoadError, LoadOptions, ModuleAst};
+use crate::ast::targets::{CrateTargets, Target, TargetKind};
 use crate::ast::visitor::{ModuleOrCrateRoot, SynModuleTree};
 use crate::rule::pattern::PathPattern;
 
+pub mod cfg;
 pub mod enums;
+pub mod functions;
 pub mod impl_blocks;
+pub mod manifest;
 pub(crate) mod parse;
 pub mod structs;
+pub mod targets;
+pub mod traits;
+pub(crate) mod types;
 pub mod visitor;
+pub mod workspace;
 
 pub fn module_tree() -> &'static ModuleTree {
     static MODULE_TREE: OnceCell<ModuleTree> = OnceCell::new();
@@ -29,7 +41,8 @@ pub fn module_tree() -> &'static ModuleTree {
 #[derive(Debug)]
 pub struct ModuleTree {
     pub span: Option<CodeSpan>,
-    pub cfg_attr: Vec<String>,
+    /// The `cfg` predicate the module is compiled on, if any.
+    pub cfg: Option<CfgPredicate>,
     pub dependencies: Vec<ModuleUse>,
     pub real_path: PathBuf,
     pub path: ItemPath,
@@ -37,12 +50,17 @@ pub struct ModuleTree {
     pub visibility: Visibility,
     pub structs: Vec<Struct>,
     pub enums: Vec<Enum>,
+    pub traits: Vec<Trait>,
     pub impl_blocks: Vec<Impl>,
+    /// Free functions and methods of the impl blocks.
+    pub functions: Vec<Function>,
+    /// Lines of the module file, or of the inline module block.
+    pub lines: usize,
     pub declaration: Option<ModuleDeclaration>,
     pub submodules: Vec<ModuleTree>,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct ItemPath {
     inner: String,
 }
@@ -92,6 +110,14 @@ impl ItemPath {
         PathPattern::from(pattern).matches_struct_path(&self.inner)
     }
 
+    /// The path of the enclosing module, or an empty path for the crate root.
+    pub fn parent(&self) -> ItemPath {
+        match self.inner.rsplit_once("::") {
+            Some((parent, _)) => ItemPath::new(parent.to_owned()),
+            None => ItemPath::empty(),
+        }
+    }
+
     pub fn name(&self) -> &str {
         if let Some((_, name)) = self.inner.rsplit_once("::") {
             name
@@ -136,40 +162,29 @@ fn get_item_module_declaration(item: &ItemMod, real_path: &Path) -> Vec<ModuleDe
     declarations
 }
 
-fn get_item_mod_cfg(item: &ItemMod) -> Vec<String> {
-    let mut cfg_attr = vec![];
-
-    for attr in &item.attrs {
-        let has_cfg = attr
-            .path
-            .segments
-            .iter()
-            .any(|segment| segment.ident == "cfg");
-
-        if has_cfg {
-            if let Ok(Meta::Path(path)) = attr.parse_args::<Meta>() {
-                for attr_value in path.segments {
-                    cfg_attr.push(attr_value.ident.to_string());
-                }
-            }
-        }
-    }
-    cfg_attr
-}
-
 #[derive(Debug)]
 pub struct ModuleUse {
     pub parts: String,
     pub span: CodeSpan,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CodeSpan {
     pub(crate) start: LineColumn,
     pub(crate) end: LineColumn,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+impl CodeSpan {
+    pub fn start(&self) -> LineColumn {
+        self.start
+    }
+
+    pub fn end(&self) -> LineColumn {
+        self.end
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LineColumn {
     pub line: usize,
     pub column: usize,
@@ -204,19 +219,24 @@ impl From<Span> for CodeSpan {
 
 impl From<&ItemUse> for ModuleUse {
     fn from(item_use: &ItemUse) -> Self {
-        let mut parts = String::new();
-        if let UseTree::Path(path) = &item_use.tree {
-            parts.push_str(&path.ident.to_string());
-            let mut tree = &*path.tree;
-            while let UseTree::Path(path) = tree {
-                parts.push_str("::");
-                parts.push_str(&path.ident.to_string());
-                tree = &*path.tree;
+        let mut parts = vec![];
+        let mut tree = &item_use.tree;
+        while let UseTree::Path(path) = tree {
+            parts.push(path.ident.to_string());
+            tree = &*path.tree;
+        }
+
+        // A bare `use other_crate;` uses the module it names
+        if parts.is_empty() {
+            match tree {
+                UseTree::Name(name) => parts.push(name.ident.to_string()),
+                UseTree::Rename(rename) => parts.push(rename.ident.to_string()),
+                _ => {}
             }
         }
 
         ModuleUse {
-            parts,
+            parts: parts.join("::"),
             span: CodeSpan {
                 start: LineColumn {
                     line: item_use.span().start().line,
@@ -232,43 +252,271 @@ impl From<&ItemUse> for ModuleUse {
 }
 
 impl ModuleTree {
+    /// Load the crate under test, from the manifest directory and package name cargo sets
+    /// when running tests.
+    ///
+    /// Panics with a rendered [`LoadError`] if the crate cannot be loaded, see
+    /// [`ModuleTree::try_load`].
     pub fn load() -> Self {
-        let mut ast = ModuleAst::load_crate_ast();
-        let syn_tree = ast.visit_modules(ModuleOrCrateRoot::CrateRoot);
-        syn_tree.to_tree(&ItemPath::empty(), None)
+        expect_loaded(Self::try_load())
+    }
+
+    /// Load the crate under test, with the [`LoadOptions`] read from the environment.
+    pub fn try_load() -> Result<Self, LoadError> {
+        let manifest_dir = cargo_env("CARGO_MANIFEST_DIR")?;
+        let crate_name = cargo_env("CARGO_PKG_NAME")?;
+        Self::try_load_from(manifest_dir, &crate_name, &LoadOptions::from_env())
+    }
+
+    /// Load the crate located at `manifest_dir`, its root module is named after `crate_name`.
+    ///
+    /// Rules are evaluated against the crate under test unless a tree is given explicitly,
+    /// see [`ArchRuleBuilder::that_in`](crate::rule::ArchRuleBuilder::that_in).
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ModuleTree;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static FIXTURE: Lazy<ModuleTree> =
+    ///     Lazy::new(|| ModuleTree::load_from(env!("CARGO_MANIFEST_DIR"), "archunit_rs"));
+    ///
+    /// assert_eq!(FIXTURE.ident, "archunit_rs");
+    /// ```
+    pub fn load_from<P: AsRef<Path>>(manifest_dir: P, crate_name: &str) -> Self {
+        expect_loaded(Self::try_load_from(
+            manifest_dir,
+            crate_name,
+            &LoadOptions::from_env(),
+        ))
+    }
+
+    /// Fallible version of [`ModuleTree::load_from`].
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::{LoadOptions, ModuleTree};
+    ///
+    /// let options = LoadOptions::default().skip_unparseable();
+    /// let tree = ModuleTree::try_load_from(env!("CARGO_MANIFEST_DIR"), "archunit_rs", &options)?;
+    ///
+    /// assert_eq!(tree.ident, "archunit_rs");
+    /// # Ok::<(), archunit_rs::LoadError>(())
+    /// ```
+    pub fn try_load_from<P: AsRef<Path>>(
+        manifest_dir: P,
+        crate_name: &str,
+        options: &LoadOptions,
+    ) -> Result<Self, LoadError> {
+        let manifest_dir = manifest_dir.as_ref();
+        let targets = CrateTargets::discover(manifest_dir)?;
+
+        match targets.default_target() {
+            Some(target) => Self::load_root(&target.path, crate_name, options),
+            None => Err(LoadError::NoTarget {
+                path: manifest_dir.to_path_buf(),
+            }),
+        }
+    }
+
+    /// Load the given target, its root module is named after the target.
+    pub fn load_target(target: &Target) -> Self {
+        expect_loaded(Self::try_load_target(target, &LoadOptions::from_env()))
+    }
+
+    /// Fallible version of [`ModuleTree::load_target`].
+    pub fn try_load_target(target: &Target, options: &LoadOptions) -> Result<Self, LoadError> {
+        Self::load_root(&target.path, &target.name, options)
+    }
+
+    /// Load every target of the given kinds of the crate located at `manifest_dir`, see
+    /// [`CrateTargets::discover`].
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::{ModuleTree, TargetKind};
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static BINARIES: Lazy<Vec<ModuleTree>> =
+    ///     Lazy::new(|| ModuleTree::load_targets(env!("CARGO_MANIFEST_DIR"), &[TargetKind::Bin]));
+    ///
+    /// assert_eq!(BINARIES[0].ident, "cargo_archunit");
+    /// ```
+    pub fn load_targets<P: AsRef<Path>>(manifest_dir: P, kinds: &[TargetKind]) -> Vec<Self> {
+        expect_loaded(Self::try_load_targets(
+            manifest_dir,
+            kinds,
+            &LoadOptions::from_env(),
+        ))
+    }
+
+    /// Fallible version of [`ModuleTree::load_targets`].
+    pub fn try_load_targets<P: AsRef<Path>>(
+        manifest_dir: P,
+        kinds: &[TargetKind],
+        options: &LoadOptions,
+    ) -> Result<Vec<Self>, LoadError> {
+        CrateTargets::discover(manifest_dir)?
+            .targets
+            .iter()
+            .filter(|target| kinds.contains(&target.kind))
+            .map(|target| Self::try_load_target(target, options))
+            .collect()
+    }
+
+    fn load_root(
+        crate_root: &Path,
+        crate_name: &str,
+        options: &LoadOptions,
+    ) -> Result<Self, LoadError> {
+        let ast = ModuleAst::load_crate_ast(crate_root, crate_name, options)?;
+        let syn_tree = ast.visit_modules(ModuleOrCrateRoot::CrateRoot(ast.name.clone()));
+        Ok(syn_tree.to_tree(&ItemPath::empty(), None))
     }
 
     pub(crate) fn is_public(&self) -> bool {
         self.visibility == Visibility::Public
     }
 
+    /// The `cfg` predicates of the modules enclosing `path`, from this module down to the
+    /// module at `path` included.
+    pub(crate) fn cfg_chain(&self, path: &ItemPath) -> Vec<&CfgPredicate> {
+        let mut chain: Vec<&CfgPredicate> = self.cfg.iter().collect();
+        let mut module = self;
+        while let Some(submodule) = module.submodules.iter().find(|submodule| {
+            path == &submodule.path || path.as_str().starts_with(&format!("{}::", submodule.path))
+        }) {
+            chain.extend(submodule.cfg.iter());
+            module = submodule;
+        }
+
+        chain
+    }
+
+    /// The module at `path`, this module or one of its descendants.
+    pub(crate) fn find(&self, path: &ItemPath) -> Option<&ModuleTree> {
+        if &self.path == path {
+            return Some(self);
+        }
+
+        self.submodules
+            .iter()
+            .find(|submodule| {
+                path == &submodule.path
+                    || path.as_str().starts_with(&format!("{}::", submodule.path))
+            })
+            .and_then(|submodule| submodule.find(path))
+    }
+
+    /// The directory of the manifest of the crate this module belongs to.
+    pub(crate) fn manifest_dir(&self) -> Option<&Path> {
+        self.real_path
+            .ancestors()
+            .skip(1)
+            .find(|dir| dir.join("Cargo.toml").exists())
+    }
+
     pub(crate) fn path_match(&self, pattern: &str) -> bool {
         self.path.match_module_path(pattern)
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+// Loading failures abort the architecture tests, render them like rule violations
+fn expect_loaded<T>(result: Result<T, LoadError>) -> T {
+    match result {
+        Ok(loaded) => loaded,
+        Err(err) => panic!("{:?}", miette::ErrReport::from(err)),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Visibility {
+    /// `pub`
     Public,
+    /// `pub(crate)`, `pub(in crate)` or the `crate` shorthand
     Crate,
-    Restricted,
+    /// `pub(super)` or `pub(in path)`, with the path as written, e.g. `super` or `crate::domain`
+    Restricted(String),
+    /// No visibility, or `pub(self)`
     Inherited,
 }
 
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Visibility::Public => write!(f, "pub"),
+            Visibility::Crate => write!(f, "pub(crate)"),
+            Visibility::Restricted(path) if path == "super" => write!(f, "pub(super)"),
+            Visibility::Restricted(path) => write!(f, "pub(in {path})"),
+            Visibility::Inherited => write!(f, "private"),
+        }
+    }
+}
+
+/// Parses a visibility as written in the source, `private` stands for no visibility.
+impl FromStr for Visibility {
+    type Err = syn::Error;
+
+    fn from_str(vis: &str) -> Result<Self, Self::Err> {
+        if vis == "private" {
+            return Ok(Visibility::Inherited);
+        }
+
+        syn::parse_str::<syn::Visibility>(vis).map(|vis| Visibility::from_syn(&vis))
+    }
+}
+
 impl Visibility {
     pub fn is_public(&self) -> bool {
         self == &Visibility::Public
     }
 
+    /// No visibility, or `pub(self)`.
+    pub fn is_private(&self) -> bool {
+        self == &Visibility::Inherited
+    }
+
     pub fn is_restricted(&self) -> bool {
-        self == &Visibility::Restricted
+        matches!(self, Visibility::Restricted(_))
+    }
+
+    pub fn is_crate_visible(&self) -> bool {
+        self == &Visibility::Crate
+    }
+
+    /// Whether the item is visible to no more code than with `other`, restricted visibilities
+    /// are considered narrower than `pub(crate)` whatever their path.
+    pub fn is_at_most(&self, other: &Visibility) -> bool {
+        self.rank() <= other.rank()
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Visibility::Inherited => 0,
+            Visibility::Restricted(_) => 1,
+            Visibility::Crate => 2,
+            Visibility::Public => 3,
+        }
     }
 
     fn from_syn(vis: &syn::Visibility) -> Self {
         match vis {
             syn::Visibility::Public(_) => Visibility::Public,
             syn::Visibility::Crate(_) => Visibility::Crate,
-            syn::Visibility::Restricted(_) => Visibility::Restricted,
+            syn::Visibility::Restricted(restricted) => {
+                let path = restricted
+                    .path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                match path.as_str() {
+                    "crate" => Visibility::Crate,
+                    "self" => Visibility::Inherited,
+                    _ => Visibility::Restricted(path),
+                }
+            }
             syn::Visibility::Inherited => Visibility::Inherited,
         }
     }
@@ -300,29 +548,32 @@ impl SynModuleTree<'_> {
         let path = path.join(ident.as_str());
         let structs = self.module.structs(&path);
         let enums = self.module.enums(&path);
+        let traits = self.module.traits(&path);
         let impl_blocks = self.module.impls(&path);
+        let functions = self.module.functions(&path);
+        let lines = self.module.lines();
         let real_path = self.module.real_path();
-        let cfg_attr = self.module.cfg_attr();
+        let cfg = self.module.cfg();
         let mut module_declarations = self.module.module_declarations();
 
         let submodules = self
             .submodules
             .iter()
             .map(|syn_module| {
-                let (idx, _) = module_declarations
+                // Submodules are visited from the declarations of this module, only their span
+                // would be lost if one was missing
+                let declaration = module_declarations
                     .iter()
-                    .enumerate()
-                    .find(|(_idx, declaration)| syn_module.module.ident() == declaration.ident)
-                    .expect("module should be declared");
-                let declaration = module_declarations.remove(idx);
-                syn_module.to_tree(&path, Some(declaration))
+                    .position(|declaration| syn_module.module.ident() == declaration.ident)
+                    .map(|idx| module_declarations.remove(idx));
+                syn_module.to_tree(&path, declaration)
             })
             .collect();
         let span = self.module.span();
 
         ModuleTree {
             span,
-            cfg_attr,
+            cfg,
             dependencies,
             real_path,
             path,
@@ -330,7 +581,10 @@ impl SynModuleTree<'_> {
             visibility,
             structs,
             enums,
+            traits,
             impl_blocks,
+            functions,
+            lines,
             declaration,
             submodules,
         }
@@ -339,9 +593,23 @@ impl SynModuleTree<'_> {
 
 #[cfg(test)]
 mod test {
-    use crate::ast::ItemPath;
+    use crate::ast::{ItemPath, Visibility};
     use speculoos::prelude::*;
 
+    #[test]
+    fn should_distinguish_restricted_visibilities() {
+        let parse = |vis: &str| vis.parse::<Visibility>().expect("valid visibility");
+
+        assert_that!(parse("pub(crate)")).is_equal_to(Visibility::Crate);
+        assert_that!(parse("pub(self)")).is_equal_to(Visibility::Inherited);
+        assert_that!(parse("private")).is_equal_to(Visibility::Inherited);
+        assert_that!(parse("pub(in crate::domain)"))
+            .is_equal_to(Visibility::Restricted("crate::domain".to_owned()));
+        assert_that!(parse("pub(super)").to_string().as_str()).is_equal_to("pub(super)");
+        assert_that!(parse("pub(super)").is_at_most(&Visibility::Crate)).is_true();
+        assert_that!(Visibility::Public.is_at_most(&Visibility::Crate)).is_false();
+    }
+
     #[test]
     fn should_reside_in_works() {
         let path = ItemPath {
@@ -1,12 +1,13 @@
-use std::fs::File;
-use std::{
-    env,
-    io::Read,
-    path::{Path, PathBuf},
-    str::FromStr,
-};
+use crate::assertion_result::get_relative_location;
+use crate::ast::manifest::ManifestError;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+use syn::spanned::Spanned;
+use syn::{File as SynFile, Item, ItemMod, Lit, Meta};
+use thiserror::Error;
 
-use syn::{File as SynFile, Ident, Item};
+const SKIP_UNPARSEABLE_ENV: &str = "ARCHUNIT_SKIP_UNPARSEABLE";
 
 #[derive(Debug)]
 pub(crate) struct ModuleFilePath(pub(crate) PathBuf);
@@ -17,17 +18,150 @@ pub(crate) struct Ast(pub SynFile);
 #[derive(Debug)]
 pub struct ModuleAst {
     pub(crate) name: String,
+    // Inline modules enclosing the declaration of this module in its parent file
+    pub(crate) enclosing: Vec<String>,
     pub(crate) location: ModuleFilePath,
     pub(crate) ast: Ast,
     pub(crate) submodules: Vec<ModuleAst>,
 }
 
+/// The file of a `mod` declaration could not be resolved, see
+/// <https://doc.rust-lang.org/reference/items/modules.html#module-source-filenames>.
+#[derive(Error, Debug, Diagnostic)]
+pub enum ModuleResolutionError {
+    #[error("File not found for module `{module}`")]
+    #[diagnostic(
+        code(archunit::module::not_found),
+        help("Create the module file at {candidates}")
+    )]
+    NotFound {
+        module: String,
+        candidates: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("declared here")]
+        span: SourceSpan,
+    },
+    #[error("File for module `{module}` found at both {candidates}")]
+    #[diagnostic(
+        code(archunit::module::ambiguous),
+        help("Delete or rename one of them to remove the ambiguity")
+    )]
+    Ambiguous {
+        module: String,
+        candidates: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("declared here")]
+        span: SourceSpan,
+    },
+}
+
+/// Error raised while loading the module tree of a crate.
+#[derive(Error, Debug, Diagnostic)]
+pub enum LoadError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Manifest(#[from] ManifestError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Module(#[from] ModuleResolutionError),
+    #[error("Unable to read {path}")]
+    #[diagnostic(code(archunit::load::io))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Unable to parse {location}: {message}")]
+    #[diagnostic(
+        code(archunit::load::parse),
+        help("Set {SKIP_UNPARSEABLE_ENV}=1 to skip the files syn cannot parse")
+    )]
+    Parse {
+        /// The file, line and column of the syntax error.
+        location: String,
+        message: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("{message}")]
+        span: SourceSpan,
+    },
+    #[error("{path} has no lib or bin target")]
+    #[diagnostic(code(archunit::load::no_target))]
+    NoTarget { path: PathBuf },
+    #[error("Environment variable {0} is not set")]
+    #[diagnostic(
+        code(archunit::load::missing_env),
+        help("Run the architecture tests with cargo, or load the crate with ModuleTree::try_load_from")
+    )]
+    MissingEnv(&'static str),
+}
+
+/// Read a variable cargo sets when running tests.
+pub(crate) fn cargo_env(name: &'static str) -> Result<String, LoadError> {
+    env::var(name).map_err(|_| LoadError::MissingEnv(name))
+}
+
+/// Options controlling how source files are loaded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Skip the modules whose file cannot be parsed with a warning instead of failing, the
+    /// crate root must always parse.
+    pub skip_unparseable: bool,
+}
+
+impl LoadOptions {
+    /// Skip unparseable module files, see [`LoadOptions::skip_unparseable`].
+    pub fn skip_unparseable(mut self) -> Self {
+        self.skip_unparseable = true;
+        self
+    }
+
+    /// Read the options from `ARCHUNIT_SKIP_UNPARSEABLE`, unparseable files are skipped when
+    /// it is set to `1` or `true`.
+    pub fn from_env() -> Self {
+        let mut options = LoadOptions::default();
+
+        if let Ok(skip) = env::var(SKIP_UNPARSEABLE_ENV) {
+            match skip.to_ascii_lowercase().as_str() {
+                "1" | "true" => options.skip_unparseable = true,
+                "0" | "false" | "" => {}
+                _ => eprintln!("Invalid value '{skip}' for {SKIP_UNPARSEABLE_ENV}"),
+            }
+        }
+
+        options
+    }
+}
+
+/// Where the files of out-of-line submodules are looked up, following rustc: submodules of a
+/// non `mod.rs` file live in a directory named after it, given by `relative`.
+#[derive(Debug, Clone)]
+struct ModuleDir {
+    path: PathBuf,
+    relative: Option<String>,
+}
+
 impl ModuleFilePath {
-    pub fn get_ast(&self) -> Ast {
-        let mut file = File::open(&self.0).expect("Unable to open file");
-        let mut src = String::new();
-        file.read_to_string(&mut src).expect("Unable to read file");
-        Ast(syn::parse_file(&src).expect("Unable to parse file"))
+    pub fn get_ast(&self) -> Result<Ast, LoadError> {
+        let src = fs::read_to_string(&self.0).map_err(|source| LoadError::Io {
+            path: self.0.clone(),
+            source,
+        })?;
+
+        syn::parse_file(&src).map(Ast).map_err(|err| {
+            let start = err.span().start();
+            let offset = byte_offset(&src, start.line, start.column);
+            let location = get_relative_location(&self.0);
+            LoadError::Parse {
+                // Columns are 0 based in spans, editors count them from 1
+                location: format!("{location}:{}:{}", start.line, start.column + 1),
+                message: err.to_string(),
+                src: NamedSource::new(location, src),
+                span: (offset, 0).into(),
+            }
+        })
     }
 
     fn get_dir(&self) -> &Path {
@@ -35,110 +169,411 @@ impl ModuleFilePath {
             .parent()
             .expect("Submodule path should have a parent")
     }
+}
 
-    pub fn crate_root() -> Self {
-        let working_directory =
-            env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be set");
-        let cargo_manifest_path = PathBuf::from_str(&working_directory)
-            .expect("CARGO_MANIFEST_DIR must point to a valid path");
+impl ModuleDir {
+    fn owned_path(&self) -> PathBuf {
+        match &self.relative {
+            Some(relative) => self.path.join(relative),
+            None => self.path.clone(),
+        }
+    }
 
-        let lib_path = cargo_manifest_path.join("src").join("lib.rs");
+    /// Directory of the submodules declared in the inline module `item_mod`.
+    fn inline(&self, item_mod: &ItemMod) -> Self {
+        let path = match path_attribute(item_mod) {
+            Some(path) => self.path.join(path),
+            None => self.owned_path().join(item_mod.ident.to_string()),
+        };
+
+        ModuleDir {
+            path,
+            relative: None,
+        }
+    }
+
+    /// Resolve the file of the out-of-line module `item_mod`, declared in `declared_in`.
+    /// Modules disabled by a `cfg` attribute may have no file, they resolve to `None`.
+    fn resolve(
+        &self,
+        item_mod: &ItemMod,
+        declared_in: &Path,
+    ) -> Result<Option<(PathBuf, ModuleDir)>, ModuleResolutionError> {
+        let ident = item_mod.ident.to_string();
+        let optional = item_mod.attrs.iter().any(|attr| attr.path.is_ident("cfg"));
+
+        if let Some(path) = path_attribute(item_mod) {
+            let file = self.path.join(path);
+            if !file.exists() {
+                return if optional {
+                    Ok(None)
+                } else {
+                    Err(not_found(item_mod, declared_in, &[&file]))
+                };
+            }
+
+            // Files included with `#[path]` are treated as `mod.rs` files
+            let dir = ModuleDir {
+                path: file
+                    .parent()
+                    .expect("Module path should have a parent")
+                    .to_path_buf(),
+                relative: None,
+            };
+            return Ok(Some((file, dir)));
+        }
 
-        if lib_path.exists() {
-            Self(lib_path)
-        } else {
-            Self(cargo_manifest_path.join("src").join("main.rs"))
+        let dir = self.owned_path();
+        let file = dir.join(format!("{ident}.rs"));
+        let mod_rs = dir.join(&ident).join("mod.rs");
+
+        match (file.exists(), mod_rs.exists()) {
+            (true, false) => Ok(Some((
+                file,
+                ModuleDir {
+                    path: dir,
+                    relative: Some(ident),
+                },
+            ))),
+            (false, true) => Ok(Some((
+                mod_rs,
+                ModuleDir {
+                    path: dir.join(ident),
+                    relative: None,
+                },
+            ))),
+            (false, false) if optional => Ok(None),
+            (false, false) => Err(not_found(item_mod, declared_in, &[&file, &mod_rs])),
+            (true, true) => {
+                let (src, span) = declaration_source(item_mod, declared_in);
+                Err(ModuleResolutionError::Ambiguous {
+                    module: ident,
+                    candidates: format!(
+                        "{} and {}",
+                        get_relative_location(&file),
+                        get_relative_location(&mod_rs)
+                    ),
+                    src,
+                    span,
+                })
+            }
         }
     }
 }
 
 impl ModuleAst {
-    pub(crate) fn load_crate_ast() -> ModuleAst {
-        let location = ModuleFilePath::crate_root();
-        let ast = location.get_ast();
-        let name = env!("CARGO_CRATE_NAME", "'CARGO_CRATE_NAME' should be set").to_owned();
-        let name = name.replace('-', "_");
+    pub(crate) fn load_crate_ast(
+        crate_root: &Path,
+        crate_name: &str,
+        options: &LoadOptions,
+    ) -> Result<ModuleAst, LoadError> {
+        let location = ModuleFilePath(crate_root.to_path_buf());
+        let ast = location.get_ast()?;
+        let name = crate_name.replace('-', "_");
+        let dir = ModuleDir {
+            path: location.get_dir().to_path_buf(),
+            relative: None,
+        };
         let mut crate_root = ModuleAst {
             name,
+            enclosing: vec![],
             location,
             ast,
             submodules: Vec::with_capacity(0),
         };
 
-        crate_root.load_submodules();
-        crate_root
-    }
-
-    fn get_submodule(&self, ident: &Ident) -> Self {
-        let base_dir = self.location.get_dir();
-        let file_module = base_dir.to_path_buf().join(format!("{ident}.rs"));
-        let directory_module = base_dir.to_path_buf().join(format!("{ident}/mod.rs"));
-        if file_module.exists() {
-            let name = ident.to_string();
-            let location = ModuleFilePath(file_module);
-            let ast = location.get_ast();
-            Self {
-                name,
-                location,
-                ast,
-                submodules: vec![],
-            }
-        } else if directory_module.exists() {
-            let name = ident.to_string();
-            let location = ModuleFilePath(directory_module);
-            let ast = location.get_ast();
-            ModuleAst {
-                name,
-                location,
-                ast,
-                submodules: vec![],
-            }
-        } else {
-            panic!("no modules path found for modules {ident}")
-        }
+        crate_root.load_submodules(&dir, options)?;
+        Ok(crate_root)
     }
 
-    fn load_submodules(&mut self) {
-        let submodules = self.ast.get_modules_ident();
-        let mut submodules: Vec<ModuleAst> = submodules
-            .iter()
-            .map(|module_ident| self.get_submodule(module_ident))
-            .collect();
+    fn load_submodules(&mut self, dir: &ModuleDir, options: &LoadOptions) -> Result<(), LoadError> {
+        let mut submodules = vec![];
+        load_declared_modules(
+            &self.location.0,
+            &self.ast.0.items,
+            dir,
+            options,
+            &mut vec![],
+            &mut submodules,
+        )?;
+        self.submodules = submodules;
+        Ok(())
+    }
+}
 
-        for module in submodules.iter_mut() {
-            module.load_submodules();
+// Load the out-of-line modules declared in `items`, including those nested in inline modules
+fn load_declared_modules(
+    declared_in: &Path,
+    items: &[Item],
+    dir: &ModuleDir,
+    options: &LoadOptions,
+    enclosing: &mut Vec<String>,
+    loaded: &mut Vec<ModuleAst>,
+) -> Result<(), LoadError> {
+    for item_mod in items.iter().filter_map(|item| match item {
+        Item::Mod(item_mod) => Some(item_mod),
+        _ => None,
+    }) {
+        if let Some((_, items)) = &item_mod.content {
+            enclosing.push(item_mod.ident.to_string());
+            load_declared_modules(
+                declared_in,
+                items,
+                &dir.inline(item_mod),
+                options,
+                enclosing,
+                loaded,
+            )?;
+            enclosing.pop();
+            continue;
         }
 
-        self.submodules = submodules;
+        let Some((path, module_dir)) = dir.resolve(item_mod, declared_in)? else {
+            continue;
+        };
+
+        let location = ModuleFilePath(path);
+        let ast = match location.get_ast() {
+            Ok(ast) => ast,
+            Err(err @ LoadError::Parse { .. }) if options.skip_unparseable => {
+                eprintln!("warning: skipping module `{}`: {err}", item_mod.ident);
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        let mut module = ModuleAst {
+            name: item_mod.ident.to_string(),
+            enclosing: enclosing.clone(),
+            location,
+            ast,
+            submodules: vec![],
+        };
+
+        module.load_submodules(&module_dir, options)?;
+        loaded.push(module);
     }
+
+    Ok(())
 }
 
-impl Ast {
-    fn get_modules_ident(&self) -> Vec<Ident> {
-        self.0
-            .items
-            .iter()
-            .filter_map(|item| match item {
-                Item::Mod(item_mod) => Some(item_mod),
+/// The value of a `#[path = "..."]` attribute.
+fn path_attribute(item_mod: &ItemMod) -> Option<String> {
+    item_mod
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("path"))
+        .find_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(meta)) => match meta.lit {
+                Lit::Str(path) => Some(path.value()),
                 _ => None,
-            })
-            // filtering out file declared modules
-            .filter(|module| module.content.is_none())
-            .map(|module| module.ident.clone())
-            .collect()
+            },
+            _ => None,
+        })
+}
+
+fn not_found(
+    item_mod: &ItemMod,
+    declared_in: &Path,
+    candidates: &[&Path],
+) -> ModuleResolutionError {
+    let (src, span) = declaration_source(item_mod, declared_in);
+    ModuleResolutionError::NotFound {
+        module: item_mod.ident.to_string(),
+        candidates: candidates
+            .iter()
+            .map(|candidate| get_relative_location(candidate))
+            .collect::<Vec<_>>()
+            .join(" or "),
+        src,
+        span,
     }
 }
 
+fn declaration_source(item_mod: &ItemMod, declared_in: &Path) -> (NamedSource, SourceSpan) {
+    let content = fs::read_to_string(declared_in).unwrap_or_default();
+    let start = item_mod.mod_token.span().start();
+    let end = item_mod.semi.span().end();
+    let start = byte_offset(&content, start.line, start.column);
+    let end = byte_offset(&content, end.line, end.column);
+    let src = NamedSource::new(get_relative_location(declared_in), content);
+    (src, (start, end.saturating_sub(start)).into())
+}
+
+/// Byte offset of a span position, lines start at 1 and columns count chars from 0.
+pub(crate) fn byte_offset(content: &str, line: usize, column: usize) -> usize {
+    content
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>()
+        + content
+            .split_inclusive('\n')
+            .nth(line.saturating_sub(1))
+            .map_or(0, |line| {
+                line.chars().take(column).map(char::len_utf8).sum::<usize>()
+            })
+}
+
 #[cfg(test)]
 mod test {
-    use crate::ast::parse::ModuleAst;
+    use crate::ast::parse::{cargo_env, LoadError, LoadOptions, ModuleAst, ModuleResolutionError};
     use crate::ast::visitor::ModuleOrCrateRoot;
     use crate::ast::ItemPath;
+    use speculoos::prelude::*;
+    use std::path::Path;
 
     #[test]
     fn test() {
-        let mut ast = ModuleAst::load_crate_ast();
-        let tree = ast.visit_modules(ModuleOrCrateRoot::CrateRoot);
+        let ast = ModuleAst::load_crate_ast(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs"),
+            "archunit_rs",
+            &LoadOptions::default(),
+        )
+        .expect("own crate");
+        let tree = ast.visit_modules(ModuleOrCrateRoot::CrateRoot(ast.name.clone()));
         let _tree = tree.to_tree(&ItemPath::empty(), None);
     }
+
+    #[test]
+    fn should_resolve_modules_like_rustc() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/module_layouts/src");
+        let ast = ModuleAst::load_crate_ast(
+            &root.join("lib.rs"),
+            "module_layouts",
+            &LoadOptions::default(),
+        )
+        .expect("fixture crate");
+
+        let mut files = vec![];
+        let mut stack: Vec<&ModuleAst> = ast.submodules.iter().collect();
+        while let Some(module) = stack.pop() {
+            let path = module
+                .location
+                .0
+                .strip_prefix(&root)
+                .expect("module in fixture")
+                .to_string_lossy()
+                .to_string();
+            files.push((module.enclosing.join("::"), module.name.clone(), path));
+            stack.extend(module.submodules.iter());
+        }
+        files.sort();
+
+        assert_that!(files).is_equal_to(vec![
+            (
+                "".to_owned(),
+                "child".to_owned(),
+                "nested/child.rs".to_owned(),
+            ),
+            (
+                "".to_owned(),
+                "config".to_owned(),
+                "config/mod.rs".to_owned(),
+            ),
+            (
+                "".to_owned(),
+                "custom".to_owned(),
+                "other/custom_file.rs".to_owned(),
+            ),
+            (
+                "".to_owned(),
+                "grandchild".to_owned(),
+                "nested/child/grandchild.rs".to_owned(),
+            ),
+            (
+                "".to_owned(),
+                "loader".to_owned(),
+                "config/loader.rs".to_owned(),
+            ),
+            ("".to_owned(), "nested".to_owned(), "nested.rs".to_owned()),
+            (
+                "".to_owned(),
+                "sibling".to_owned(),
+                "other/sibling.rs".to_owned(),
+            ),
+            ("".to_owned(), "store".to_owned(), "store.rs".to_owned()),
+            (
+                "inline".to_owned(),
+                "deep".to_owned(),
+                "nested/inline/deep.rs".to_owned(),
+            ),
+            (
+                "inner".to_owned(),
+                "backend".to_owned(),
+                "store/inner/backend.rs".to_owned(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn should_point_at_unresolved_module_declaration() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/missing_module/src");
+        let err = ModuleAst::load_crate_ast(
+            &root.join("lib.rs"),
+            "missing_module",
+            &LoadOptions::default(),
+        )
+        .expect_err("missing module file");
+
+        let LoadError::Module(ModuleResolutionError::NotFound {
+            module,
+            candidates,
+            span,
+            ..
+        }) = err
+        else {
+            panic!("expected a not found error, got {err:?}");
+        };
+
+        assert_that!(module.as_str()).is_equal_to("missing");
+        assert_that!(candidates.as_str())
+            .ends_with("src/missing.rs or tests/fixtures/missing_module/src/missing/mod.rs");
+        assert_that!(span.offset()).is_equal_to(4);
+        assert_that!(span.len()).is_equal_to(12);
+    }
+
+    #[test]
+    fn should_locate_syntax_errors() {
+        let root =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/unparseable_crate/src");
+        let err = ModuleAst::load_crate_ast(
+            &root.join("lib.rs"),
+            "unparseable_crate",
+            &LoadOptions::default(),
+        )
+        .expect_err("syntax error");
+
+        let LoadError::Parse { location, .. } = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+
+        assert_that!(location.as_str()).ends_with("unparseable_crate/src/generated.rs:4:5");
+    }
+
+    #[test]
+    fn should_skip_unparseable_modules() {
+        let root =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/unparseable_crate/src");
+        let ast = ModuleAst::load_crate_ast(
+            &root.join("lib.rs"),
+            "unparseable_crate",
+            &LoadOptions::default().skip_unparseable(),
+        )
+        .expect("generated module skipped");
+
+        let modules: Vec<&str> = ast
+            .submodules
+            .iter()
+            .map(|module| module.name.as_str())
+            .collect();
+        assert_that!(modules).is_equal_to(vec!["domain"]);
+    }
+
+    #[test]
+    fn should_report_missing_cargo_env() {
+        let error = cargo_env("ARCHUNIT_RS_UNSET_VARIABLE").expect_err("unset variable");
+
+        assert_that!(error.to_string())
+            .is_equal_to("Environment variable ARCHUNIT_RS_UNSET_VARIABLE is not set".to_owned());
+    }
 }
@@ -1,7 +1,12 @@
+use crate::ast::cfg::{attributes_cfg, CfgPredicate};
+use crate::ast::types::Imports;
 use crate::ast::{CodeSpan, ItemPath, Visibility};
+use quote::ToTokens;
+use std::fmt;
 use std::path::{Path, PathBuf};
 use syn::spanned::Spanned;
-use syn::{ItemStruct, Meta, NestedMeta};
+use syn::{Fields, ItemStruct, Meta, NestedMeta};
+use wildmatch::WildMatch;
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Struct {
@@ -12,11 +17,54 @@ pub struct Struct {
     pub derives: Vec<String>,
     pub visibility: Visibility,
     pub fields: Vec<Field>,
+    pub kind: StructKind,
+    /// Names of the type and const generic parameters.
+    pub generics: Vec<String>,
+    /// Lifetime parameters, e.g. `'a`.
+    pub lifetimes: Vec<String>,
+    /// Predicates of the where clause, as written.
+    pub where_predicates: Vec<String>,
     pub path: ItemPath,
+    pub cfg: Option<CfgPredicate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StructKind {
+    /// `struct Marker;`
+    Unit,
+    /// `struct Id(u64);`
+    Tuple,
+    /// `struct Point { x: f64, y: f64 }`
+    Named,
+}
+
+impl fmt::Display for StructKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructKind::Unit => write!(f, "a unit struct"),
+            StructKind::Tuple => write!(f, "a tuple struct"),
+            StructKind::Named => write!(f, "a struct with named fields"),
+        }
+    }
+}
+
+impl From<&Fields> for StructKind {
+    fn from(fields: &Fields) -> Self {
+        match fields {
+            Fields::Unit => StructKind::Unit,
+            Fields::Unnamed(_) => StructKind::Tuple,
+            Fields::Named(_) => StructKind::Named,
+        }
+    }
 }
 
 impl Struct {
-    pub fn from_syn(struct_: &ItemStruct, path: &ItemPath, real_path: &Path) -> Self {
+    pub(crate) fn from_syn(
+        struct_: &ItemStruct,
+        path: &ItemPath,
+        real_path: &Path,
+        imports: &Imports,
+    ) -> Self {
         let ident = struct_.ident.to_string();
         let path = path.join(&ident);
         let derives = struct_
@@ -29,36 +77,44 @@ impl Struct {
                     .map(|ident| ident == "derive")
                     .unwrap_or(false)
             })
-            .filter_map(|attr| {
-                let attr = attr.parse_meta().expect("failed to parse derive attribute");
-
-                match attr {
-                    Meta::List(list) => {
-                        let derives: Vec<String> = list
-                            .nested
-                            .iter()
-                            .filter_map(|nested| match nested {
-                                NestedMeta::Meta(meta) => match meta {
-                                    Meta::Path(path) => Some(
-                                        path.segments
-                                            .iter()
-                                            .map(|segment| segment.ident.to_string()),
-                                    ),
-                                    _ => None,
-                                },
-                                NestedMeta::Lit(_) => None,
-                            })
-                            .flatten()
-                            .collect();
-                        Some(derives)
-                    }
-                    _ => None,
+            .filter_map(|attr| match attr.parse_meta().ok()? {
+                Meta::List(list) => {
+                    let derives: Vec<String> = list
+                        .nested
+                        .iter()
+                        .filter_map(|nested| match nested {
+                            NestedMeta::Meta(meta) => match meta {
+                                Meta::Path(path) => Some(
+                                    path.segments
+                                        .iter()
+                                        .map(|segment| segment.ident.to_string()),
+                                ),
+                                _ => None,
+                            },
+                            NestedMeta::Lit(_) => None,
+                        })
+                        .flatten()
+                        .collect();
+                    Some(derives)
                 }
+                _ => None,
             })
             .flatten()
             .collect();
 
-        let fields = struct_.fields.iter().map(Field::from).collect();
+        let fields = struct_
+            .fields
+            .iter()
+            .map(|field| Field::from_syn(field, imports))
+            .collect();
+        let kind = StructKind::from(&struct_.fields);
+        let generics = &struct_.generics;
+        let where_predicates = generics
+            .where_clause
+            .iter()
+            .flat_map(|clause| &clause.predicates)
+            .map(|predicate| predicate.to_token_stream().to_string())
+            .collect();
         let span = struct_.ident.span().into();
         Self {
             span,
@@ -67,7 +123,19 @@ impl Struct {
             derives,
             visibility: Visibility::from_syn(&struct_.vis),
             fields,
+            kind,
+            generics: generics
+                .type_params()
+                .map(|param| param.ident.to_string())
+                .chain(generics.const_params().map(|param| param.ident.to_string()))
+                .collect(),
+            lifetimes: generics
+                .lifetimes()
+                .map(|param| format!("'{}", param.lifetime.ident))
+                .collect(),
+            where_predicates,
             path,
+            cfg: attributes_cfg(&struct_.attrs),
         }
     }
 }
@@ -77,18 +145,29 @@ pub struct Field {
     pub visibility: Visibility,
     pub name: Option<String>,
     pub span: CodeSpan,
+    /// The field type with imported names resolved, e.g. `std::sync::Mutex<Vec<u8>>`.
     pub type_: String,
+    /// Paths of the types the field type refers to, without generic arguments.
+    pub type_paths: Vec<String>,
 }
 
-impl From<&syn::Field> for Field {
-    fn from(field: &syn::Field) -> Self {
+impl Field {
+    pub(crate) fn from_syn(field: &syn::Field, imports: &Imports) -> Self {
+        let type_ = imports.normalize(&field.ty);
         Self {
             visibility: Visibility::from_syn(&field.vis),
             name: field.ident.as_ref().map(|ident| ident.to_string()),
             span: field.span().into(),
-            type_: format!("{:?}", field.ty),
+            type_: type_.name,
+            type_paths: type_.paths,
         }
     }
+
+    /// Whether the field type, or one of the types it refers to, matches `pattern`.
+    pub fn has_type_matching(&self, pattern: &str) -> bool {
+        let pattern = WildMatch::new(pattern);
+        pattern.matches(&self.type_) || self.type_paths.iter().any(|path| pattern.matches(path))
+    }
 }
 
 impl Struct {
@@ -108,6 +187,26 @@ impl Struct {
         self.derives.contains(&trait_.to_owned())
     }
 
+    /// Whether the struct has type, const or lifetime parameters.
+    pub fn is_generic(&self) -> bool {
+        !self.generics.is_empty() || !self.lifetimes.is_empty()
+    }
+
+    /// The generic parameters as declared, e.g. `<'a, T>`, or an empty string.
+    pub fn generic_params(&self) -> String {
+        if !self.is_generic() {
+            return String::new();
+        }
+
+        let params: Vec<&str> = self
+            .lifetimes
+            .iter()
+            .chain(&self.generics)
+            .map(String::as_str)
+            .collect();
+        format!("<{}>", params.join(", "))
+    }
+
     pub fn has_non_public_field(&self) -> bool {
         let has_non_public_field = self
             .fields
@@ -117,3 +216,34 @@ impl Struct {
         has_non_public_field
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::ast::structs::{Struct, StructKind};
+    use crate::ast::types::Imports;
+    use crate::ast::ItemPath;
+    use speculoos::prelude::*;
+    use std::path::Path;
+
+    fn parse(source: &str) -> Struct {
+        let module = ItemPath::new("app".to_owned());
+        let imports = Imports::of(&[], &module);
+        let item = syn::parse_str(source).expect("valid struct");
+        Struct::from_syn(&item, &module, Path::new(""), &imports)
+    }
+
+    #[test]
+    fn should_record_struct_shape() {
+        let unit = parse("struct Marker;");
+        let tuple = parse("struct Id(u64);");
+        let generic =
+            parse("struct Ref<'a, T, const N: usize> where T: Clone { items: &'a [T; N] }");
+
+        assert_that!(unit.kind).is_equal_to(StructKind::Unit);
+        assert_that!(unit.is_generic()).is_false();
+        assert_that!(tuple.kind).is_equal_to(StructKind::Tuple);
+        assert_that!(generic.kind).is_equal_to(StructKind::Named);
+        assert_that!(generic.generic_params().as_str()).is_equal_to("<'a, T, N>");
+        assert_that!(generic.where_predicates).has_length(1);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::ast::manifest::{read_manifest, ManifestError, RawTarget};
+use crate::ast::module_tree;
+use crate::ast::parse::cargo_env;
+use crate::ModuleTree;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The kind of a cargo target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    Lib,
+    Bin,
+    Example,
+    Test,
+}
+
+/// A compilation target of a package and its root source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub kind: TargetKind,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// The targets of a package, discovered from its manifest like cargo does: the `[lib]`,
+/// `[[bin]]`, `[[example]]` and `[[test]]` tables, then `src/main.rs`, `src/bin`, `examples`
+/// and `tests` unless automatic discovery is disabled.
+#[derive(Debug)]
+pub struct CrateTargets {
+    pub package: String,
+    pub targets: Vec<Target>,
+}
+
+/// Module trees of the crate under test targets, each of them is loaded on first use.
+pub(crate) fn target_trees(kinds: &[TargetKind]) -> Vec<&'static ModuleTree> {
+    static TARGETS: OnceCell<Vec<(Target, OnceCell<ModuleTree>)>> = OnceCell::new();
+    let targets = TARGETS.get_or_init(|| {
+        let targets = cargo_env("CARGO_MANIFEST_DIR")
+            .and_then(|manifest_dir| Ok(CrateTargets::discover(manifest_dir)?));
+        match targets {
+            Ok(targets) => targets
+                .targets
+                .into_iter()
+                .map(|target| (target, OnceCell::new()))
+                .collect(),
+            Err(err) => panic!("{:?}", miette::ErrReport::from(err)),
+        }
+    });
+
+    targets
+        .iter()
+        .filter(|(target, _)| kinds.contains(&target.kind))
+        .map(|(target, tree)| match target.kind {
+            // Share the tree rules are evaluated against by default
+            TargetKind::Lib => module_tree(),
+            _ => tree.get_or_init(|| ModuleTree::load_target(target)),
+        })
+        .collect()
+}
+
+impl CrateTargets {
+    /// Discover the targets of the package located at `manifest_dir`.
+    pub fn discover<P: AsRef<Path>>(manifest_dir: P) -> Result<Self, ManifestError> {
+        let manifest_dir = manifest_dir.as_ref();
+        let manifest_path = manifest_dir.join("Cargo.toml");
+        let manifest = read_manifest(&manifest_path)?;
+        let Some(package) = manifest.package else {
+            return Err(ManifestError::Invalid {
+                path: manifest_path,
+                message: "missing [package] table".to_owned(),
+            });
+        };
+
+        let mut targets = vec![];
+        let lib_path = match manifest.lib.as_ref().and_then(|lib| lib.path.as_ref()) {
+            Some(path) => Some(manifest_dir.join(path)),
+            None => Some(manifest_dir.join("src/lib.rs")).filter(|path| path.exists()),
+        };
+
+        if let Some(path) = lib_path {
+            let name = manifest
+                .lib
+                .as_ref()
+                .and_then(|lib| lib.name.clone())
+                .unwrap_or_else(|| package.name.replace('-', "_"));
+
+            targets.push(Target {
+                kind: TargetKind::Lib,
+                name,
+                path,
+            });
+        }
+
+        let mut bins = explicit_targets(manifest_dir, TargetKind::Bin, &manifest.bin, "src/bin");
+        if package.autobins.unwrap_or(true) {
+            let main = manifest_dir.join("src/main.rs");
+            if main.exists() {
+                push_discovered(&mut bins, TargetKind::Bin, package.name.clone(), main);
+            }
+
+            for (name, path) in discover_dir(&manifest_dir.join("src/bin")) {
+                push_discovered(&mut bins, TargetKind::Bin, name, path);
+            }
+        }
+
+        let mut examples = explicit_targets(
+            manifest_dir,
+            TargetKind::Example,
+            &manifest.example,
+            "examples",
+        );
+        if package.autoexamples.unwrap_or(true) {
+            for (name, path) in discover_dir(&manifest_dir.join("examples")) {
+                push_discovered(&mut examples, TargetKind::Example, name, path);
+            }
+        }
+
+        let mut tests = explicit_targets(manifest_dir, TargetKind::Test, &manifest.test, "tests");
+        if package.autotests.unwrap_or(true) {
+            for (name, path) in discover_dir(&manifest_dir.join("tests")) {
+                push_discovered(&mut tests, TargetKind::Test, name, path);
+            }
+        }
+
+        for mut kind in [bins, examples, tests] {
+            kind.sort_by(|a, b| a.name.cmp(&b.name));
+            targets.extend(kind);
+        }
+
+        Ok(CrateTargets {
+            package: package.name,
+            targets,
+        })
+    }
+
+    /// The library target, if any.
+    pub fn lib(&self) -> Option<&Target> {
+        self.of_kind(TargetKind::Lib).next()
+    }
+
+    /// Targets of the given kind, sorted by name.
+    pub fn of_kind(&self, kind: TargetKind) -> impl Iterator<Item = &Target> {
+        self.targets
+            .iter()
+            .filter(move |target| target.kind == kind)
+    }
+
+    /// The target rules are evaluated against by default: the library, or the binary named
+    /// after the package for binary crates.
+    pub(crate) fn default_target(&self) -> Option<&Target> {
+        self.lib().or_else(|| {
+            self.of_kind(TargetKind::Bin)
+                .find(|target| target.name == self.package)
+                .or_else(|| self.of_kind(TargetKind::Bin).next())
+        })
+    }
+}
+
+fn explicit_targets(
+    manifest_dir: &Path,
+    kind: TargetKind,
+    declared: &[RawTarget],
+    default_dir: &str,
+) -> Vec<Target> {
+    declared
+        .iter()
+        .filter_map(|target| {
+            let name = target.name.clone().or_else(|| {
+                target
+                    .path
+                    .as_ref()
+                    .and_then(|path| path.file_stem())
+                    .map(|stem| stem.to_string_lossy().to_string())
+            })?;
+
+            let path = match &target.path {
+                Some(path) => manifest_dir.join(path),
+                None => {
+                    let file = manifest_dir.join(default_dir).join(format!("{name}.rs"));
+                    if file.exists() {
+                        file
+                    } else {
+                        manifest_dir.join(default_dir).join(&name).join("main.rs")
+                    }
+                }
+            };
+
+            Some(Target { kind, name, path })
+        })
+        .collect()
+}
+
+// Explicitly declared targets take precedence over discovered ones
+fn push_discovered(targets: &mut Vec<Target>, kind: TargetKind, name: String, path: PathBuf) {
+    if !targets
+        .iter()
+        .any(|target| target.name == name || target.path == path)
+    {
+        targets.push(Target { kind, name, path });
+    }
+}
+
+/// `*.rs` files and directories with a `main.rs` of `dir`, named after their file stem.
+fn discover_dir(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut discovered: Vec<(String, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            if path.is_dir() {
+                let main = path.join("main.rs");
+                let name = path.file_name()?.to_string_lossy().to_string();
+                main.exists().then_some((name, main))
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let name = path.file_stem()?.to_string_lossy().to_string();
+                Some((name, path))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    discovered.sort();
+    discovered
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::targets::{CrateTargets, Target, TargetKind};
+    use speculoos::prelude::*;
+    use std::path::Path;
+
+    #[test]
+    fn should_discover_own_targets() {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let targets = CrateTargets::discover(manifest_dir).expect("own manifest");
+
+        assert_that!(targets.lib()).is_some().is_equal_to(&Target {
+            kind: TargetKind::Lib,
+            name: "archunit_rs".to_owned(),
+            path: manifest_dir.join("src/lib.rs"),
+        });
+
+        let bins: Vec<&str> = targets
+            .of_kind(TargetKind::Bin)
+            .map(|target| target.name.as_str())
+            .collect();
+        assert_that!(bins).is_equal_to(vec!["cargo-archunit"]);
+        assert_that!(targets.of_kind(TargetKind::Test).count()).is_greater_than(0);
+    }
+
+    #[test]
+    fn should_discover_declared_and_conventional_targets() {
+        let manifest_dir =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/targets_crate");
+        let targets = CrateTargets::discover(&manifest_dir).expect("fixture manifest");
+
+        let found: Vec<(TargetKind, &str, String)> = targets
+            .targets
+            .iter()
+            .map(|target| {
+                let path = target
+                    .path
+                    .strip_prefix(&manifest_dir)
+                    .expect("target in fixture")
+                    .to_string_lossy()
+                    .to_string();
+                (target.kind, target.name.as_str(), path)
+            })
+            .collect();
+
+        assert_that!(found).is_equal_to(vec![
+            (TargetKind::Lib, "targets_crate", "src/core.rs".to_owned()),
+            (TargetKind::Bin, "admin", "src/bin/admin.rs".to_owned()),
+            (
+                TargetKind::Bin,
+                "server",
+                "src/bin/server/main.rs".to_owned(),
+            ),
+            (TargetKind::Bin, "targets-crate", "src/main.rs".to_owned()),
+            (TargetKind::Bin, "tool", "cli/tool.rs".to_owned()),
+            (TargetKind::Example, "demo", "examples/demo.rs".to_owned()),
+            (TargetKind::Test, "smoke", "tests/smoke.rs".to_owned()),
+        ]);
+    }
+}
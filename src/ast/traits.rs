@@ -0,0 +1,32 @@
+use crate::ast::cfg::{attributes_cfg, CfgPredicate};
+use crate::ast::{CodeSpan, ItemPath, Visibility};
+use std::path::{Path, PathBuf};
+use syn::ItemTrait;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Trait {
+    pub span: CodeSpan,
+    pub real_path: PathBuf,
+    pub ident: String,
+    pub visibility: Visibility,
+    pub path: ItemPath,
+    pub cfg: Option<CfgPredicate>,
+}
+
+impl Trait {
+    pub(crate) fn from_syn(trait_: &ItemTrait, path: &ItemPath, real_path: &Path) -> Self {
+        let ident = trait_.ident.to_string();
+        Self {
+            span: trait_.ident.span().into(),
+            real_path: real_path.to_path_buf(),
+            path: path.join(&ident),
+            ident,
+            visibility: Visibility::from_syn(&trait_.vis),
+            cfg: attributes_cfg(&trait_.attrs),
+        }
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.visibility == Visibility::Public
+    }
+}
@@ -0,0 +1,359 @@
+use crate::ast::ItemPath;
+use quote::ToTokens;
+use std::collections::HashMap;
+use syn::{GenericArgument, Item, Path, PathArguments, ReturnType, Type, TypeParamBound, UseTree};
+
+/// Names brought into scope by the `use` declarations of a module, to their full path.
+#[derive(Debug)]
+pub(crate) struct Imports {
+    module: ItemPath,
+    names: HashMap<String, String>,
+}
+
+/// A type as written in the source, with its imported names resolved.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct NormalizedType {
+    /// The whole type, with generic arguments and references, e.g. `&std::sync::Mutex<u32>`.
+    pub name: String,
+    /// The paths of all the types the type refers to, without generic arguments.
+    pub paths: Vec<String>,
+}
+
+impl Imports {
+    pub(crate) fn of(items: &[Item], module: &ItemPath) -> Self {
+        let mut imports = Imports {
+            module: module.clone(),
+            names: HashMap::new(),
+        };
+
+        // Items declared in the module, submodules included for relative paths
+        for item in items {
+            let ident = match item {
+                Item::Struct(item) => &item.ident,
+                Item::Enum(item) => &item.ident,
+                Item::Union(item) => &item.ident,
+                Item::Trait(item) => &item.ident,
+                Item::Type(item) => &item.ident,
+                Item::Mod(item) => &item.ident,
+                _ => continue,
+            };
+            imports.names.insert(
+                ident.to_string(),
+                module.join(ident.to_string()).to_string(),
+            );
+        }
+
+        for item in items {
+            if let Item::Use(item_use) = item {
+                imports.collect(&item_use.tree, &mut vec![]);
+            }
+        }
+
+        imports
+    }
+
+    fn collect(&mut self, tree: &UseTree, prefix: &mut Vec<String>) {
+        match tree {
+            UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                self.collect(&path.tree, prefix);
+                prefix.pop();
+            }
+            UseTree::Name(name) => {
+                let name = name.ident.to_string();
+                let mut path = prefix.clone();
+                if name != "self" {
+                    path.push(name.clone());
+                }
+                let key = path.last().cloned().unwrap_or(name);
+                let path = self.resolve(path).join("::");
+                self.names.insert(key, path);
+            }
+            UseTree::Rename(rename) => {
+                let mut path = prefix.clone();
+                if rename.ident != "self" {
+                    path.push(rename.ident.to_string());
+                }
+                let path = self.resolve(path).join("::");
+                self.names.insert(rename.rename.to_string(), path);
+            }
+            UseTree::Group(group) => group
+                .items
+                .iter()
+                .for_each(|tree| self.collect(tree, prefix)),
+            UseTree::Glob(_) => {}
+        }
+    }
+
+    /// Resolve `crate`, `self` and `super` against the module.
+    fn absolute(&self, mut segments: Vec<String>) -> Vec<String> {
+        let mut module: Vec<String> = self
+            .module
+            .as_str()
+            .split("::")
+            .map(str::to_owned)
+            .collect();
+
+        match segments.first().map(String::as_str) {
+            Some("crate") => {
+                segments[0] = module[0].clone();
+                segments
+            }
+            Some("self") => {
+                module.extend(segments.into_iter().skip(1));
+                module
+            }
+            Some("super") => {
+                let supers = segments.iter().take_while(|s| *s == "super").count();
+                module.truncate(module.len().saturating_sub(supers));
+                module.extend(segments.into_iter().skip(supers));
+                module
+            }
+            _ => segments,
+        }
+    }
+
+    /// Resolve the first segment against the imported and declared names, then `crate`,
+    /// `self` and `super`.
+    fn resolve(&self, segments: Vec<String>) -> Vec<String> {
+        match self.names.get(&segments[0]) {
+            Some(name) => {
+                let mut resolved: Vec<String> = name.split("::").map(str::to_owned).collect();
+                resolved.extend(segments.into_iter().skip(1));
+                resolved
+            }
+            None => self.absolute(segments),
+        }
+    }
+
+    pub(crate) fn normalize(&self, ty: &Type) -> NormalizedType {
+        let mut paths = vec![];
+        let name = self.render(ty, &mut paths);
+        NormalizedType { name, paths }
+    }
+
+    fn render(&self, ty: &Type, paths: &mut Vec<String>) -> String {
+        match ty {
+            Type::Path(type_path) => match &type_path.qself {
+                Some(qself) => {
+                    let self_ty = self.render(&qself.ty, paths);
+                    // In `<T as path::Trait>::Assoc` the first `position` segments are the trait
+                    let as_trait = if qself.position > 0 {
+                        let trait_path = Path {
+                            leading_colon: type_path.path.leading_colon,
+                            segments: type_path
+                                .path
+                                .segments
+                                .iter()
+                                .take(qself.position)
+                                .cloned()
+                                .collect(),
+                        };
+                        format!(" as {}", self.render_path(&trait_path, 0, paths))
+                    } else {
+                        String::new()
+                    };
+                    format!(
+                        "<{self_ty}{as_trait}>::{}",
+                        self.render_path(&type_path.path, qself.position, paths)
+                    )
+                }
+                None => self.render_path(&type_path.path, 0, paths),
+            },
+            Type::Reference(reference) => {
+                let lifetime = reference
+                    .lifetime
+                    .as_ref()
+                    .map(|lifetime| format!("'{} ", lifetime.ident))
+                    .unwrap_or_default();
+                let mutability = if reference.mutability.is_some() {
+                    "mut "
+                } else {
+                    ""
+                };
+                format!(
+                    "&{lifetime}{mutability}{}",
+                    self.render(&reference.elem, paths)
+                )
+            }
+            Type::Ptr(ptr) => {
+                let mutability = if ptr.mutability.is_some() {
+                    "mut"
+                } else {
+                    "const"
+                };
+                format!("*{mutability} {}", self.render(&ptr.elem, paths))
+            }
+            Type::Slice(slice) => format!("[{}]", self.render(&slice.elem, paths)),
+            Type::Array(array) => format!(
+                "[{}; {}]",
+                self.render(&array.elem, paths),
+                array.len.to_token_stream()
+            ),
+            Type::Tuple(tuple) => {
+                let elems: Vec<String> = tuple
+                    .elems
+                    .iter()
+                    .map(|elem| self.render(elem, paths))
+                    .collect();
+                format!("({})", elems.join(", "))
+            }
+            Type::TraitObject(object) => {
+                format!("dyn {}", self.render_bounds(object.bounds.iter(), paths))
+            }
+            Type::ImplTrait(imp) => {
+                format!("impl {}", self.render_bounds(imp.bounds.iter(), paths))
+            }
+            Type::BareFn(bare_fn) => {
+                let inputs: Vec<String> = bare_fn
+                    .inputs
+                    .iter()
+                    .map(|arg| self.render(&arg.ty, paths))
+                    .collect();
+                format!(
+                    "fn({}){}",
+                    inputs.join(", "),
+                    self.render_output(&bare_fn.output, paths)
+                )
+            }
+            Type::Paren(paren) => self.render(&paren.elem, paths),
+            Type::Group(group) => self.render(&group.elem, paths),
+            Type::Never(_) => "!".to_owned(),
+            Type::Infer(_) => "_".to_owned(),
+            other => other.to_token_stream().to_string(),
+        }
+    }
+
+    /// Segments before `skip` belong to a qualified self type and are not resolved.
+    fn render_path(&self, path: &Path, skip: usize, paths: &mut Vec<String>) -> String {
+        let mut segments: Vec<String> = path
+            .segments
+            .iter()
+            .skip(skip)
+            .map(|segment| segment.ident.to_string())
+            .collect();
+
+        if skip == 0 && path.leading_colon.is_none() {
+            segments = self.resolve(segments);
+        }
+        paths.push(segments.join("::"));
+
+        // Generic arguments are rendered on the last segment only, where types carry them
+        let arguments = match path.segments.last().map(|segment| &segment.arguments) {
+            Some(PathArguments::AngleBracketed(args)) => {
+                let args: Vec<String> = args
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        GenericArgument::Type(ty) => self.render(ty, paths),
+                        GenericArgument::Lifetime(lifetime) => format!("'{}", lifetime.ident),
+                        GenericArgument::Binding(binding) => {
+                            format!("{} = {}", binding.ident, self.render(&binding.ty, paths))
+                        }
+                        other => other.to_token_stream().to_string(),
+                    })
+                    .collect();
+                format!("<{}>", args.join(", "))
+            }
+            Some(PathArguments::Parenthesized(args)) => {
+                let inputs: Vec<String> = args
+                    .inputs
+                    .iter()
+                    .map(|input| self.render(input, paths))
+                    .collect();
+                format!(
+                    "({}){}",
+                    inputs.join(", "),
+                    self.render_output(&args.output, paths)
+                )
+            }
+            _ => String::new(),
+        };
+
+        format!("{}{arguments}", segments.join("::"))
+    }
+
+    fn render_bounds<'a, I>(&self, bounds: I, paths: &mut Vec<String>) -> String
+    where
+        I: Iterator<Item = &'a TypeParamBound>,
+    {
+        bounds
+            .map(|bound| match bound {
+                TypeParamBound::Trait(bound) => self.render_path(&bound.path, 0, paths),
+                TypeParamBound::Lifetime(lifetime) => format!("'{}", lifetime.ident),
+            })
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+
+    fn render_output(&self, output: &ReturnType, paths: &mut Vec<String>) -> String {
+        match output {
+            ReturnType::Default => String::new(),
+            ReturnType::Type(_, ty) => format!(" -> {}", self.render(ty, paths)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::types::Imports;
+    use crate::ast::ItemPath;
+    use speculoos::prelude::*;
+
+    fn normalize(imports: &str, ty: &str) -> (String, Vec<String>) {
+        let file = syn::parse_file(imports).expect("valid items");
+        let imports = Imports::of(&file.items, &ItemPath::new("app::domain".to_owned()));
+        let normalized = imports.normalize(&syn::parse_str(ty).expect("valid type"));
+        (normalized.name, normalized.paths)
+    }
+
+    #[test]
+    fn should_resolve_imported_names() {
+        let (name, paths) = normalize(
+            "use std::sync::{Arc, Mutex as Lock};\nuse crate::infra::db;",
+            "Arc<Lock<db::Pool>>",
+        );
+
+        assert_that!(name.as_str())
+            .is_equal_to("std::sync::Arc<std::sync::Mutex<app::infra::db::Pool>>");
+        assert_that!(paths).is_equal_to(vec![
+            "std::sync::Arc".to_owned(),
+            "std::sync::Mutex".to_owned(),
+            "app::infra::db::Pool".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn should_qualify_items_and_submodules_of_the_module() {
+        let (name, _) = normalize(
+            "mod point;\nuse point::Point;\nstruct Cache;",
+            "(Point, Cache)",
+        );
+
+        assert_that!(name.as_str()).is_equal_to("(app::domain::point::Point, app::domain::Cache)");
+    }
+
+    #[test]
+    fn should_keep_references_and_relative_paths() {
+        let (name, _) = normalize("", "&'a mut [super::Order; 2]");
+        assert_that!(name.as_str()).is_equal_to("&'a mut [app::Order; 2]");
+
+        let (name, paths) = normalize("", "Option<(u32, Box<dyn Fn(u8) -> bool>)>");
+        assert_that!(name.as_str()).is_equal_to("Option<(u32, Box<dyn Fn(u8) -> bool>)>");
+        assert_that!(paths).has_length(6);
+    }
+
+    #[test]
+    fn should_keep_the_trait_of_qualified_paths() {
+        let (name, paths) = normalize(
+            "use crate::infra::Repository;",
+            "<Vec<u8> as Repository<u8>>::Id",
+        );
+
+        assert_that!(name.as_str()).is_equal_to("<Vec<u8> as app::infra::Repository<u8>>::Id");
+        assert_that!(paths).contains("app::infra::Repository".to_owned());
+
+        let (name, _) = normalize("", "<T>::Output");
+        assert_that!(name.as_str()).is_equal_to("<T>::Output");
+    }
+}
@@ -1,65 +1,78 @@
+use crate::ast::cfg::{attributes_cfg, CfgPredicate};
 use crate::ast::enums::Enum;
+use crate::ast::functions::Function;
 use crate::ast::impl_blocks::Impl;
 use crate::ast::parse::ModuleAst;
 use crate::ast::structs::Struct;
+use crate::ast::traits::Trait;
+use crate::ast::types::Imports;
 use crate::ast::{
-    get_item_mod_cfg, get_item_module_declaration, CodeSpan, ItemPath, LineColumn,
-    ModuleDeclaration, ModuleUse, Visibility,
+    get_item_module_declaration, CodeSpan, ItemPath, LineColumn, ModuleDeclaration, ModuleUse,
+    Visibility,
 };
-use std::env;
 use std::path::{Path, PathBuf};
 use syn::__private::Span;
 use syn::spanned::Spanned;
-use syn::visit::Visit;
-use syn::{visit, File, Ident, Item, ItemMod, VisPublic};
+use syn::{File, Ident, Item, ItemMod, VisPublic};
 
 impl ModuleAst {
     pub fn visit_modules<'ast>(
-        &'ast mut self,
+        &'ast self,
         module_ident: ModuleOrCrateRoot<'ast>,
     ) -> SynModuleTree<'ast> {
-        let mut visitor = FileVisitor::default();
-
-        let mut module = SynModuleTree {
+        SynModuleTree {
             module: ModuleOrFile::SynFile {
                 module: module_ident,
                 file: &self.ast.0,
                 real_path: &self.location.0,
             },
-            submodules: vec![],
-        };
+            submodules: self.visit_items(&self.ast.0.items, &mut vec![]),
+        }
+    }
 
-        visitor.visit_file(&self.ast.0);
+    // Inline modules come first, then modules declared in their own file
+    fn visit_items<'ast>(
+        &'ast self,
+        items: &'ast [Item],
+        enclosing: &mut Vec<String>,
+    ) -> Vec<SynModuleTree<'ast>> {
+        let mut inline_modules = vec![];
+        let mut file_modules = vec![];
+
+        for item_mod in items.iter().filter_map(|item| match item {
+            Item::Mod(item_mod) => Some(item_mod),
+            _ => None,
+        }) {
+            let ident = item_mod.ident.to_string();
+            if let Some((_, items)) = &item_mod.content {
+                enclosing.push(ident);
+                let submodules = self.visit_items(items, enclosing);
+                enclosing.pop();
+                inline_modules.push(SynModuleTree {
+                    module: ModuleOrFile::InnerModule {
+                        module: item_mod,
+                        real_path: &self.location.0,
+                    },
+                    submodules,
+                });
+                continue;
+            }
 
-        let modules: Vec<SynModuleTree> = visitor
-            .inner_modules
-            .iter()
-            .map(|module| SynModuleTree {
-                module: ModuleOrFile::InnerModule {
-                    module,
-                    real_path: &self.location.0,
-                },
-                submodules: vec![],
-            })
-            .collect();
-
-        module.submodules.extend(modules);
-
-        let mut iter_mut = self.submodules.iter_mut();
-
-        for item_mod in visitor.file_modules {
-            let mod_name = item_mod.ident.to_string();
-            let subtree_ast = iter_mut
-                .find(|sub_module_ast| sub_module_ast.name == mod_name)
-                .expect("submodule exist");
-            let sub_tree = subtree_ast.visit_modules(ModuleOrCrateRoot::Module {
-                module: item_mod,
-                real_path: subtree_ast.location.0.clone(),
-            });
-            module.submodules.push(sub_tree);
+            // Modules disabled by a cfg attribute or skipped as unparseable are not loaded
+            if let Some(subtree_ast) = self
+                .submodules
+                .iter()
+                .find(|module| module.name == ident && &module.enclosing == enclosing)
+            {
+                file_modules.push(subtree_ast.visit_modules(ModuleOrCrateRoot::Module {
+                    module: item_mod,
+                    real_path: subtree_ast.location.0.clone(),
+                }));
+            }
         }
 
-        module
+        inline_modules.extend(file_modules);
+        inline_modules
     }
 }
 
@@ -84,7 +97,8 @@ pub enum ModuleOrFile<'ast> {
 
 #[derive(Debug)]
 pub enum ModuleOrCrateRoot<'ast> {
-    CrateRoot,
+    /// The crate root module, named after the crate
+    CrateRoot(String),
     Module {
         module: &'ast ItemMod,
         real_path: PathBuf,
@@ -99,10 +113,16 @@ impl ModuleOrFile<'_> {
         }
     }
 
-    pub fn cfg_attr(&self) -> Vec<String> {
+    pub fn cfg(&self) -> Option<CfgPredicate> {
         match self {
-            ModuleOrFile::InnerModule { module, .. } => get_item_mod_cfg(module),
-            ModuleOrFile::SynFile { module, .. } => module.cfg_attr(),
+            ModuleOrFile::InnerModule { module, .. } => attributes_cfg(&module.attrs),
+            // Files may also be conditionally compiled with an inner `#![cfg(...)]` attribute
+            ModuleOrFile::SynFile { module, file, .. } => match module {
+                ModuleOrCrateRoot::CrateRoot(_) => attributes_cfg(&file.attrs),
+                ModuleOrCrateRoot::Module { module, .. } => {
+                    attributes_cfg(module.attrs.iter().chain(&file.attrs))
+                }
+            },
         }
     }
 
@@ -152,7 +172,7 @@ impl ModuleOrFile<'_> {
                 let mut structs = get_files_impls(file, path);
 
                 match module {
-                    ModuleOrCrateRoot::CrateRoot => {}
+                    ModuleOrCrateRoot::CrateRoot(_) => {}
                     ModuleOrCrateRoot::Module { module, .. } => {
                         structs.extend(get_module_impls(module, path))
                     }
@@ -176,7 +196,7 @@ impl ModuleOrFile<'_> {
                 let mut structs = get_file_structs(file, path, real_path);
 
                 match module {
-                    ModuleOrCrateRoot::CrateRoot => {}
+                    ModuleOrCrateRoot::CrateRoot(_) => {}
                     ModuleOrCrateRoot::Module { module, real_path } => {
                         structs.extend(get_module_structs(module, path, real_path))
                     }
@@ -200,7 +220,7 @@ impl ModuleOrFile<'_> {
                 let mut structs = get_file_enums(file, path, real_path);
 
                 match module {
-                    ModuleOrCrateRoot::CrateRoot => {}
+                    ModuleOrCrateRoot::CrateRoot(_) => {}
                     ModuleOrCrateRoot::Module { module, real_path } => {
                         structs.extend(get_module_enums(module, path, real_path))
                     }
@@ -210,35 +230,85 @@ impl ModuleOrFile<'_> {
             }
         }
     }
-}
 
-impl ModuleOrCrateRoot<'_> {
-    fn cfg_attr(&self) -> Vec<String> {
+    pub fn traits(&self, path: &ItemPath) -> Vec<Trait> {
         match self {
-            ModuleOrCrateRoot::CrateRoot => vec![],
-            ModuleOrCrateRoot::Module { module, .. } => get_item_mod_cfg(module),
+            ModuleOrFile::InnerModule {
+                module, real_path, ..
+            } => get_module_traits(module, path, real_path),
+            ModuleOrFile::SynFile {
+                module,
+                file,
+                real_path,
+            } => {
+                let mut traits = get_file_traits(file, path, real_path);
+
+                match module {
+                    ModuleOrCrateRoot::CrateRoot(_) => {}
+                    ModuleOrCrateRoot::Module { module, real_path } => {
+                        traits.extend(get_module_traits(module, path, real_path))
+                    }
+                };
+
+                traits
+            }
         }
     }
 
+    pub fn functions(&self, path: &ItemPath) -> Vec<Function> {
+        match self {
+            ModuleOrFile::InnerModule {
+                module, real_path, ..
+            } => get_module_functions(module, path, real_path),
+            ModuleOrFile::SynFile {
+                module,
+                file,
+                real_path,
+            } => {
+                let mut functions = get_items_functions(&file.items, path, real_path);
+
+                match module {
+                    ModuleOrCrateRoot::CrateRoot(_) => {}
+                    ModuleOrCrateRoot::Module { module, real_path } => {
+                        functions.extend(get_module_functions(module, path, real_path))
+                    }
+                };
+
+                functions
+            }
+        }
+    }
+
+    /// Lines of the inline module block, or of the whole file.
+    pub fn lines(&self) -> usize {
+        match self {
+            ModuleOrFile::InnerModule { module, .. } => {
+                let span = module.span();
+                span.end().line + 1 - span.start().line
+            }
+            ModuleOrFile::SynFile { file, .. } => file.span().end().line,
+        }
+    }
+}
+
+impl ModuleOrCrateRoot<'_> {
     fn span(&self) -> Option<CodeSpan> {
         match self {
-            ModuleOrCrateRoot::CrateRoot => None,
+            ModuleOrCrateRoot::CrateRoot(_) => None,
             ModuleOrCrateRoot::Module { module, .. } => Some(module.ident.span().into()),
         }
     }
 
     fn ident(&self) -> Ident {
-        let name = env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME must be set");
-        let name = name.replace('-', "_");
         match self {
-            ModuleOrCrateRoot::CrateRoot => Ident::new(name.as_str(), Span::mixed_site()),
+            ModuleOrCrateRoot::CrateRoot(name) => Ident::new(name, Span::mixed_site()),
             ModuleOrCrateRoot::Module { module, .. } => module.ident.clone(),
         }
     }
 
     fn vis(&self) -> syn::Visibility {
         match self {
-            ModuleOrCrateRoot::CrateRoot => syn::Visibility::Public(VisPublic {
+            ModuleOrCrateRoot::CrateRoot(_) => syn::Visibility::Public(VisPublic {
                 pub_token: Default::default(),
             }),
             ModuleOrCrateRoot::Module { module, .. } => module.vis.clone(),
@@ -246,38 +316,6 @@ impl ModuleOrCrateRoot<'_> {
     }
 }
 
-#[derive(Debug, Default)]
-pub struct FileVisitor<'ast> {
-    inner_modules: Vec<&'ast ItemMod>,
-    file_modules: Vec<&'ast ItemMod>,
-}
-
-impl<'ast> Visit<'ast> for FileVisitor<'ast> {
-    fn visit_file(&mut self, node: &'ast File) {
-        node.items
-            .iter()
-            .filter_map(|item| {
-                if let Item::Mod(module) = item {
-                    Some(module)
-                } else {
-                    None
-                }
-            })
-            .for_each(|module| {
-                if module.content.is_some() {
-                    self.inner_modules.push(module);
-                    self.visit_item_mod(module);
-                } else {
-                    self.file_modules.push(module)
-                }
-            })
-    }
-
-    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
-        visit::visit_item_mod(self, node)
-    }
-}
-
 fn get_module_use_item(module: &ItemMod) -> Vec<ModuleUse> {
     module
         .content
@@ -310,10 +348,11 @@ fn get_module_impls(module: &ItemMod, path: &ItemPath) -> Vec<Impl> {
 
 fn get_module_structs(module: &ItemMod, path: &ItemPath, real_path: &Path) -> Vec<Struct> {
     if let Some((_, items)) = &module.content {
+        let imports = Imports::of(items, path);
         items
             .iter()
             .filter_map(|item| match item {
-                Item::Struct(struct_) => Some(Struct::from_syn(struct_, path, real_path)),
+                Item::Struct(struct_) => Some(Struct::from_syn(struct_, path, real_path, &imports)),
                 _ => None,
             })
             .collect()
@@ -324,10 +363,11 @@ fn get_module_structs(module: &ItemMod, path: &ItemPath, real_path: &Path) -> Ve
 
 fn get_module_enums(module: &ItemMod, path: &ItemPath, real_path: &Path) -> Vec<Enum> {
     if let Some((_, items)) = &module.content {
+        let imports = Imports::of(items, path);
         items
             .iter()
             .filter_map(|item| match item {
-                Item::Enum(enum_) => Some(Enum::from_syn(enum_, path, real_path)),
+                Item::Enum(enum_) => Some(Enum::from_syn(enum_, path, real_path, &imports)),
                 _ => None,
             })
             .collect()
@@ -347,25 +387,70 @@ fn get_files_impls(file: &File, path: &ItemPath) -> Vec<Impl> {
 }
 
 fn get_file_structs(file: &File, path: &ItemPath, real_path: &Path) -> Vec<Struct> {
+    let imports = Imports::of(&file.items, path);
     file.items
         .iter()
         .filter_map(|item| match item {
-            Item::Struct(struct_) => Some(Struct::from_syn(struct_, path, real_path)),
+            Item::Struct(struct_) => Some(Struct::from_syn(struct_, path, real_path, &imports)),
             _ => None,
         })
         .collect()
 }
 
 fn get_file_enums(file: &File, path: &ItemPath, real_path: &Path) -> Vec<Enum> {
+    let imports = Imports::of(&file.items, path);
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Enum(enum_) => Some(Enum::from_syn(enum_, path, real_path, &imports)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_module_traits(module: &ItemMod, path: &ItemPath, real_path: &Path) -> Vec<Trait> {
+    if let Some((_, items)) = &module.content {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Trait(trait_) => Some(Trait::from_syn(trait_, path, real_path)),
+                _ => None,
+            })
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+fn get_file_traits(file: &File, path: &ItemPath, real_path: &Path) -> Vec<Trait> {
     file.items
         .iter()
         .filter_map(|item| match item {
-            Item::Enum(enum_) => Some(Enum::from_syn(enum_, path, real_path)),
+            Item::Trait(trait_) => Some(Trait::from_syn(trait_, path, real_path)),
             _ => None,
         })
         .collect()
 }
 
+fn get_module_functions(module: &ItemMod, path: &ItemPath, real_path: &Path) -> Vec<Function> {
+    if let Some((_, items)) = &module.content {
+        get_items_functions(items, path, real_path)
+    } else {
+        vec![]
+    }
+}
+
+fn get_items_functions(items: &[Item], path: &ItemPath, real_path: &Path) -> Vec<Function> {
+    items
+        .iter()
+        .flat_map(|item| match item {
+            Item::Fn(item_fn) => vec![Function::from_syn(item_fn, path, real_path)],
+            Item::Impl(imp) => Function::from_impl(imp, path, real_path),
+            _ => vec![],
+        })
+        .collect()
+}
+
 fn get_files_use_item(file: &File) -> Vec<ModuleUse> {
     file.items
         .iter()
@@ -410,14 +495,66 @@ fn get_file_mod_item(file: &File, real_path: &Path) -> Vec<ModuleDeclaration> {
 
 #[cfg(test)]
 mod test {
-    use crate::ast::parse::ModuleAst;
+    use crate::ast::parse::{LoadOptions, ModuleAst};
     use crate::ast::visitor::ModuleOrCrateRoot;
+    use crate::ast::ItemPath;
+    use crate::ModuleTree;
+    use speculoos::prelude::*;
+    use std::path::Path;
 
     #[test]
     fn should_visit_crate_modules() {
-        ModuleAst::load_crate_ast();
-        let mut ast = ModuleAst::load_crate_ast();
-        let tree = ast.visit_modules(ModuleOrCrateRoot::CrateRoot);
+        let ast = ModuleAst::load_crate_ast(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs"),
+            "archunit_rs",
+            &LoadOptions::default(),
+        )
+        .expect("own crate");
+        let tree = ast.visit_modules(ModuleOrCrateRoot::CrateRoot(ast.name.clone()));
         let _root = &tree.module;
     }
+
+    #[test]
+    fn should_nest_file_modules_declared_in_inline_modules() {
+        let ast = ModuleAst::load_crate_ast(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/module_layouts/src/lib.rs"),
+            "module-layouts",
+            &LoadOptions::default(),
+        )
+        .expect("fixture crate");
+        let tree = ast
+            .visit_modules(ModuleOrCrateRoot::CrateRoot(ast.name.clone()))
+            .to_tree(&ItemPath::empty(), None);
+
+        fn collect_paths(tree: &ModuleTree, paths: &mut Vec<String>) {
+            paths.push(tree.path.to_string());
+            tree.submodules
+                .iter()
+                .for_each(|module| collect_paths(module, paths));
+        }
+
+        let mut found = vec![];
+        collect_paths(&tree, &mut found);
+        found.sort();
+
+        assert_that!(found).is_equal_to(
+            [
+                "module_layouts",
+                "module_layouts::config",
+                "module_layouts::config::loader",
+                "module_layouts::custom",
+                "module_layouts::custom::sibling",
+                "module_layouts::nested",
+                "module_layouts::nested::child",
+                "module_layouts::nested::child::grandchild",
+                "module_layouts::nested::inline",
+                "module_layouts::nested::inline::deep",
+                "module_layouts::store",
+                "module_layouts::store::inner",
+                "module_layouts::store::inner::backend",
+            ]
+            .map(str::to_owned)
+            .to_vec(),
+        );
+    }
 }
@@ -0,0 +1,265 @@
+use crate::ast::manifest::{
+    code_span, parse_manifest, read_manifest, read_to_string, ManifestError, RawDependencies,
+};
+use crate::ast::parse::{cargo_env, LoadError, LoadOptions};
+use crate::ast::targets::CrateTargets;
+use crate::ast::CodeSpan;
+use crate::ModuleTree;
+use once_cell::sync::OnceCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use wildmatch::WildMatch;
+
+/// The workspace of the crate under test, loaded once.
+pub(crate) fn workspace() -> &'static Workspace {
+    static WORKSPACE: OnceCell<Workspace> = OnceCell::new();
+    WORKSPACE.get_or_init(
+        || match cargo_env("CARGO_MANIFEST_DIR").and_then(Workspace::discover) {
+            Ok(workspace) => workspace,
+            Err(err) => panic!("{:?}", miette::ErrReport::from(err)),
+        },
+    )
+}
+
+/// A cargo workspace and the module tree of each of its members.
+///
+/// Manifests are read from disk only, nothing is resolved through the network: dependencies are
+/// known by the names declared in each member `Cargo.toml`.
+#[derive(Debug)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub crates: Vec<WorkspaceCrate>,
+}
+
+/// A workspace member.
+#[derive(Debug)]
+pub struct WorkspaceCrate {
+    /// The package name, as declared in its manifest.
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub dependencies: Vec<CrateDependency>,
+    pub tree: ModuleTree,
+}
+
+/// A dependency declared in a member manifest.
+#[derive(Debug)]
+pub struct CrateDependency {
+    /// The package name of the dependency.
+    pub name: String,
+    /// The name the dependency is declared and used with, differs from `name` when renamed.
+    pub alias: String,
+    pub kind: DependencyKind,
+    /// Location of the dependency key in the manifest.
+    pub span: CodeSpan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl Workspace {
+    /// Find the workspace `manifest_dir` belongs to, like cargo does: the closest ancestor
+    /// manifest with a `[workspace]` table, or the crate itself if there is none.
+    pub fn discover<P: AsRef<Path>>(manifest_dir: P) -> Result<Self, LoadError> {
+        let manifest_dir = manifest_dir.as_ref();
+        for dir in manifest_dir.ancestors() {
+            let manifest_path = dir.join("Cargo.toml");
+            if manifest_path.exists() && read_manifest(&manifest_path)?.workspace.is_some() {
+                return Self::load(dir);
+            }
+        }
+
+        Self::load(manifest_dir)
+    }
+
+    /// Load the workspace whose root manifest is located in `root`, a manifest without a
+    /// `[workspace]` table is loaded as a single crate workspace.
+    pub fn load<P: AsRef<Path>>(root: P) -> Result<Self, LoadError> {
+        let root = root.as_ref().to_path_buf();
+        let manifest = read_manifest(&root.join("Cargo.toml"))?;
+        let mut member_dirs = vec![];
+
+        if manifest.package.is_some() {
+            member_dirs.push(root.clone());
+        }
+
+        if let Some(workspace) = &manifest.workspace {
+            let excluded: Vec<PathBuf> = workspace
+                .exclude
+                .iter()
+                .map(|path| root.join(path))
+                .collect();
+
+            for member in &workspace.members {
+                for dir in expand_member(&root, member) {
+                    if !excluded.contains(&dir) && !member_dirs.contains(&dir) {
+                        member_dirs.push(dir);
+                    }
+                }
+            }
+        }
+
+        let crates = member_dirs
+            .iter()
+            .map(|dir| WorkspaceCrate::load(dir))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Workspace { root, crates })
+    }
+
+    /// Look up a member by package name.
+    pub fn member(&self, name: &str) -> Option<&WorkspaceCrate> {
+        self.crates.iter().find(|krate| krate.name == name)
+    }
+}
+
+impl WorkspaceCrate {
+    fn load(manifest_dir: &Path) -> Result<Self, LoadError> {
+        let manifest_path = manifest_dir.join("Cargo.toml");
+        let content = read_to_string(&manifest_path)?;
+        let manifest = parse_manifest(&manifest_path, &content)?;
+        let Some(package) = manifest.package else {
+            return Err(ManifestError::Invalid {
+                path: manifest_path,
+                message: "workspace members must have a [package] table".to_owned(),
+            }
+            .into());
+        };
+
+        let mut dependencies = vec![];
+        let mut push_all = |table: RawDependencies, kind| {
+            for (key, dependency) in table {
+                let span = code_span(&content, key.span());
+                let alias = key.into_inner();
+                // Renamed dependencies declare their package name with `package = "..."`
+                let name = dependency
+                    .get("package")
+                    .and_then(toml::Value::as_str)
+                    .map_or_else(|| alias.clone(), str::to_owned);
+
+                dependencies.push(CrateDependency {
+                    name,
+                    alias,
+                    kind,
+                    span,
+                });
+            }
+        };
+
+        push_all(manifest.dependencies, DependencyKind::Normal);
+        push_all(manifest.dev_dependencies, DependencyKind::Dev);
+        push_all(manifest.build_dependencies, DependencyKind::Build);
+        for target in manifest.target.into_values() {
+            push_all(target.dependencies, DependencyKind::Normal);
+            push_all(target.dev_dependencies, DependencyKind::Dev);
+            push_all(target.build_dependencies, DependencyKind::Build);
+        }
+
+        dependencies.sort_by_key(|dependency| dependency.span);
+
+        let targets = CrateTargets::discover(manifest_dir)?;
+        let Some(target) = targets.default_target() else {
+            return Err(LoadError::NoTarget {
+                path: manifest_dir.to_path_buf(),
+            });
+        };
+        let tree = ModuleTree::try_load_target(target, &LoadOptions::from_env())?;
+
+        Ok(WorkspaceCrate {
+            name: package.name,
+            manifest_path,
+            dependencies,
+            tree,
+        })
+    }
+}
+
+impl CrateDependency {
+    /// The identifier this dependency is referred to with in `use` paths.
+    pub fn ident(&self) -> String {
+        self.alias.replace('-', "_")
+    }
+}
+
+/// Expand a `members` entry, each path segment may contain `*` and `?` wildcards.
+fn expand_member(root: &Path, member: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+
+    for segment in member.split('/').filter(|segment| !segment.is_empty()) {
+        if !segment.contains(['*', '?']) {
+            dirs = dirs.into_iter().map(|dir| dir.join(segment)).collect();
+            continue;
+        }
+
+        let pattern = WildMatch::new(segment);
+        let mut matches: Vec<PathBuf> = dirs
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|entry| pattern.matches(&entry.file_name().to_string_lossy()))
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        matches.sort();
+        dirs = matches;
+    }
+
+    dirs.retain(|dir| dir.join("Cargo.toml").exists());
+    dirs
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::workspace::{DependencyKind, Workspace};
+    use speculoos::prelude::*;
+    use std::path::Path;
+
+    #[test]
+    fn should_load_workspace_members() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspace");
+        let workspace = Workspace::load(&root).expect("fixture workspace");
+
+        let names: Vec<&str> = workspace
+            .crates
+            .iter()
+            .map(|krate| krate.name.as_str())
+            .collect();
+        assert_that!(names).is_equal_to(vec!["shop-app", "shop-domain", "shop-infra"]);
+
+        let domain = workspace.member("shop-domain").expect("domain member");
+        assert_that!(domain.tree.ident.as_str()).is_equal_to("shop_domain");
+        assert_that!(domain.dependencies).has_length(1);
+        assert_that!(domain.dependencies[0].name.as_str()).is_equal_to("shop-infra");
+        assert_that!(domain.dependencies[0].ident()).is_equal_to("shop_infra".to_owned());
+        assert_that!(domain.dependencies[0].kind).is_equal_to(DependencyKind::Normal);
+        assert_that!(domain.dependencies[0].span.start.line).is_equal_to(7);
+    }
+
+    #[test]
+    fn should_discover_workspace_from_member() {
+        let member = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/workspace/crates/shop-infra");
+        let workspace = Workspace::discover(member).expect("fixture workspace");
+
+        assert_that!(workspace.crates).has_length(3);
+    }
+
+    #[test]
+    fn should_resolve_renamed_dependencies() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspace");
+        let workspace = Workspace::load(root).expect("fixture workspace");
+        let app = workspace.member("shop-app").expect("app member");
+
+        let renamed = app
+            .dependencies
+            .iter()
+            .find(|dependency| dependency.alias == "store")
+            .expect("renamed dependency");
+        assert_that!(renamed.name.as_str()).is_equal_to("shop-infra");
+        assert_that!(renamed.ident()).is_equal_to("store".to_owned());
+    }
+}
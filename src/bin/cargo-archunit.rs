@@ -0,0 +1,206 @@
+//! `cargo archunit`: evaluate the rules of an `archunit.toml` file against a crate, without
+//! compiling its test suite.
+
+use archunit_rs::config::ArchConfig;
+use archunit_rs::report::json::JsonReport;
+use archunit_rs::report::sarif::SarifReport;
+use archunit_rs::report::text::ReportConfig;
+use archunit_rs::{LoadOptions, ModuleTree};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const USAGE: &str = "Usage: cargo archunit [OPTIONS]
+
+Options:
+  --manifest-path <PATH>  Path to the Cargo.toml of the crate to analyze [default: Cargo.toml]
+  --config <PATH>         Rule configuration file [default: archunit.toml next to the manifest]
+  --format <FORMAT>       Output format: text, json or sarif [default: text]
+  --skip-unparseable      Skip the module files that cannot be parsed instead of failing
+  -h, --help              Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            other => Err(format!(
+                "Unknown format '{other}', expected one of 'text', 'json' or 'sarif'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Args {
+    manifest_path: PathBuf,
+    config: Option<PathBuf>,
+    format: OutputFormat,
+    load_options: LoadOptions,
+}
+
+impl Args {
+    fn parse() -> Result<Option<Self>, String> {
+        let mut args = env::args().skip(1).peekable();
+        // Invoked as `cargo archunit`, cargo passes the subcommand name as first argument
+        args.next_if(|arg| arg == "archunit");
+
+        let mut parsed = Args {
+            manifest_path: PathBuf::from("Cargo.toml"),
+            config: None,
+            format: OutputFormat::Text,
+            load_options: LoadOptions::from_env(),
+        };
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_owned(), Some(value.to_owned())),
+                None => (arg, None),
+            };
+
+            if flag == "-h" || flag == "--help" {
+                return Ok(None);
+            }
+
+            if flag == "--skip-unparseable" {
+                parsed.load_options = parsed.load_options.skip_unparseable();
+                continue;
+            }
+
+            let value = inline_value
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("Missing value for '{flag}'"))?;
+
+            match flag.as_str() {
+                "--manifest-path" => parsed.manifest_path = PathBuf::from(value),
+                "--config" => parsed.config = Some(PathBuf::from(value)),
+                "--format" => parsed.format = value.parse()?,
+                other => return Err(format!("Unknown argument '{other}'")),
+            }
+        }
+
+        Ok(Some(parsed))
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err:?}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Evaluate the configured rules, returns `false` if any of them failed.
+fn run(args: Args) -> miette::Result<bool> {
+    let manifest_path = args
+        .manifest_path
+        .canonicalize()
+        .map_err(|err| miette::miette!("{}: {err}", args.manifest_path.display()))?;
+    let manifest_dir = manifest_path
+        .parent()
+        .expect("manifest path should have a parent")
+        .to_path_buf();
+    let config_path = match args.config {
+        Some(config) => config,
+        None => manifest_dir.join("archunit.toml"),
+    };
+
+    let config = ArchConfig::load(&config_path)?;
+    let crate_name = package_name(&manifest_path)?;
+
+    // Violations are reported relative to the analyzed crate
+    env::set_current_dir(&manifest_dir)
+        .map_err(|err| miette::miette!("{}: {err}", manifest_dir.display()))?;
+
+    // Rules borrow the trees for the rest of the program, like the one `cargo test` loads
+    let trees: &'static [ModuleTree] = match config.targets() {
+        None => Box::leak(Box::new([ModuleTree::try_load_from(
+            &manifest_dir,
+            &crate_name,
+            &args.load_options,
+        )?])),
+        Some(kinds) => {
+            ModuleTree::try_load_targets(&manifest_dir, kinds, &args.load_options)?.leak()
+        }
+    };
+    let results: Vec<_> = trees
+        .iter()
+        .flat_map(|tree| config.evaluate_in(tree))
+        .collect();
+    let success = results.iter().all(|result| result.is_success());
+    let rule_set = config_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "archunit".to_owned());
+
+    match args.format {
+        OutputFormat::Text => {
+            let report_config = ReportConfig::from_env();
+            for result in results.iter().filter(|result| !result.is_success()) {
+                println!("{}", result.render(&report_config));
+            }
+
+            let failed = results.iter().filter(|result| !result.is_success()).count();
+            println!("{} rules checked, {failed} failed", results.len());
+        }
+        OutputFormat::Json => {
+            let mut report = JsonReport::new();
+            report.add_rule_set(&rule_set, &results);
+            println!("{}", report.to_json());
+        }
+        OutputFormat::Sarif => {
+            let mut report = SarifReport::new();
+            report.add_run(&rule_set, &results);
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(success)
+}
+
+fn package_name(manifest_path: &Path) -> miette::Result<String> {
+    let manifest = fs::read_to_string(manifest_path)
+        .map_err(|err| miette::miette!("{}: {err}", manifest_path.display()))?;
+    let manifest: toml::Table = toml::from_str(&manifest)
+        .map_err(|err| miette::miette!("{}: {err}", manifest_path.display()))?;
+
+    manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+        .map(|name| name.replace('-', "_"))
+        .ok_or_else(|| {
+            miette::miette!(
+                "{} has no [package] name, workspace manifests are not supported",
+                manifest_path.display()
+            )
+        })
+}
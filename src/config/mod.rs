@@ -0,0 +1,811 @@
+//! Declarative rules loaded from a TOML file.
+//!
+//! Each rule lists its conditions (`that`) and assertions (`should`) using the builder
+//! function names, arguments are given between parenthesis. Layers are declared by name and
+//! module path, then constrained with `layer_rules`:
+//!
+//! ```toml
+//! exclude_cfg = ["test"]
+//!
+//! [[structs]]
+//! that = ["implement(Display)", "and", "reside_in_a_module(my_crate::model::*)"]
+//! should = ["be_public", "and_should", "derive(Debug)"]
+//! because = "model types are part of the public API"
+//!
+//! [[modules]]
+//! that = ["reside_in_a_module(my_crate::domain::*)"]
+//! should = ["only_have_dependency_module", "that", "have_simple_name(domain)"]
+//!
+//! [layers]
+//! domain = "my_crate::domain"
+//! infra = "my_crate::infra"
+//! app = "my_crate::app"
+//!
+//! [[layer_rules]]
+//! where_layer = "domain"
+//! may_only_be_accessed_by_layers = ["app", "infra"]
+//!
+//! [[layer_rules]]
+//! where_layer = "app"
+//! may_not_be_accessed_by_any_layer = true
+//! ```
+//!
+//! When `that` is omitted the rule applies to every item of its kind.
+//!
+//! Rules are evaluated against the library by default, `targets` selects the kinds of cargo
+//! targets to analyze instead, each selected target is checked on its own:
+//!
+//! ```toml
+//! targets = ["lib", "bin", "example", "test"]
+//! ```
+//!
+//! Items are filtered on their `cfg` predicate: `exclude_cfg` lists the options considered
+//! unset, `active_cfg` gives the full set of options of the build to analyze, in the `--cfg`
+//! flag syntax:
+//!
+//! ```toml
+//! active_cfg = ["unix", 'target_os = "linux"', 'feature = "serde"']
+//! ```
+
+use crate::assertion_result::AssertionResult;
+use crate::ast::module_tree;
+use crate::ast::targets::{target_trees, TargetKind};
+use crate::layer_rule::{LayerAssertion, LayeredArchitecture};
+use crate::report::text::ReportConfig;
+use crate::rule::assertable::Assertable;
+use crate::rule::{
+    enums, modules, structs, ArchRule, Assertion, CheckRule, Condition,
+    PredicateConjunctionBuilder, Subject,
+};
+use crate::{CfgOption, CfgSet, ExludeModules, ModuleTree};
+use miette::{Diagnostic, ErrReport, NamedSource, SourceSpan};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use tokens::{token_kind, TokenKind};
+use toml::Spanned;
+
+mod tokens;
+
+/// Evaluate the rules declared in the configuration file at `path` and panic if the file is
+/// invalid or any rule is violated, the report is configured via [`ReportConfig::from_env`].
+///
+/// **Example:**
+/// ```rust
+/// archunit_rs::check_config("archunit.toml");
+/// ```
+pub fn check_config<P: AsRef<Path>>(path: P) {
+    let config = match ArchConfig::load(path) {
+        Ok(config) => config,
+        Err(err) => panic!("{:?}", ErrReport::from(err)),
+    };
+
+    let report_config = ReportConfig::from_env();
+    let failures: Vec<String> = config
+        .evaluate()
+        .iter()
+        .filter(|result| !result.is_success())
+        .map(|result| result.render(&report_config))
+        .collect();
+
+    if !failures.is_empty() {
+        panic!("{}", failures.join("\n"))
+    }
+}
+
+/// A set of rules parsed from a configuration file, see the [module documentation](self).
+///
+/// **Example:**
+/// ```rust
+/// use archunit_rs::config::ArchConfig;
+///
+/// let config: ArchConfig = r#"
+///     [[enums]]
+///     should = ["implement_or_derive(Debug)"]
+/// "#
+/// .parse()
+/// .expect("valid config");
+///
+/// let results = config.evaluate();
+/// assert!(results.iter().all(|result| result.is_success()));
+/// ```
+#[derive(Debug)]
+pub struct ArchConfig {
+    filters: ExludeModules<'static>,
+    targets: Option<Vec<TargetKind>>,
+    rules: Vec<ConfiguredRule>,
+}
+
+#[derive(Debug)]
+enum ConfiguredRule {
+    Structs(TokenRule<structs::ConditionToken, structs::AssertionToken>),
+    Enums(TokenRule<enums::ConditionToken, enums::AssertionToken>),
+    Modules(TokenRule<modules::ConditionToken, modules::AssertionToken>),
+    Layers(LayeredArchitecture),
+}
+
+#[derive(Debug)]
+struct TokenRule<C, A> {
+    conditions: Vec<C>,
+    assertions: Vec<A>,
+    because: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    exclude_cfg: Vec<String>,
+    active_cfg: Option<Vec<CfgOption>>,
+    targets: Option<Vec<TargetKind>>,
+    #[serde(default)]
+    structs: Vec<RawRule>,
+    #[serde(default)]
+    enums: Vec<RawRule>,
+    #[serde(default)]
+    modules: Vec<RawRule>,
+    #[serde(default)]
+    layers: BTreeMap<String, String>,
+    #[serde(default)]
+    layer_rules: Vec<Spanned<RawLayerRule>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    that: Option<Spanned<Vec<Spanned<String>>>>,
+    should: Spanned<Vec<Spanned<String>>>,
+    because: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLayerRule {
+    where_layer: Spanned<String>,
+    may_only_be_accessed_by_layers: Option<Vec<Spanned<String>>>,
+    #[serde(default)]
+    may_not_be_accessed_by_any_layer: bool,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum ConfigError {
+    #[error("Unable to read config file '{}'", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Invalid config file: {message}")]
+    Toml {
+        message: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("here")]
+        span: Option<SourceSpan>,
+    },
+    #[error("Unknown {kind} '{token}'")]
+    #[diagnostic(help("Check the spelling and the arguments against the {subject} rule builder"))]
+    UnknownToken {
+        subject: &'static str,
+        kind: &'static str,
+        token: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("unknown {kind}")]
+        span: SourceSpan,
+    },
+    #[error("Unexpected {kind} '{token}'")]
+    #[diagnostic(help("Conditions and assertions must be separated by a single conjunction"))]
+    UnexpectedToken {
+        kind: &'static str,
+        token: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("unexpected {kind}")]
+        span: SourceSpan,
+    },
+    #[error("Missing {kind}")]
+    MissingToken {
+        kind: &'static str,
+        #[source_code]
+        src: NamedSource,
+        #[label("expected a {kind} at the end of this list")]
+        span: SourceSpan,
+    },
+    #[error("Undefined layer '{layer}'")]
+    #[diagnostic(help("Declare it in the [layers] table"))]
+    UndefinedLayer {
+        layer: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("undefined layer")]
+        span: SourceSpan,
+    },
+    #[error("Invalid layer rule")]
+    #[diagnostic(help(
+        "Set exactly one of `may_only_be_accessed_by_layers` or `may_not_be_accessed_by_any_layer`"
+    ))]
+    InvalidLayerRule {
+        #[source_code]
+        src: NamedSource,
+        #[label("in this rule")]
+        span: SourceSpan,
+    },
+    #[error("Duplicate rule for layer '{layer}'")]
+    #[diagnostic(help("Merge the rules for this layer into a single [[layer_rules]] entry"))]
+    DuplicateLayerRule {
+        layer: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("second rule for this layer")]
+        span: SourceSpan,
+    },
+}
+
+/// The configuration file being parsed, used to point errors at the offending line.
+#[derive(Debug)]
+struct Source<'a> {
+    name: &'a str,
+    content: &'a str,
+}
+
+impl Source<'_> {
+    fn named(&self) -> NamedSource {
+        NamedSource::new(self.name, self.content.to_owned())
+    }
+}
+
+impl ArchConfig {
+    /// Read and parse the configuration file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Self::parse(&Source {
+            name: &path.to_string_lossy(),
+            content: &content,
+        })
+    }
+
+    /// The kinds of targets selected with `targets`, if any.
+    pub fn targets(&self) -> Option<&[TargetKind]> {
+        self.targets.as_deref()
+    }
+
+    /// Evaluate every configured rule, in declaration order, against the crate under test or
+    /// each of its selected targets.
+    pub fn evaluate(&self) -> Vec<AssertionResult> {
+        match self.targets() {
+            None => self.evaluate_in(module_tree()),
+            Some(kinds) => target_trees(kinds)
+                .into_iter()
+                .flat_map(|tree| self.evaluate_in(tree))
+                .collect(),
+        }
+    }
+
+    /// Same as [`ArchConfig::evaluate`], against the given tree instead of the crate under test.
+    pub fn evaluate_in(&self, tree: &'static ModuleTree) -> Vec<AssertionResult> {
+        let filters = &self.filters;
+        self.rules
+            .iter()
+            .map(|rule| match rule {
+                ConfiguredRule::Structs(rule) => {
+                    rule.evaluate::<structs::StructMatches>(tree, filters.clone())
+                }
+                ConfiguredRule::Enums(rule) => {
+                    rule.evaluate::<enums::EnumMatches>(tree, filters.clone())
+                }
+                ConfiguredRule::Modules(rule) => {
+                    rule.evaluate::<modules::ModuleMatches>(tree, filters.clone())
+                }
+                ConfiguredRule::Layers(architecture) => architecture.evaluate_in(tree),
+            })
+            .collect()
+    }
+
+    fn parse(source: &Source) -> Result<Self, ConfigError> {
+        let raw: RawConfig = toml::from_str(source.content).map_err(|err| ConfigError::Toml {
+            message: err.message().to_owned(),
+            src: source.named(),
+            span: err.span().map(SourceSpan::from),
+        })?;
+
+        let mut rules = vec![];
+
+        for rule in &raw.structs {
+            rules.push((
+                rule.start(),
+                ConfiguredRule::Structs(rule.parse(
+                    source,
+                    "struct",
+                    tokens::structs_condition,
+                    structs::ConditionToken::Should,
+                    tokens::structs_assertion,
+                )?),
+            ));
+        }
+
+        for rule in &raw.enums {
+            rules.push((
+                rule.start(),
+                ConfiguredRule::Enums(rule.parse(
+                    source,
+                    "enum",
+                    tokens::enums_condition,
+                    enums::ConditionToken::Should,
+                    tokens::enums_assertion,
+                )?),
+            ));
+        }
+
+        for rule in &raw.modules {
+            rules.push((
+                rule.start(),
+                ConfiguredRule::Modules(rule.parse(
+                    source,
+                    "module",
+                    tokens::modules_condition,
+                    modules::ConditionToken::Should,
+                    tokens::modules_assertion,
+                )?),
+            ));
+        }
+
+        if let Some(first) = raw.layer_rules.first() {
+            rules.push((
+                first.span().start,
+                ConfiguredRule::Layers(parse_layers(source, raw.layers, &raw.layer_rules)?),
+            ));
+        }
+
+        // Rules of different kinds are evaluated in the order they are declared in
+        rules.sort_by_key(|(start, _)| *start);
+        let rules = rules.into_iter().map(|(_, rule)| rule).collect();
+
+        let mut filters = raw
+            .exclude_cfg
+            .into_iter()
+            .fold(ExludeModules::default(), ExludeModules::exclude_cfg);
+        if let Some(options) = raw.active_cfg {
+            filters = filters.active_cfg(options.into_iter().fold(CfgSet::new(), CfgSet::option));
+        }
+
+        Ok(ArchConfig {
+            filters,
+            targets: raw.targets,
+            rules,
+        })
+    }
+}
+
+impl FromStr for ArchConfig {
+    type Err = ConfigError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        Self::parse(&Source {
+            name: "archunit.toml",
+            content,
+        })
+    }
+}
+
+impl<C: Condition + Clone, A: Assertion> TokenRule<C, A> {
+    fn evaluate<S: Subject>(
+        &self,
+        tree: &'static ModuleTree,
+        filters: ExludeModules<'static>,
+    ) -> AssertionResult
+    where
+        PredicateConjunctionBuilder<C, A, S>: CheckRule<C, A, S, ArchRule<C, A, S>>,
+        ArchRule<C, A, S>: Assertable<C, A, S>,
+    {
+        let mut rule = PredicateConjunctionBuilder::from_tokens(
+            tree,
+            filters,
+            self.conditions.clone(),
+            self.assertions.clone(),
+        );
+        if let Some(rationale) = &self.because {
+            rule = rule.because(rationale);
+        }
+
+        rule.evaluate()
+    }
+}
+
+type ParseToken<T> = fn(&str, Option<&str>) -> Option<T>;
+
+impl RawRule {
+    /// Offset of the rule in the configuration file.
+    fn start(&self) -> usize {
+        self.that.as_ref().map_or(self.should.span().start, |that| {
+            that.span().start.min(self.should.span().start)
+        })
+    }
+
+    fn parse<C, A>(
+        &self,
+        source: &Source,
+        subject: &'static str,
+        parse_condition: ParseToken<C>,
+        should: C,
+        parse_assertion: ParseToken<A>,
+    ) -> Result<TokenRule<C, A>, ConfigError> {
+        let mut conditions = match &self.that {
+            Some(that) => parse_tokens(source, subject, "condition", that, parse_condition)?,
+            None => vec![],
+        };
+
+        if !conditions.is_empty() {
+            conditions.push(should);
+        }
+
+        let assertions = parse_tokens(source, subject, "assertion", &self.should, parse_assertion)?;
+
+        Ok(TokenRule {
+            conditions,
+            assertions,
+            because: self.because.clone(),
+        })
+    }
+}
+
+fn parse_tokens<T>(
+    source: &Source,
+    subject: &'static str,
+    kind: &'static str,
+    tokens: &Spanned<Vec<Spanned<String>>>,
+    parse: ParseToken<T>,
+) -> Result<Vec<T>, ConfigError> {
+    // Tokens must alternate between items and conjunctions, prefixes such as
+    // `only_have_dependency_module` and `that` introduce the next item
+    let mut expect_item = true;
+    let mut parsed = Vec::with_capacity(tokens.get_ref().len());
+
+    for token in tokens.get_ref() {
+        let (name, arg) = tokens::parse_call(token.get_ref());
+        let unexpected = match token_kind(name) {
+            TokenKind::Item => !std::mem::replace(&mut expect_item, false),
+            TokenKind::Conjunction => std::mem::replace(&mut expect_item, true),
+            TokenKind::Prefix => !expect_item,
+        };
+
+        if unexpected {
+            return Err(ConfigError::UnexpectedToken {
+                kind,
+                token: token.get_ref().clone(),
+                src: source.named(),
+                span: token.span().into(),
+            });
+        }
+
+        let parsed_token = parse(name, arg).ok_or_else(|| ConfigError::UnknownToken {
+            subject,
+            kind,
+            token: token.get_ref().clone(),
+            src: source.named(),
+            span: token.span().into(),
+        })?;
+
+        parsed.push(parsed_token);
+    }
+
+    // An empty `that` list is rejected as well, omit it to match every item
+    if expect_item {
+        return Err(ConfigError::MissingToken {
+            kind,
+            src: source.named(),
+            span: tokens.span().into(),
+        });
+    }
+
+    Ok(parsed)
+}
+
+fn parse_layers(
+    source: &Source,
+    layer_definitions: BTreeMap<String, String>,
+    layer_rules: &[Spanned<RawLayerRule>],
+) -> Result<LayeredArchitecture, ConfigError> {
+    let check_defined = |layer: &Spanned<String>| {
+        if layer_definitions.contains_key(layer.get_ref()) {
+            Ok(layer.get_ref().clone())
+        } else {
+            Err(ConfigError::UndefinedLayer {
+                layer: layer.get_ref().clone(),
+                src: source.named(),
+                span: layer.span().into(),
+            })
+        }
+    };
+
+    let mut layer_assertions = BTreeMap::new();
+    for rule in layer_rules {
+        let layer = check_defined(&rule.get_ref().where_layer)?;
+        if layer_assertions.contains_key(&layer) {
+            return Err(ConfigError::DuplicateLayerRule {
+                layer,
+                src: source.named(),
+                span: rule.span().into(),
+            });
+        }
+
+        let assertion = match rule.get_ref() {
+            RawLayerRule {
+                may_only_be_accessed_by_layers: Some(layers),
+                may_not_be_accessed_by_any_layer: false,
+                ..
+            } => LayerAssertion::MayOnlyBeAccessedByLayers(
+                layers.iter().map(check_defined).collect::<Result<_, _>>()?,
+            ),
+            RawLayerRule {
+                may_only_be_accessed_by_layers: None,
+                may_not_be_accessed_by_any_layer: true,
+                ..
+            } => LayerAssertion::MayNotBeAccessedByAnyLayer,
+            _ => {
+                return Err(ConfigError::InvalidLayerRule {
+                    src: source.named(),
+                    span: rule.span().into(),
+                })
+            }
+        };
+
+        layer_assertions.insert(layer, assertion);
+    }
+
+    Ok(LayeredArchitecture {
+        layer_definitions,
+        layer_assertions,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::targets::TargetKind;
+    use crate::config::{check_config, ArchConfig, ConfigError};
+    use miette::SourceSpan;
+    use speculoos::prelude::*;
+    use std::{env, fs};
+
+    #[test]
+    fn should_check_own_config() {
+        check_config("archunit.toml");
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown assertion 'be_awesome'")]
+    fn should_panic_on_invalid_config() {
+        let path = env::temp_dir().join("archunit_rs_invalid_config.toml");
+        fs::write(&path, "[[structs]]\nshould = [\"be_awesome\"]\n").expect("temp file");
+        check_config(path);
+    }
+
+    #[test]
+    fn should_evaluate_configured_rules() {
+        let config: ArchConfig = r#"
+            exclude_cfg = ["test"]
+
+            [[structs]]
+            that = ["have_simple_name(AssertionResult)"]
+            should = ["be_public", "and_should", "derive(Debug)"]
+
+            [[enums]]
+            that = ["have_simple_name(ModuleRuleViolation)"]
+            should = ["derive(Deserialize)"]
+            because = "violations are sent over the wire"
+
+            [[modules]]
+            that = ["reside_in_a_module(archunit_rs::report::*)"]
+            should = ["be_public"]
+        "#
+        .parse()
+        .expect("valid config");
+
+        let results = config.evaluate();
+
+        assert_that!(results).has_length(3);
+        assert_that!(results[0].is_success()).is_true();
+        assert_that!(results[1].is_success()).is_false();
+        assert_that!(results[1].rationale)
+            .is_equal_to(Some("violations are sent over the wire".to_owned()));
+        assert_that!(results[2].is_success()).is_true();
+    }
+
+    #[test]
+    fn should_evaluate_rules_in_declaration_order() {
+        let config: ArchConfig = r#"
+            [[modules]]
+            that = ["reside_in_a_module(archunit_rs::report::*)"]
+            should = ["be_public"]
+
+            [[structs]]
+            that = ["have_simple_name(AssertionResult)"]
+            should = ["implement_or_derive(Debug)"]
+
+            [[modules]]
+            that = ["have_simple_name(report)"]
+            should = ["have_simple_name(reports)"]
+        "#
+        .parse()
+        .expect("valid config");
+
+        let results = config.evaluate();
+
+        assert_that!(results).has_length(3);
+        assert_that!(results[0].expected.as_str()).starts_with("Modules that");
+        assert_that!(results[1].expected.as_str()).starts_with("Structs that");
+        assert_that!(results[2].expected.as_str()).starts_with("Modules that have simple name");
+        assert_that!(results[2].is_success()).is_false();
+    }
+
+    #[test]
+    fn should_evaluate_layered_architecture() {
+        let config: ArchConfig = r#"
+            [layers]
+            report = "archunit_rs::report"
+            config = "archunit_rs::config"
+
+            [[layer_rules]]
+            where_layer = "config"
+            may_not_be_accessed_by_any_layer = true
+
+            [[layer_rules]]
+            where_layer = "report"
+            may_only_be_accessed_by_layers = ["config"]
+        "#
+        .parse()
+        .expect("valid config");
+
+        let results = config.evaluate();
+
+        assert_that!(results).has_length(1);
+        assert_that!(results[0].is_success()).is_false();
+        let modules: Vec<&str> = results[0]
+            .actual
+            .iter()
+            .map(|violation| violation.module.as_str())
+            .collect();
+        assert_that!(modules).contains("archunit_rs::assertion_result");
+        assert_that!(results[0]
+            .actual
+            .iter()
+            .any(|violation| violation.module.as_str().starts_with("archunit_rs::config")))
+        .is_false();
+    }
+
+    #[test]
+    fn should_point_errors_at_the_offending_token() {
+        let content = r#"
+[[structs]]
+should = ["be_public", "and_should", "be_awesome"]
+"#;
+        let error = content.parse::<ArchConfig>();
+        let token_start = content.find("\"be_awesome\"").expect("token");
+
+        assert!(matches!(
+            error,
+            Err(ConfigError::UnknownToken { token, span, .. })
+                if token == "be_awesome" && span == SourceSpan::from((token_start, 12))
+        ));
+    }
+
+    #[test]
+    fn should_reject_misplaced_tokens_and_undefined_layers() {
+        let misplaced = r#"
+            [[modules]]
+            that = ["and", "are_declared_public"]
+            should = ["be_public"]
+        "#
+        .parse::<ArchConfig>();
+
+        let dangling = r#"
+            [[enums]]
+            should = ["be_public", "or_should"]
+        "#
+        .parse::<ArchConfig>();
+
+        let undefined_layer = r#"
+            [layers]
+            domain = "my_crate::domain"
+
+            [[layer_rules]]
+            where_layer = "domain"
+            may_only_be_accessed_by_layers = ["infra"]
+        "#
+        .parse::<ArchConfig>();
+
+        assert!(matches!(
+            misplaced,
+            Err(ConfigError::UnexpectedToken { token, .. }) if token == "and"
+        ));
+        assert!(matches!(
+            dangling,
+            Err(ConfigError::MissingToken {
+                kind: "assertion",
+                ..
+            })
+        ));
+        assert!(matches!(
+            undefined_layer,
+            Err(ConfigError::UndefinedLayer { layer, .. }) if layer == "infra"
+        ));
+    }
+
+    #[test]
+    fn should_reject_duplicate_layer_rules() {
+        let config = r#"
+            [layers]
+            domain = "my_crate::domain"
+            app = "my_crate::app"
+
+            [[layer_rules]]
+            where_layer = "domain"
+            may_only_be_accessed_by_layers = ["app"]
+
+            [[layer_rules]]
+            where_layer = "domain"
+            may_not_be_accessed_by_any_layer = true
+        "#;
+        let second_rule = config.rfind("[[layer_rules]]").expect("second rule");
+
+        assert!(matches!(
+            config.parse::<ArchConfig>(),
+            Err(ConfigError::DuplicateLayerRule { layer, span, .. })
+                if layer == "domain" && span.offset() == second_rule
+        ));
+    }
+
+    #[test]
+    fn should_evaluate_rules_against_selected_targets() {
+        let config: ArchConfig = r#"
+            targets = ["lib", "bin"]
+
+            [[structs]]
+            that = ["have_simple_name(Args)"]
+            should = ["be_private"]
+        "#
+        .parse()
+        .expect("valid config");
+
+        assert_that!(config.targets())
+            .is_equal_to(Some([TargetKind::Lib, TargetKind::Bin].as_slice()));
+        // `Args` is only declared by the `cargo-archunit` binary
+        let results = config.evaluate();
+        assert_that!(results).has_length(2);
+        assert_that!(results.iter().all(|result| result.is_success())).is_true();
+
+        let unknown = "targets = [\"benches\"]".parse::<ArchConfig>();
+        assert!(matches!(unknown, Err(ConfigError::Toml { .. })));
+    }
+
+    #[test]
+    fn should_filter_items_on_active_cfg() {
+        let rule = r#"
+            [[structs]]
+            that = ["have_simple_name(RuleViolation)"]
+            should = ["be_public"]
+        "#;
+        let evaluate = |active_cfg: &str| {
+            format!("active_cfg = {active_cfg}\n{rule}")
+                .parse::<ArchConfig>()
+                .expect("valid config")
+                .evaluate()
+        };
+
+        // `RuleViolation` is a private struct of a `#[cfg(test)]` module
+        assert_that!(evaluate(r#"["unix", 'feature = "serde"']"#)[0].is_success()).is_true();
+        assert_that!(evaluate(r#"["test"]"#)[0].is_success()).is_false();
+
+        let invalid = r#"active_cfg = ["feature = serde"]"#.parse::<ArchConfig>();
+        assert!(matches!(invalid, Err(ConfigError::Toml { .. })));
+    }
+}
@@ -0,0 +1,322 @@
+use crate::ast::structs::StructKind;
+use crate::rule::naming::NameConvention;
+use crate::rule::{enums, modules, structs};
+
+#[derive(Debug)]
+pub(super) enum TokenKind {
+    Item,
+    Conjunction,
+    Prefix,
+}
+
+pub(super) fn token_kind(name: &str) -> TokenKind {
+    match name {
+        "and" | "or" | "and_should" | "or_should" => TokenKind::Conjunction,
+        "only_have_dependency_module" | "that" => TokenKind::Prefix,
+        _ => TokenKind::Item,
+    }
+}
+
+/// Split `name(argument)` into its name and optional argument, quotes around the
+/// argument are ignored.
+pub(super) fn parse_call(token: &str) -> (&str, Option<&str>) {
+    let token = token.trim();
+    match token.split_once('(') {
+        Some((name, arg)) if arg.ends_with(')') => {
+            let arg = arg[..arg.len() - 1]
+                .trim()
+                .trim_matches(|c| c == '"' || c == '\'');
+            (name.trim(), Some(arg))
+        }
+        _ => (token, None),
+    }
+}
+
+pub(super) fn structs_condition(name: &str, arg: Option<&str>) -> Option<structs::ConditionToken> {
+    use structs::ConditionToken;
+    let token = match (name, arg) {
+        ("are_declared_public", None) => ConditionToken::AreDeclaredPublic,
+        ("are_declared_private", None) => ConditionToken::AreDeclaredPrivate,
+        ("reside_in_a_module", Some(module)) => ConditionToken::ResidesInAModule(module.to_owned()),
+        ("have_simple_name", Some(name)) => ConditionToken::HaveSimpleName(name.to_owned()),
+        ("have_name_matching", Some(pattern)) => {
+            ConditionToken::HaveNameMatching(pattern.to_owned())
+        }
+        ("derives", Some(trait_name)) => ConditionToken::Derives(trait_name.to_owned()),
+        ("implement", Some(trait_name)) => ConditionToken::Implement(trait_name.to_owned()),
+        ("are_gated_by_feature", Some(feature)) => {
+            ConditionToken::AreGatedByFeature(feature.to_owned())
+        }
+        ("have_field_of_type", Some(pattern)) => {
+            ConditionToken::HaveFieldOfType(pattern.to_owned())
+        }
+        ("are_unit_structs", None) => ConditionToken::HaveKind(StructKind::Unit),
+        ("are_tuple_structs", None) => ConditionToken::HaveKind(StructKind::Tuple),
+        ("have_named_fields", None) => ConditionToken::HaveKind(StructKind::Named),
+        ("are_generic", None) => ConditionToken::AreGeneric,
+        ("and", None) => ConditionToken::And,
+        ("or", None) => ConditionToken::Or,
+        _ => return None,
+    };
+
+    Some(token)
+}
+
+pub(super) fn structs_assertion(name: &str, arg: Option<&str>) -> Option<structs::AssertionToken> {
+    use structs::{AssertionConjunction, AssertionToken, SimpleAssertions};
+    let assertion = match (name, arg) {
+        ("be_public", None) => SimpleAssertions::BePublic,
+        ("be_private", None) => SimpleAssertions::BePrivate,
+        ("have_simple_name", Some(name)) => SimpleAssertions::HaveSimpleName(name.to_owned()),
+        ("implement", Some(trait_name)) => SimpleAssertions::Implement(trait_name.to_owned()),
+        ("derive", Some(trait_name)) => SimpleAssertions::Derive(trait_name.to_owned()),
+        ("implement_or_derive", Some(trait_name)) => {
+            SimpleAssertions::ImplementOrDerive(trait_name.to_owned())
+        }
+        ("only_have_private_fields", None) => SimpleAssertions::OnlyHavePrivateFields,
+        ("only_have_public_fields", None) => SimpleAssertions::OnlyHavePublicFields,
+        ("have_at_most_fields", Some(max)) => SimpleAssertions::HaveAtMostFields(max.parse().ok()?),
+        ("have_field_of_type", Some(pattern)) => {
+            SimpleAssertions::HaveFieldOfType(pattern.to_owned())
+        }
+        ("be_unit_struct", None) => SimpleAssertions::HaveKind(StructKind::Unit),
+        ("be_tuple_struct", None) => SimpleAssertions::HaveKind(StructKind::Tuple),
+        ("have_named_fields", None) => SimpleAssertions::HaveKind(StructKind::Named),
+        ("have_exactly_fields", Some(count)) => {
+            SimpleAssertions::HaveExactlyFields(count.parse().ok()?)
+        }
+        ("be_generic", None) => SimpleAssertions::BeGeneric,
+        ("not_be_generic", None) => SimpleAssertions::NotBeGeneric,
+        ("not_have_lifetimes", None) => SimpleAssertions::NotHaveLifetimes,
+        ("not_have_where_clause", None) => SimpleAssertions::NotHaveWhereClause,
+        ("have_name_matching_regex", Some(pattern)) => {
+            SimpleAssertions::HaveName(NameConvention::try_regex(pattern).ok()?)
+        }
+        ("have_name_starting_with", Some(prefix)) => {
+            SimpleAssertions::HaveName(NameConvention::StartingWith(prefix.to_owned()))
+        }
+        ("have_name_ending_with", Some(suffix)) => {
+            SimpleAssertions::HaveName(NameConvention::EndingWith(suffix.to_owned()))
+        }
+        ("have_upper_camel_case_name", None) => {
+            SimpleAssertions::HaveName(NameConvention::UpperCamelCase)
+        }
+        ("be_crate_visible", None) => SimpleAssertions::BeCrateVisible,
+        ("have_visibility_at_most", Some(max)) => {
+            SimpleAssertions::HaveVisibilityAtMost(max.parse().ok()?)
+        }
+        ("not_be_more_visible_than_parent_module", None) => {
+            SimpleAssertions::NotBeMoreVisibleThanParentModule
+        }
+        ("not_have_fields_of_type_matching", Some(pattern)) => {
+            SimpleAssertions::NotHaveFieldsOfTypeMatching(pattern.to_owned())
+        }
+        // Modules are separated by commas
+        ("only_have_fields_of_types_in_modules", Some(modules)) => {
+            SimpleAssertions::OnlyHaveFieldsOfTypesInModules(
+                modules
+                    .split(',')
+                    .map(|module| module.trim().to_owned())
+                    .collect(),
+            )
+        }
+        ("and_should", None) => {
+            return Some(AssertionToken::Conjunction(AssertionConjunction::AndShould))
+        }
+        ("or_should", None) => {
+            return Some(AssertionToken::Conjunction(AssertionConjunction::OrShould))
+        }
+        _ => return None,
+    };
+
+    Some(AssertionToken::SimpleAssertion(assertion))
+}
+
+pub(super) fn enums_condition(name: &str, arg: Option<&str>) -> Option<enums::ConditionToken> {
+    use enums::ConditionToken;
+    let token = match (name, arg) {
+        ("are_declared_public", None) => ConditionToken::AreDeclaredPublic,
+        ("are_declared_private", None) => ConditionToken::AreDeclaredPrivate,
+        ("reside_in_a_module", Some(module)) => ConditionToken::ResidesInAModule(module.to_owned()),
+        ("have_simple_name", Some(name)) => ConditionToken::HaveSimpleName(name.to_owned()),
+        ("derives", Some(trait_name)) => ConditionToken::Derives(trait_name.to_owned()),
+        ("implement", Some(trait_name)) => ConditionToken::Implement(trait_name.to_owned()),
+        ("are_gated_by_feature", Some(feature)) => {
+            ConditionToken::AreGatedByFeature(feature.to_owned())
+        }
+        ("are_fieldless", None) => ConditionToken::AreFieldless,
+        ("and", None) => ConditionToken::And,
+        ("or", None) => ConditionToken::Or,
+        _ => return None,
+    };
+
+    Some(token)
+}
+
+pub(super) fn enums_assertion(name: &str, arg: Option<&str>) -> Option<enums::AssertionToken> {
+    use enums::{AssertionConjunction, AssertionToken, SimpleAssertions};
+    let assertion = match (name, arg) {
+        ("be_public", None) => SimpleAssertions::BePublic,
+        ("be_private", None) => SimpleAssertions::BePrivate,
+        ("have_simple_name", Some(name)) => SimpleAssertions::HaveSimpleName(name.to_owned()),
+        ("implement", Some(trait_name)) => SimpleAssertions::Implement(trait_name.to_owned()),
+        ("derive", Some(trait_name)) => SimpleAssertions::Derive(trait_name.to_owned()),
+        ("implement_or_derive", Some(trait_name)) => {
+            SimpleAssertions::ImplementOrDerive(trait_name.to_owned())
+        }
+        ("have_at_most_variants", Some(max)) => {
+            SimpleAssertions::HaveAtMostVariants(max.parse().ok()?)
+        }
+        ("have_variants_matching", Some(pattern)) => {
+            SimpleAssertions::HaveVariantsMatching(pattern.to_owned())
+        }
+        ("be_fieldless", None) => SimpleAssertions::BeFieldless,
+        ("have_name_matching_regex", Some(pattern)) => {
+            SimpleAssertions::HaveName(NameConvention::try_regex(pattern).ok()?)
+        }
+        ("have_name_starting_with", Some(prefix)) => {
+            SimpleAssertions::HaveName(NameConvention::StartingWith(prefix.to_owned()))
+        }
+        ("have_name_ending_with", Some(suffix)) => {
+            SimpleAssertions::HaveName(NameConvention::EndingWith(suffix.to_owned()))
+        }
+        ("have_upper_camel_case_name", None) => {
+            SimpleAssertions::HaveName(NameConvention::UpperCamelCase)
+        }
+        ("be_crate_visible", None) => SimpleAssertions::BeCrateVisible,
+        ("have_visibility_at_most", Some(max)) => {
+            SimpleAssertions::HaveVisibilityAtMost(max.parse().ok()?)
+        }
+        ("not_be_more_visible_than_parent_module", None) => {
+            SimpleAssertions::NotBeMoreVisibleThanParentModule
+        }
+        ("have_no_variant_carrying_type", Some(pattern)) => {
+            SimpleAssertions::HaveNoVariantCarryingType(pattern.to_owned())
+        }
+        ("and_should", None) => {
+            return Some(AssertionToken::Conjunction(AssertionConjunction::AndShould))
+        }
+        ("or_should", None) => {
+            return Some(AssertionToken::Conjunction(AssertionConjunction::OrShould))
+        }
+        _ => return None,
+    };
+
+    Some(AssertionToken::SimpleAssertion(assertion))
+}
+
+pub(super) fn modules_condition(name: &str, arg: Option<&str>) -> Option<modules::ConditionToken> {
+    use modules::ConditionToken;
+    let token = match (name, arg) {
+        ("are_declared_public", None) => ConditionToken::AreDeclaredPublic,
+        ("are_declared_private", None) => ConditionToken::AreDeclaredPrivate,
+        ("reside_in_a_module", Some(module)) => ConditionToken::ResidesInAModule(module.to_owned()),
+        ("does_not_reside_in_a_module", Some(module)) => {
+            ConditionToken::NotResidesInAModule(module.to_owned())
+        }
+        ("have_simple_name", Some(name)) => ConditionToken::HaveSimpleName(name.to_owned()),
+        ("are_gated_by_feature", Some(feature)) => {
+            ConditionToken::AreGatedByFeature(feature.to_owned())
+        }
+        ("and", None) => ConditionToken::And,
+        ("or", None) => ConditionToken::Or,
+        _ => return None,
+    };
+
+    Some(token)
+}
+
+pub(super) fn modules_assertion(name: &str, arg: Option<&str>) -> Option<modules::AssertionToken> {
+    use modules::{
+        AssertionConjunction, AssertionToken, DependencyAssertion, DependencyAssertionConjunction,
+        SimpleAssertions,
+    };
+    let assertion = match (name, arg) {
+        ("be_public", None) => SimpleAssertions::BePublic,
+        ("be_private", None) => SimpleAssertions::BePrivate,
+        ("have_simple_name", Some(name)) => SimpleAssertions::HaveSimpleName(name.to_owned()),
+        ("have_snake_case_name", None) => SimpleAssertions::HaveName(NameConvention::SnakeCase),
+        ("be_crate_visible", None) => SimpleAssertions::BeCrateVisible,
+        ("have_visibility_at_most", Some(max)) => {
+            SimpleAssertions::HaveVisibilityAtMost(max.parse().ok()?)
+        }
+        ("not_be_more_visible_than_parent_module", None) => {
+            SimpleAssertions::NotBeMoreVisibleThanParentModule
+        }
+        ("does_not_have_simple_name", Some(name)) => {
+            SimpleAssertions::NotHaveSimpleName(name.to_owned())
+        }
+        ("be_feature_gated", None) => SimpleAssertions::BeFeatureGated,
+        ("be_gated_by_feature", Some(feature)) => {
+            SimpleAssertions::BeGatedByFeature(feature.to_owned())
+        }
+        ("only_use_declared_features", None) => SimpleAssertions::OnlyUseDeclaredFeatures,
+        ("have_at_most_items", Some(max)) => SimpleAssertions::HaveAtMostItems(max.parse().ok()?),
+        ("have_at_most_lines", Some(max)) => SimpleAssertions::HaveAtMostLines(max.parse().ok()?),
+        ("only_have_functions_with_at_most_parameters", Some(max)) => {
+            SimpleAssertions::OnlyHaveFunctionsWithAtMostParameters(max.parse().ok()?)
+        }
+        ("only_have_functions_with_at_most_lines", Some(max)) => {
+            SimpleAssertions::OnlyHaveFunctionsWithAtMostLines(max.parse().ok()?)
+        }
+        ("have_instability_below", Some(threshold)) => {
+            SimpleAssertions::HaveInstabilityBelow(threshold.parse().ok()?)
+        }
+        ("have_abstractness_above", Some(threshold)) => {
+            SimpleAssertions::HaveAbstractnessAbove(threshold.parse().ok()?)
+        }
+        ("have_distance_from_main_sequence_below", Some(threshold)) => {
+            SimpleAssertions::HaveDistanceFromMainSequenceBelow(threshold.parse().ok()?)
+        }
+        ("and_should", None) => {
+            return Some(AssertionToken::Conjunction(AssertionConjunction::AndShould))
+        }
+        ("or_should", None) => {
+            return Some(AssertionToken::Conjunction(AssertionConjunction::OrShould))
+        }
+        ("only_have_dependency_module", None) => {
+            return Some(AssertionToken::DependencyAssertionConjunction(
+                DependencyAssertionConjunction::OnlyHaveDependencyModule,
+            ))
+        }
+        ("that", None) => {
+            return Some(AssertionToken::DependencyAssertion(
+                DependencyAssertion::That,
+            ))
+        }
+        _ => return None,
+    };
+
+    Some(AssertionToken::SimpleAssertion(assertion))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::Visibility;
+    use crate::config::tokens::{parse_call, structs_assertion};
+    use crate::rule::structs::{AssertionToken, SimpleAssertions};
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_parse_token_arguments() {
+        assert_that!(parse_call("be_public")).is_equal_to(("be_public", None));
+        assert_that!(parse_call(" derive( \"Debug\" ) ")).is_equal_to(("derive", Some("Debug")));
+    }
+
+    #[test]
+    fn should_reject_invalid_name_regex() {
+        assert_that!(structs_assertion("have_name_matching_regex", Some("^Sql"))).is_some();
+        assert_that!(structs_assertion("have_name_matching_regex", Some("(Sql"))).is_none();
+    }
+
+    #[test]
+    fn should_parse_visibility_arguments() {
+        let (name, arg) = parse_call("have_visibility_at_most(pub(crate))");
+
+        assert_that!(structs_assertion(name, arg)).is_equal_to(Some(
+            AssertionToken::SimpleAssertion(SimpleAssertions::HaveVisibilityAtMost(
+                Visibility::Crate,
+            )),
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::assertion_result::AssertionResult;
+use crate::crate_rule::report::{ForbiddenCrateDependency, ForbiddenCrateUsage};
+use crate::crate_rule::{CrateDependency, CrateRule, DependencyKind};
+use crate::Workspace;
+use wildmatch::WildMatch;
+
+impl CrateRule {
+    pub(crate) fn evaluate_workspace(&self, workspace: &'static Workspace) -> AssertionResult {
+        let mut result = AssertionResult::new();
+        result.push_expected(format!(
+            "Crates matching '{}' should not depend on crates matching '{}'",
+            self.crates, self.forbidden_crates
+        ));
+        result.rationale = self.rationale.clone();
+
+        let crates = WildMatch::new(&self.crates);
+        let forbidden_crates = WildMatch::new(&self.forbidden_crates);
+
+        for krate in workspace
+            .crates
+            .iter()
+            .filter(|krate| crates.matches(&krate.name))
+        {
+            let forbidden: Vec<&CrateDependency> = krate
+                .dependencies
+                .iter()
+                .filter(|dependency| dependency.kind != DependencyKind::Dev)
+                .filter(|dependency| forbidden_crates.matches(&dependency.name))
+                .collect();
+
+            for dependency in &forbidden {
+                let error = ForbiddenCrateDependency::from_span_and_location(
+                    dependency.span,
+                    &krate.manifest_path,
+                    krate.name.clone(),
+                    &dependency.alias,
+                    dependency.name.clone(),
+                );
+
+                result.push_actual(
+                    &krate.tree.path,
+                    &krate.manifest_path,
+                    dependency.span,
+                    error,
+                );
+            }
+
+            for module in krate.tree.flatten(&self.filters).0.values() {
+                for usage in &module.dependencies {
+                    let Some(dependency) = forbidden
+                        .iter()
+                        .find(|dependency| uses_crate(&usage.parts, &dependency.ident()))
+                    else {
+                        continue;
+                    };
+
+                    let error = ForbiddenCrateUsage::from_span_and_location(
+                        usage.span,
+                        &module.real_path,
+                        &dependency.ident(),
+                        dependency.name.clone(),
+                        module.path.to_string(),
+                    );
+
+                    result.push_actual(&module.path, &module.real_path, usage.span, error);
+                }
+            }
+        }
+
+        result.success = result.actual.is_empty();
+        result.sort_violations();
+        result
+    }
+}
+
+fn uses_crate(path: &str, ident: &str) -> bool {
+    path == ident
+        || path
+            .strip_prefix(ident)
+            .is_some_and(|rest| rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crate_rule::{Crates, Workspace};
+    use crate::ExludeModules;
+    use once_cell::sync::Lazy;
+    use speculoos::prelude::*;
+
+    static SHOP: Lazy<Workspace> = Lazy::new(|| {
+        Workspace::load(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/workspace"
+        ))
+        .expect("fixture workspace")
+    });
+
+    #[test]
+    fn should_report_manifest_and_use_violations() {
+        let result = Crates::that(ExludeModules::cfg_test())
+            .have_name_matching("*-domain")
+            .should()
+            .not_depend_on_crates_matching("*-infra")
+            .evaluate_in(&SHOP);
+
+        assert_that!(result.is_success()).is_false();
+        let locations: Vec<String> = result
+            .actual
+            .iter()
+            .map(|violation| violation.relative_location())
+            .collect();
+        assert_that!(locations).is_equal_to(vec![
+            "tests/fixtures/workspace/crates/shop-domain/Cargo.toml".to_owned(),
+            "tests/fixtures/workspace/crates/shop-domain/src/lib.rs".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn should_match_renamed_dependencies() {
+        let result = Crates::that(ExludeModules::cfg_test())
+            .have_name_matching("shop-app")
+            .should()
+            .not_depend_on_crates_matching("shop-infra")
+            .evaluate_in(&SHOP);
+
+        // The manifest entry, the bare `use store;` and `use store::Db;`
+        assert_that!(result.actual).has_length(3);
+        assert_that!(result.actual[1].message())
+            .is_equal_to("Forbidden usage of crate 'shop-infra' in shop_app".to_owned());
+        assert_that!(result.actual[1].span.start().line).is_equal_to(2);
+        assert_that!(result.actual[2].span.start().line).is_equal_to(3);
+    }
+
+    #[test]
+    fn should_pass_when_no_crate_depends_on_forbidden_crates() {
+        let result = Crates::that(ExludeModules::cfg_test())
+            .have_name_matching("*-infra")
+            .should()
+            .not_depend_on_crates_matching("*-domain")
+            .evaluate_in(&SHOP);
+
+        assert_that!(result.is_success()).is_true();
+    }
+}
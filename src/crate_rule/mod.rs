@@ -0,0 +1,120 @@
+//! Dependency rules between the crates of a cargo workspace.
+//!
+//! Dependencies are checked both in the member manifests and in the `use` declarations of
+//! their sources, dev-dependencies are not considered.
+//!
+//! **Example:**
+//! ```rust
+//! use archunit_rs::crate_rule::Crates;
+//! use archunit_rs::ExludeModules;
+//!
+//! Crates::that(ExludeModules::cfg_test())
+//!     .have_name_matching("*-domain")
+//!     .should()
+//!     .not_depend_on_crates_matching("*-infra")
+//!     .check();
+//! ```
+use crate::assertion_result::AssertionResult;
+use crate::ast::workspace::workspace;
+use crate::report::text::ReportConfig;
+use crate::{ExludeModules, Workspace};
+
+pub use crate::ast::workspace::{CrateDependency, DependencyKind, WorkspaceCrate};
+
+pub mod check;
+pub mod report;
+
+/// Entry point for crate rules, crates are matched by package name.
+#[derive(Debug)]
+pub struct Crates;
+
+#[derive(Debug)]
+pub struct CrateConditionBuilder {
+    filters: ExludeModules<'static>,
+}
+
+#[derive(Debug)]
+pub struct CrateConditionConjunctionBuilder {
+    filters: ExludeModules<'static>,
+    crates: String,
+}
+
+#[derive(Debug)]
+pub struct CratePredicateBuilder {
+    filters: ExludeModules<'static>,
+    crates: String,
+}
+
+#[derive(Debug)]
+pub struct CrateRule {
+    pub(crate) filters: ExludeModules<'static>,
+    pub(crate) crates: String,
+    pub(crate) forbidden_crates: String,
+    pub(crate) rationale: Option<String>,
+}
+
+impl Crates {
+    /// Builder function for crate rules, `filters` apply to the modules whose `use`
+    /// declarations are checked.
+    pub fn that(filters: ExludeModules<'static>) -> CrateConditionBuilder {
+        CrateConditionBuilder { filters }
+    }
+}
+
+impl CrateConditionBuilder {
+    /// Match crates whose package name matches the given wildcard pattern, e.g. `*-domain`.
+    pub fn have_name_matching(self, pattern: &str) -> CrateConditionConjunctionBuilder {
+        CrateConditionConjunctionBuilder {
+            filters: self.filters,
+            crates: pattern.to_owned(),
+        }
+    }
+}
+
+impl CrateConditionConjunctionBuilder {
+    pub fn should(self) -> CratePredicateBuilder {
+        CratePredicateBuilder {
+            filters: self.filters,
+            crates: self.crates,
+        }
+    }
+}
+
+impl CratePredicateBuilder {
+    /// Forbid dependencies on crates whose package name matches the given wildcard pattern.
+    pub fn not_depend_on_crates_matching(self, pattern: &str) -> CrateRule {
+        CrateRule {
+            filters: self.filters,
+            crates: self.crates,
+            forbidden_crates: pattern.to_owned(),
+            rationale: None,
+        }
+    }
+}
+
+impl CrateRule {
+    /// Document why this rule exists, the rationale is displayed along with rule violations.
+    pub fn because(mut self, rationale: &str) -> Self {
+        self.rationale = Some(rationale.to_owned());
+        self
+    }
+
+    /// Evaluate the rule against the workspace of the crate under test.
+    pub fn evaluate(&self) -> AssertionResult {
+        self.evaluate_in(workspace())
+    }
+
+    /// Evaluate the rule and panic on violations, the report is configured via
+    /// [`ReportConfig::from_env`].
+    pub fn check(&self) {
+        let result = self.evaluate();
+        if !result.is_success() {
+            panic!("{}", result.render(&ReportConfig::from_env()))
+        }
+    }
+
+    /// Same as [`CrateRule::evaluate`], against the given workspace, see [`Workspace::load`].
+    pub fn evaluate_in(&self, workspace: &'static Workspace) -> AssertionResult {
+        self.evaluate_workspace(workspace)
+    }
+}
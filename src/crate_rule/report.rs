@@ -0,0 +1,81 @@
+use crate::assertion_result::{get_code_sample_region, get_relative_location};
+use crate::ast::CodeSpan;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("Crate '{krate}' depends on forbidden crate '{dependency}'")]
+#[diagnostic(help("Try removing '{dependency}' from the dependencies of '{krate}'"))]
+pub struct ForbiddenCrateDependency {
+    krate: String,
+    dependency: String,
+    #[source_code]
+    src: NamedSource,
+    #[label("Forbidden dependency")]
+    span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("Forbidden usage of crate '{dependency}' in {accessed_in}")]
+#[diagnostic(help("Try refactoring your code to remove usage of '{dependency}'"))]
+pub struct ForbiddenCrateUsage {
+    dependency: String,
+    accessed_in: String,
+    #[source_code]
+    src: NamedSource,
+    #[label("Forbidden usage")]
+    span: SourceSpan,
+}
+
+impl ForbiddenCrateDependency {
+    pub fn from_span_and_location(
+        span: CodeSpan,
+        manifest_path: &Path,
+        krate: String,
+        alias: &str,
+        dependency: String,
+    ) -> Self {
+        let (src, span) = highlight(span, manifest_path, alias);
+        ForbiddenCrateDependency {
+            krate,
+            dependency,
+            src,
+            span,
+        }
+    }
+}
+
+impl ForbiddenCrateUsage {
+    pub fn from_span_and_location(
+        span: CodeSpan,
+        location: &Path,
+        ident: &str,
+        dependency: String,
+        accessed_in: String,
+    ) -> Self {
+        let (src, span) = highlight(span, location, ident);
+        ForbiddenCrateUsage {
+            dependency,
+            accessed_in,
+            src,
+            span,
+        }
+    }
+}
+
+fn highlight(span: CodeSpan, location: &Path, needle: &str) -> (NamedSource, SourceSpan) {
+    let sample = fs::read_to_string(location).expect("path exists");
+    let sample = get_code_sample_region(&sample, &span);
+    let span = sample
+        .find(needle)
+        .map(|start| (start, needle.len()))
+        .unwrap_or((0, sample.len()))
+        .into();
+
+    (
+        NamedSource::new(get_relative_location(location), sample),
+        span,
+    )
+}
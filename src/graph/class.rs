@@ -0,0 +1,175 @@
+use crate::ast::structs::Field;
+use crate::ast::{module_tree, ItemPath, Visibility};
+use crate::rule::impl_block::impl_matches;
+use crate::{ExludeModules, ModuleTree};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Structs and enums along with the traits they derive or implement, see
+/// [`ClassDiagram::builder`].
+///
+/// **Example:**
+/// ```rust
+/// use archunit_rs::graph::ClassDiagram;
+/// use archunit_rs::ExludeModules;
+///
+/// let mermaid = ClassDiagram::builder(ExludeModules::cfg_test())
+///     .reside_in_a_module("archunit_rs::graph*")
+///     .build()
+///     .to_mermaid();
+///
+/// assert!(mermaid.contains(r#"class archunit_rs__graph__Edge["Edge"]"#));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ClassDiagram {
+    /// Classes keyed by the path of their type.
+    pub classes: BTreeMap<String, Class>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Class {
+    pub name: String,
+    pub kind: ClassKind,
+    /// Fields of a struct or variants of an enum.
+    pub members: Vec<Member>,
+    pub derives: Vec<String>,
+    /// Traits implemented by hand, as written in the impl blocks.
+    pub traits: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassKind {
+    Struct,
+    Enum,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Member {
+    pub name: String,
+    pub public: bool,
+}
+
+#[derive(Debug)]
+pub struct ClassDiagramBuilder {
+    filters: ExludeModules<'static>,
+    types: Vec<String>,
+}
+
+impl ClassDiagram {
+    /// Builder for the class diagram of the crate under test, `filters` apply to its modules.
+    pub fn builder(filters: ExludeModules<'static>) -> ClassDiagramBuilder {
+        ClassDiagramBuilder {
+            filters,
+            types: vec![],
+        }
+    }
+
+    /// All the traits derived or implemented by the classes.
+    pub fn traits(&self) -> BTreeSet<&str> {
+        self.classes
+            .values()
+            .flat_map(|class| class.derives.iter().chain(class.traits.iter()))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl ClassDiagramBuilder {
+    /// Only keep the structs and enums matching `pattern`, like the `reside_in_a_module`
+    /// conditions of struct and enum rules. Patterns add up.
+    pub fn reside_in_a_module(mut self, pattern: &str) -> Self {
+        self.types.push(pattern.to_owned());
+        self
+    }
+
+    /// Build the diagram of the crate under test.
+    pub fn build(&self) -> ClassDiagram {
+        self.build_in(module_tree())
+    }
+
+    /// Same as [`ClassDiagramBuilder::build`], for the given tree, see
+    /// [`ModuleTree::load_from`].
+    pub fn build_in(&self, tree: &'static ModuleTree) -> ClassDiagram {
+        let matches = |path: &ItemPath| {
+            self.types.is_empty()
+                || self
+                    .types
+                    .iter()
+                    .any(|pattern| path.match_struct_path(pattern))
+        };
+
+        let mut diagram = ClassDiagram::default();
+        for struct_ in tree
+            .flatten_structs(&self.filters)
+            .0
+            .into_iter()
+            .filter(|struct_| matches(&struct_.path))
+        {
+            diagram.classes.insert(
+                struct_.path.to_string(),
+                Class {
+                    name: struct_.ident.clone(),
+                    kind: ClassKind::Struct,
+                    members: struct_
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .map(Member::from)
+                        .collect(),
+                    derives: struct_.derives.clone(),
+                    traits: BTreeSet::new(),
+                },
+            );
+        }
+
+        for enum_ in tree
+            .flatten_enums(&self.filters)
+            .0
+            .into_iter()
+            .filter(|enum_| matches(&enum_.path))
+        {
+            diagram.classes.insert(
+                enum_.path.to_string(),
+                Class {
+                    name: enum_.ident.clone(),
+                    kind: ClassKind::Enum,
+                    members: enum_
+                        .variants
+                        .iter()
+                        .map(|variant| Member {
+                            name: variant.ident.clone(),
+                            public: true,
+                        })
+                        .collect(),
+                    derives: enum_.derives.clone(),
+                    traits: BTreeSet::new(),
+                },
+            );
+        }
+
+        // Impl blocks are matched by type name, like the `implement` assertions
+        for imp in &impl_matches(tree, &self.filters).0 {
+            let Some(trait_) = &imp.trait_impl else {
+                continue;
+            };
+
+            for class in diagram
+                .classes
+                .values_mut()
+                .filter(|class| class.name == imp.self_ty.name())
+            {
+                class.traits.insert(trait_.to_string());
+            }
+        }
+
+        diagram
+    }
+}
+
+impl From<(usize, &Field)> for Member {
+    fn from((idx, field): (usize, &Field)) -> Self {
+        Member {
+            name: field.name.clone().unwrap_or_else(|| idx.to_string()),
+            public: field.visibility == Visibility::Public,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::graph::DependencyGraph;
+use std::fmt::Write;
+
+impl DependencyGraph {
+    /// Render the graph in the Graphviz DOT language, layers are rendered as clusters and
+    /// dependencies violating a rule in red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph dependencies {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box];\n");
+
+        for (idx, (layer, nodes)) in self.layers.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{idx} {{");
+            let _ = writeln!(dot, "        label={};", quote(layer));
+            for node in nodes {
+                let _ = writeln!(dot, "        {};", quote(node));
+            }
+            dot.push_str("    }\n");
+        }
+
+        for node in self
+            .nodes
+            .iter()
+            .filter(|node| self.layer_of(node).is_none())
+        {
+            let _ = writeln!(dot, "    {};", quote(node));
+        }
+
+        for ((from, to), edge) in &self.edges {
+            let _ = write!(dot, "    {} -> {}", quote(from), quote(to));
+            if edge.violation {
+                dot.push_str(" [color=red]");
+            }
+            dot.push_str(";\n");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph::{DependencyGraph, Edge};
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_render_dot_graph() {
+        let mut graph = DependencyGraph::default();
+        graph.nodes.insert("app::domain".to_owned());
+        graph.nodes.insert("app::infra".to_owned());
+        graph.layers.insert(
+            "domain".to_owned(),
+            ["app::domain".to_owned()].into_iter().collect(),
+        );
+        graph.edges.insert(
+            ("app::domain".to_owned(), "app::infra".to_owned()),
+            Edge {
+                uses: 1,
+                violation: true,
+            },
+        );
+
+        assert_that!(graph.to_dot().as_str()).is_equal_to(
+            r#"digraph dependencies {
+    rankdir=LR;
+    node [shape=box];
+    subgraph cluster_0 {
+        label="domain";
+        "app::domain";
+    }
+    "app::infra";
+    "app::domain" -> "app::infra" [color=red];
+}
+"#,
+        );
+    }
+}
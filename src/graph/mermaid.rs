@@ -0,0 +1,173 @@
+use crate::graph::{node_id, ClassDiagram, ClassKind, DependencyGraph};
+use std::fmt::Write;
+
+impl DependencyGraph {
+    /// Render the graph as a Mermaid flowchart, layers are rendered as subgraphs and
+    /// dependencies violating a rule in red.
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::new();
+        mermaid.push_str("flowchart LR\n");
+
+        for (idx, (layer, nodes)) in self.layers.iter().enumerate() {
+            let _ = writeln!(mermaid, "    subgraph layer_{idx}[\"{}\"]", escape(layer));
+            for node in nodes {
+                let _ = writeln!(mermaid, "        {}[\"{node}\"]", node_id(node));
+            }
+            mermaid.push_str("    end\n");
+        }
+
+        for node in self
+            .nodes
+            .iter()
+            .filter(|node| self.layer_of(node).is_none())
+        {
+            let _ = writeln!(mermaid, "    {}[\"{node}\"]", node_id(node));
+        }
+
+        let mut violations = vec![];
+        for (idx, ((from, to), edge)) in self.edges.iter().enumerate() {
+            let _ = writeln!(mermaid, "    {} --> {}", node_id(from), node_id(to));
+            if edge.violation {
+                violations.push(idx.to_string());
+            }
+        }
+
+        if !violations.is_empty() {
+            let _ = writeln!(mermaid, "    linkStyle {} stroke:red", violations.join(","));
+        }
+
+        mermaid
+    }
+}
+
+impl ClassDiagram {
+    /// Render the diagram as a Mermaid class diagram, derived traits are labeled `derive`.
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::new();
+        mermaid.push_str("classDiagram\n");
+
+        for (path, class) in &self.classes {
+            let _ = writeln!(
+                mermaid,
+                "    class {}[\"{}\"] {{",
+                node_id(path),
+                class.name
+            );
+            if class.kind == ClassKind::Enum {
+                mermaid.push_str("        <<enumeration>>\n");
+            }
+            for member in &class.members {
+                let visibility = match (class.kind, member.public) {
+                    (ClassKind::Enum, _) => "",
+                    (ClassKind::Struct, true) => "+",
+                    (ClassKind::Struct, false) => "-",
+                };
+                let _ = writeln!(mermaid, "        {visibility}{}", member.name);
+            }
+            mermaid.push_str("    }\n");
+        }
+
+        for trait_ in self.traits() {
+            let _ = writeln!(mermaid, "    class {}[\"{trait_}\"] {{", node_id(trait_));
+            mermaid.push_str("        <<trait>>\n");
+            mermaid.push_str("    }\n");
+        }
+
+        for (path, class) in &self.classes {
+            for derive in &class.derives {
+                let _ = writeln!(
+                    mermaid,
+                    "    {} ..|> {} : derive",
+                    node_id(path),
+                    node_id(derive)
+                );
+            }
+            for trait_ in &class.traits {
+                let _ = writeln!(mermaid, "    {} ..|> {}", node_id(path), node_id(trait_));
+            }
+        }
+
+        mermaid
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph::{Class, ClassDiagram, ClassKind, DependencyGraph, Edge, Member};
+    use speculoos::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn should_render_mermaid_flowchart() {
+        let mut graph = DependencyGraph::default();
+        graph.nodes.insert("app::domain".to_owned());
+        graph.nodes.insert("app::infra".to_owned());
+        graph.layers.insert(
+            "domain".to_owned(),
+            ["app::domain".to_owned()].into_iter().collect(),
+        );
+        graph.edges.insert(
+            ("app::domain".to_owned(), "app::infra".to_owned()),
+            Edge {
+                uses: 1,
+                violation: true,
+            },
+        );
+
+        assert_that!(graph.to_mermaid().as_str()).is_equal_to(
+            r#"flowchart LR
+    subgraph layer_0["domain"]
+        app__domain["app::domain"]
+    end
+    app__infra["app::infra"]
+    app__domain --> app__infra
+    linkStyle 0 stroke:red
+"#,
+        );
+    }
+
+    #[test]
+    fn should_render_mermaid_class_diagram() {
+        let mut diagram = ClassDiagram::default();
+        diagram.classes.insert(
+            "app::Order".to_owned(),
+            Class {
+                name: "Order".to_owned(),
+                kind: ClassKind::Struct,
+                members: vec![
+                    Member {
+                        name: "id".to_owned(),
+                        public: true,
+                    },
+                    Member {
+                        name: "lines".to_owned(),
+                        public: false,
+                    },
+                ],
+                derives: vec!["Debug".to_owned()],
+                traits: BTreeSet::from(["fmt::Display".to_owned()]),
+            },
+        );
+
+        assert_that!(diagram.to_mermaid().as_str()).is_equal_to(
+            r#"classDiagram
+    class app__Order["Order"] {
+        +id
+        -lines
+    }
+    class Debug["Debug"] {
+        <<trait>>
+    }
+    class fmt__Display["fmt::Display"] {
+        <<trait>>
+    }
+    app__Order ..|> Debug : derive
+    app__Order ..|> fmt__Display
+"#,
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Module dependency graphs and class diagrams, to discuss the architecture of a crate.
+//!
+//! The dependency graph has a node per module and an edge per module dependency, resolved from
+//! the `use` declarations of the crate. Dependencies on other crates are left out. It can be
+//! rendered as Graphviz DOT, Mermaid or PlantUML, and so can [`ClassDiagram`]s.
+//!
+//! **Example:**
+//! ```rust
+//! use archunit_rs::graph::DependencyGraph;
+//! use archunit_rs::layer_rule::{layered_architecture, LayerDefinitionBuilder};
+//! use archunit_rs::ExludeModules;
+//!
+//! let layers = layered_architecture()
+//!     .layer("rules")
+//!     .defined_by("archunit_rs::rule")
+//!     .layer("ast")
+//!     .defined_by("archunit_rs::ast");
+//!
+//! let dot = DependencyGraph::builder(ExludeModules::cfg_test())
+//!     .depth(1)
+//!     .group_by_layers(&layers.into())
+//!     .build()
+//!     .to_dot();
+//!
+//! assert!(dot.contains(r#""archunit_rs::rule" -> "archunit_rs::ast""#));
+//! ```
+use crate::assertion_result::AssertionResult;
+use crate::ast::{module_tree, ItemPath, ModuleUse};
+use crate::layer_rule::LayeredArchitecture;
+use crate::{ExludeModules, ModuleTree};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub mod class;
+pub mod dot;
+pub mod mermaid;
+pub mod plantuml;
+
+pub use class::{Class, ClassDiagram, ClassKind, Member};
+
+/// Modules and their dependencies, see [`DependencyGraph::builder`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    pub nodes: BTreeSet<String>,
+    /// Dependencies keyed by their source and target modules.
+    pub edges: BTreeMap<(String, String), Edge>,
+    /// Nodes grouped by the layer they belong to.
+    pub layers: BTreeMap<String, BTreeSet<String>>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    /// Number of `use` declarations backing this dependency.
+    pub uses: usize,
+    /// Whether one of these `use` declarations violates a rule.
+    pub violation: bool,
+}
+
+#[derive(Debug)]
+pub struct DependencyGraphBuilder<'a> {
+    filters: ExludeModules<'static>,
+    depth: Option<usize>,
+    modules: Vec<String>,
+    layers: BTreeMap<String, String>,
+    violations: Vec<&'a AssertionResult>,
+}
+
+impl DependencyGraph {
+    /// Builder for the dependency graph of the crate under test, `filters` apply to its modules.
+    pub fn builder<'a>(filters: ExludeModules<'static>) -> DependencyGraphBuilder<'a> {
+        DependencyGraphBuilder {
+            filters,
+            depth: None,
+            modules: vec![],
+            layers: BTreeMap::new(),
+            violations: vec![],
+        }
+    }
+
+    /// The layer `node` belongs to, if any.
+    pub fn layer_of(&self, node: &str) -> Option<&str> {
+        self.layers
+            .iter()
+            .find(|(_, nodes)| nodes.contains(node))
+            .map(|(layer, _)| layer.as_str())
+    }
+}
+
+impl<'a> DependencyGraphBuilder<'a> {
+    /// Collapse modules nested deeper than `depth` into their ancestor, `0` being the crate root.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Only keep the modules matching `pattern`, like the `reside_in_a_module` conditions of
+    /// module rules. Patterns add up.
+    pub fn reside_in_a_module(mut self, pattern: &str) -> Self {
+        self.modules.push(pattern.to_owned());
+        self
+    }
+
+    /// Group modules by the layers of the given architecture.
+    pub fn group_by_layers(mut self, architecture: &LayeredArchitecture) -> Self {
+        self.layers = architecture.layer_definitions.clone();
+        self
+    }
+
+    /// Flag the dependencies whose `use` declarations are reported as violations in `results`.
+    pub fn highlight_violations(mut self, results: &'a [AssertionResult]) -> Self {
+        self.violations.extend(results);
+        self
+    }
+
+    /// Build the graph of the crate under test.
+    pub fn build(&self) -> DependencyGraph {
+        self.build_in(module_tree())
+    }
+
+    /// Same as [`DependencyGraphBuilder::build`], for the given tree, see
+    /// [`ModuleTree::load_from`].
+    pub fn build_in(&self, tree: &'static ModuleTree) -> DependencyGraph {
+        let mut modules = tree.flatten(&self.filters).0;
+        if !self.modules.is_empty() {
+            modules.retain(|path, _| {
+                self.modules
+                    .iter()
+                    .any(|pattern| path.match_module_path(pattern))
+            });
+        }
+
+        let mut graph = DependencyGraph::default();
+
+        for (path, module) in &modules {
+            let from = self.collapse(path);
+            graph.nodes.insert(from.clone());
+
+            for usage in &module.dependencies {
+                let Some(target) = resolve_use(tree, &modules, path, usage) else {
+                    continue;
+                };
+
+                let to = self.collapse(target);
+                if from == to {
+                    continue;
+                }
+
+                let violation = self.violations.iter().any(|result| {
+                    result.actual.iter().any(|violation| {
+                        violation.location == module.real_path && violation.span == usage.span
+                    })
+                });
+
+                let edge = graph.edges.entry((from.clone(), to)).or_default();
+                edge.uses += 1;
+                edge.violation |= violation;
+            }
+        }
+
+        for (layer, layer_path) in &self.layers {
+            let nodes = graph
+                .nodes
+                .iter()
+                .filter(|node| resides_in(node, layer_path))
+                .cloned()
+                .collect::<BTreeSet<String>>();
+
+            if !nodes.is_empty() {
+                graph.layers.insert(layer.clone(), nodes);
+            }
+        }
+
+        graph
+    }
+
+    fn collapse(&self, path: &ItemPath) -> String {
+        match self.depth {
+            Some(depth) => path
+                .as_str()
+                .split("::")
+                .take(depth + 1)
+                .collect::<Vec<&str>>()
+                .join("::"),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// The module of `tree` a `use` declaration of the module at `path` points to, or `None` for
+/// other crates. Paths into items resolve to the module declaring them.
+pub(crate) fn resolve_use<'a>(
+    tree: &ModuleTree,
+    modules: &BTreeMap<&'a ItemPath, &'a ModuleTree>,
+    path: &ItemPath,
+    usage: &ModuleUse,
+) -> Option<&'a ItemPath> {
+    let mut segments = usage.parts.split("::").peekable();
+    // Paths starting with a module name are relative to the current module
+    let mut anchored = true;
+    let mut base = match segments.peek().copied() {
+        Some("crate") => {
+            segments.next();
+            tree.path.clone()
+        }
+        Some(name) if name == tree.ident => {
+            segments.next();
+            tree.path.clone()
+        }
+        Some("self" | "super") => path.clone(),
+        _ => {
+            anchored = false;
+            path.clone()
+        }
+    };
+
+    while let Some(segment) = segments.peek().copied() {
+        match segment {
+            "self" => {}
+            "super" => base = base.parent(),
+            _ => break,
+        }
+        segments.next();
+    }
+
+    // The longest prefix of the path naming a module of the crate
+    let mut resolved = modules
+        .get_key_value(&base)
+        .map(|(module, _)| *module)
+        .filter(|_| anchored);
+
+    let mut candidate = base;
+    for segment in segments {
+        candidate = candidate.join(segment);
+        match modules.get_key_value(&candidate) {
+            Some((module, _)) => resolved = Some(*module),
+            None => break,
+        }
+    }
+
+    resolved
+}
+
+/// An identifier for `path` in diagram languages which do not allow `::` in names.
+pub(crate) fn node_id(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn resides_in(path: &str, module: &str) -> bool {
+    path == module
+        || path
+            .strip_prefix(module)
+            .is_some_and(|rest| rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::{module_tree, CodeSpan, ItemPath, ModuleUse};
+    use crate::graph::resolve_use;
+    use crate::ExludeModules;
+    use speculoos::prelude::*;
+
+    fn resolve(from: &str, parts: &str) -> Option<String> {
+        let tree = module_tree();
+        let modules = tree.flatten(&ExludeModules::default()).0;
+        let usage = ModuleUse {
+            parts: parts.to_owned(),
+            span: CodeSpan::default(),
+        };
+
+        resolve_use(tree, &modules, &ItemPath::new(from.to_owned()), &usage)
+            .map(ToString::to_string)
+    }
+
+    #[test]
+    fn should_resolve_use_declarations_to_modules() {
+        assert_that!(resolve("archunit_rs::graph", "crate::ast"))
+            .is_equal_to(Some("archunit_rs::ast".to_owned()));
+        assert_that!(resolve("archunit_rs::graph", "crate::ast::structs::Struct"))
+            .is_equal_to(Some("archunit_rs::ast::structs".to_owned()));
+        assert_that!(resolve("archunit_rs::ast::enums", "super::cfg"))
+            .is_equal_to(Some("archunit_rs::ast::cfg".to_owned()));
+        assert_that!(resolve("archunit_rs", "ast::cfg"))
+            .is_equal_to(Some("archunit_rs::ast::cfg".to_owned()));
+    }
+
+    #[test]
+    fn should_not_resolve_other_crates() {
+        assert_that!(resolve("archunit_rs::graph", "std::collections")).is_none();
+        assert_that!(resolve("archunit_rs::graph", "miette")).is_none();
+    }
+}
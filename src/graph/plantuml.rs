@@ -0,0 +1,141 @@
+use crate::graph::{node_id, ClassDiagram, ClassKind, DependencyGraph};
+use std::fmt::Write;
+
+impl DependencyGraph {
+    /// Render the graph as a PlantUML component diagram, layers are rendered as packages and
+    /// dependencies violating a rule in red.
+    pub fn to_plantuml(&self) -> String {
+        let mut uml = String::new();
+        uml.push_str("@startuml\n");
+
+        for (layer, nodes) in &self.layers {
+            let _ = writeln!(uml, "package \"{layer}\" {{");
+            for node in nodes {
+                let _ = writeln!(uml, "    [{node}] as {}", node_id(node));
+            }
+            uml.push_str("}\n");
+        }
+
+        for node in self
+            .nodes
+            .iter()
+            .filter(|node| self.layer_of(node).is_none())
+        {
+            let _ = writeln!(uml, "[{node}] as {}", node_id(node));
+        }
+
+        for ((from, to), edge) in &self.edges {
+            let arrow = if edge.violation { "-[#red]->" } else { "-->" };
+            let _ = writeln!(uml, "{} {arrow} {}", node_id(from), node_id(to));
+        }
+
+        uml.push_str("@enduml\n");
+        uml
+    }
+}
+
+impl ClassDiagram {
+    /// Render the diagram as a PlantUML class diagram, derived traits are labeled `derive`.
+    pub fn to_plantuml(&self) -> String {
+        let mut uml = String::new();
+        uml.push_str("@startuml\n");
+
+        for (path, class) in &self.classes {
+            let kind = match class.kind {
+                ClassKind::Struct => "class",
+                ClassKind::Enum => "enum",
+            };
+            let _ = writeln!(uml, "{kind} \"{}\" as {} {{", class.name, node_id(path));
+            for member in &class.members {
+                let visibility = match (class.kind, member.public) {
+                    (ClassKind::Enum, _) => "",
+                    (ClassKind::Struct, true) => "+",
+                    (ClassKind::Struct, false) => "-",
+                };
+                let _ = writeln!(uml, "    {visibility}{}", member.name);
+            }
+            uml.push_str("}\n");
+        }
+
+        for trait_ in self.traits() {
+            let _ = writeln!(uml, "interface \"{trait_}\" as {}", node_id(trait_));
+        }
+
+        for (path, class) in &self.classes {
+            for derive in &class.derives {
+                let _ = writeln!(uml, "{} ..|> {} : derive", node_id(path), node_id(derive));
+            }
+            for trait_ in &class.traits {
+                let _ = writeln!(uml, "{} ..|> {}", node_id(path), node_id(trait_));
+            }
+        }
+
+        uml.push_str("@enduml\n");
+        uml
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph::{Class, ClassDiagram, ClassKind, DependencyGraph, Edge, Member};
+    use speculoos::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn should_render_plantuml_component_diagram() {
+        let mut graph = DependencyGraph::default();
+        graph.nodes.insert("app::domain".to_owned());
+        graph.nodes.insert("app::infra".to_owned());
+        graph.layers.insert(
+            "domain".to_owned(),
+            ["app::domain".to_owned()].into_iter().collect(),
+        );
+        graph.edges.insert(
+            ("app::domain".to_owned(), "app::infra".to_owned()),
+            Edge {
+                uses: 1,
+                violation: true,
+            },
+        );
+
+        assert_that!(graph.to_plantuml().as_str()).is_equal_to(
+            r#"@startuml
+package "domain" {
+    [app::domain] as app__domain
+}
+[app::infra] as app__infra
+app__domain -[#red]-> app__infra
+@enduml
+"#,
+        );
+    }
+
+    #[test]
+    fn should_render_plantuml_class_diagram() {
+        let mut diagram = ClassDiagram::default();
+        diagram.classes.insert(
+            "app::Status".to_owned(),
+            Class {
+                name: "Status".to_owned(),
+                kind: ClassKind::Enum,
+                members: vec![Member {
+                    name: "Open".to_owned(),
+                    public: true,
+                }],
+                derives: vec![],
+                traits: BTreeSet::from(["Display".to_owned()]),
+            },
+        );
+
+        assert_that!(diagram.to_plantuml().as_str()).is_equal_to(
+            r#"@startuml
+enum "Status" as app__Status {
+    Open
+}
+interface "Display" as Display
+app__Status ..|> Display
+@enduml
+"#,
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Support for the [`test`](crate::test) attribute, which turns functions returning a rule into
+//! one test per rule.
+//!
+//! **Example:**
+//! ```rust
+//! use archunit_rs::rule::ArchRuleBuilder;
+//! use archunit_rs::rule::enums::{EnumPredicateConjunctionBuilder, Enums};
+//! use archunit_rs::ExludeModules;
+//!
+//! #[archunit_rs::test]
+//! fn enums_should_derive_debug() -> EnumPredicateConjunctionBuilder {
+//!     Enums::all_should(ExludeModules::cfg_test()).implement_or_derive("Debug")
+//! }
+//! ```
+//!
+//! Every generated test evaluates its rule against the same module tree, which is only loaded
+//! once per test binary.
+
+use crate::assertion_result::AssertionResult;
+use crate::crate_rule::CrateRule;
+use crate::layer_rule::LayerArchitectureAssertionChainBuilder;
+use crate::plantuml_rule::PlantUmlRule;
+use crate::public_api::PublicApiRule;
+use crate::report::text::ReportConfig;
+use crate::rule::assertable::Assertable;
+use crate::rule::{
+    ArchRule, Assertion, CheckRule, Condition, PredicateConjunctionBuilder, Subject,
+};
+
+/// A rule that can be returned from a function annotated with [`test`](crate::test).
+pub trait ArchTestRule {
+    fn evaluate_rule(self) -> AssertionResult;
+}
+
+impl<C, A, S> ArchTestRule for PredicateConjunctionBuilder<C, A, S>
+where
+    C: Condition,
+    A: Assertion,
+    S: Subject,
+    Self: CheckRule<C, A, S, ArchRule<C, A, S>>,
+    ArchRule<C, A, S>: Assertable<C, A, S>,
+{
+    fn evaluate_rule(self) -> AssertionResult {
+        self.evaluate()
+    }
+}
+
+impl ArchTestRule for LayerArchitectureAssertionChainBuilder {
+    fn evaluate_rule(self) -> AssertionResult {
+        self.evaluate()
+    }
+}
+
+impl ArchTestRule for CrateRule {
+    fn evaluate_rule(self) -> AssertionResult {
+        self.evaluate()
+    }
+}
+
+impl ArchTestRule for PlantUmlRule {
+    fn evaluate_rule(self) -> AssertionResult {
+        self.evaluate()
+    }
+}
+
+impl ArchTestRule for PublicApiRule {
+    fn evaluate_rule(self) -> AssertionResult {
+        self.evaluate()
+    }
+}
+
+impl ArchTestRule for AssertionResult {
+    fn evaluate_rule(self) -> AssertionResult {
+        self
+    }
+}
+
+/// Evaluate `rule` and panic with a report named after it if it is violated, the report is
+/// configured via [`ReportConfig::from_env`].
+pub fn run<R: ArchTestRule>(name: &str, rule: R) {
+    let result = rule.evaluate_rule();
+    if !result.is_success() {
+        panic!(
+            "Architecture rule '{name}' failed\n{}",
+            result.render(&ReportConfig::from_env())
+        )
+    }
+}
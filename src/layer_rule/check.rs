@@ -1,149 +1,102 @@
-use crate::ast::{module_tree, ModuleUse};
+use crate::assertion_result::AssertionResult;
+use crate::ast::module_tree;
+use crate::layer_rule::report::ForbiddenLayerAccess;
 use crate::layer_rule::{LayerAssertion, LayeredArchitecture};
-use crate::rule::modules::ModuleMatches;
-use crate::ExludeModules;
+use crate::{ExludeModules, ModuleTree};
 use miette::ErrReport;
 use miette::Result;
 
-use crate::layer_rule::report::ForbiddenLayerAccess;
-
-#[derive(Debug)]
-struct LayerRule {
-    layer_name: String,
-    layer_path: String,
-    layer_assertions: LayerAssertion,
-}
-
 impl LayeredArchitecture {
     pub fn check(self) -> Result<(), Vec<ErrReport>> {
-        let mut architecture_rule_violation = vec![];
-        let modules = module_tree();
-        let rules: Vec<LayerRule> = self
-            .layer_definitions
-            .iter()
-            .filter_map(|(layer_name, layer_path)| {
-                self.layer_assertions
-                    .get(layer_name)
-                    .cloned()
-                    .map(|assertion| LayerRule {
-                        layer_name: layer_name.to_string(),
-                        layer_path: layer_path.to_string(),
-                        layer_assertions: assertion,
-                    })
-            })
-            .collect();
+        let result = self.evaluate();
+        if result.is_success() {
+            Ok(())
+        } else {
+            Err(result
+                .actual
+                .into_iter()
+                .map(|violation| violation.report)
+                .collect())
+        }
+    }
 
-        for rule in &rules {
-            match &rule.layer_assertions {
-                LayerAssertion::MayNotBeAccessedByAnyLayer => {
-                    unimplemented!()
-                }
-                LayerAssertion::MayOnlyBeAccessedByLayers(layers) => {
-                    // Get all layer that are not allowed
-                    let permitted_modules: Vec<&String> = rules
-                        .iter()
-                        .filter(|rule| layers.contains(&rule.layer_name))
-                        .map(|rule| &rule.layer_path)
-                        .collect();
+    /// Evaluate the layer assertions without panicking, each `use` of a layer from a module
+    /// outside of its permitted layers is reported as a violation.
+    pub fn evaluate(&self) -> AssertionResult {
+        self.evaluate_in(module_tree())
+    }
 
-                    let forbidden_layers: ModuleMatches = modules.module_that(
-                        |module| {
-                            // Not resides in ast
-                            !module.path.reside_in_any(permitted_modules.as_slice())
-                            // not reside in rule module
-                            && !module.path.reside_in(&rule.layer_path)
-                        },
-                        &ExludeModules::default(),
-                    );
+    /// Same as [`LayeredArchitecture::evaluate`], against the given tree instead of the crate
+    /// under test.
+    pub fn evaluate_in(&self, modules: &'static ModuleTree) -> AssertionResult {
+        let mut result = AssertionResult::new();
+        result.push_expected("Layered architecture where ");
+
+        for (idx, (layer_name, assertion)) in self.layer_assertions.iter().enumerate() {
+            let Some(layer_path) = self.layer_definitions.get(layer_name) else {
+                continue;
+            };
 
-                    // Get each module tree for forbidden layers
-                    for (_module_path, tree) in forbidden_layers.0 {
-                        // Flatten dependencies for each forbidden layers
-                        for (path, (_, deps)) in tree.flatten_deps(&ExludeModules::default()).0 {
-                            // Find forbidden access
-                            let forbidden_dependencies: Vec<&ModuleUse> = deps
-                                .iter()
-                                .filter(|deps| deps.matching(&rule.layer_path.clone()))
-                                .collect();
+            if idx > 0 {
+                result.push_expected(", ");
+            }
 
-                            // Act
-                            if !forbidden_dependencies.is_empty() {
-                                for dep in forbidden_dependencies {
-                                    println!("{}", dep.parts);
-                                }
-                                println!(
-                                    "Forbidden access to layer {} by layer {}",
-                                    rule.layer_name, path
-                                );
-                                // println!("{:?}", forbidden_dependencies);
-                            }
-                        }
-                    }
+            let permitted_layers: Vec<&String> = match assertion {
+                LayerAssertion::MayNotBeAccessedByAnyLayer => {
+                    result.push_expected(format!(
+                        "layer '{layer_name}' may not be accessed by any layer"
+                    ));
+                    vec![]
                 }
                 LayerAssertion::MayOnlyBeAccessedByLayer(layer) => {
-                    let layer_module_path = self
-                        .layer_definitions
-                        .get(layer)
-                        .expect("layer should be defined");
+                    result.push_expected(format!(
+                        "layer '{layer_name}' may only be accessed by layer '{layer}'"
+                    ));
+                    vec![layer]
+                }
+                LayerAssertion::MayOnlyBeAccessedByLayers(layers) => {
+                    result.push_expected(format!(
+                        "layer '{layer_name}' may only be accessed by layers {layers:?}"
+                    ));
+                    layers.iter().collect()
+                }
+            };
 
-                    let forbidden_layers: ModuleMatches = modules.module_that(
-                        |module| {
-                            // Not resides in ast
-                            !module.path.reside_in(layer_module_path)
-                            // not reside in rule module
-                            && !module.path.reside_in(&rule.layer_path)
-                        },
-                        &ExludeModules::default(),
-                    );
+            let permitted_modules: Vec<&String> = permitted_layers
+                .iter()
+                .filter_map(|layer| self.layer_definitions.get(*layer))
+                .collect();
 
-                    // Get each module tree for forbidden layers
-                    for (_, tree) in forbidden_layers.0 {
-                        // Flatten dependencies for each forbidden layers
-                        for (path, (real_path, deps)) in
-                            tree.flatten_deps(&ExludeModules::default()).0
-                        {
-                            for usage in deps {
-                                let rule_module_path = rule.layer_path.as_str();
-                                if usage.starts_with(rule_module_path) {
-                                    let error = ForbiddenLayerAccess::from_span_and_location(
-                                        usage.span,
-                                        real_path,
-                                        rule.layer_name.to_owned(),
-                                        rule_module_path.to_owned(),
-                                        path.to_string(),
-                                    );
+            let forbidden_modules = modules.module_that(
+                |module| {
+                    !module.path.reside_in(layer_path)
+                        && !module.path.reside_in_any(permitted_modules.as_slice())
+                },
+                &ExludeModules::default(),
+            );
 
-                                    architecture_rule_violation.push(error.into())
-                                }
-                            }
-                            // Find forbidden access
-                            let forbidden_dependencies: Vec<&ModuleUse> = deps
-                                .iter()
-                                .filter(|deps| deps.matching(&rule.layer_path))
-                                .collect();
+            for module in forbidden_modules.0.values() {
+                for usage in module
+                    .dependencies
+                    .iter()
+                    .filter(|usage| usage.starts_with(&modules.ident, layer_path))
+                {
+                    let error = ForbiddenLayerAccess::from_span_and_location(
+                        usage.span,
+                        &module.real_path,
+                        layer_name.to_owned(),
+                        layer_path.to_owned(),
+                        module.path.to_string(),
+                    );
 
-                            // Act
-                            if !forbidden_dependencies.is_empty() {
-                                for dep in forbidden_dependencies {
-                                    println!("{}", dep.parts);
-                                }
-                                println!(
-                                    "Forbidden access to layer {} by layer {}",
-                                    rule.layer_name, path
-                                );
-                                // println!("{:?}", forbidden_dependencies);
-                            }
-                        }
-                    }
+                    result.push_actual(&module.path, &module.real_path, usage.span, error);
                 }
             }
         }
 
-        if architecture_rule_violation.is_empty() {
-            Ok(())
-        } else {
-            Err(architecture_rule_violation)
-        }
+        result.success = result.actual.is_empty();
+        result.sort_violations();
+        result
     }
 }
 
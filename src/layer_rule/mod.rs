@@ -1,15 +1,19 @@
+use crate::assertion_result::AssertionResult;
+use crate::ModuleTree;
 use miette::ErrReport;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 pub mod check;
 pub mod report;
 
-trait LayerDefinitionBuilder {
+/// Declares the next layer of a [`layered_architecture`], in scope to chain the definitions.
+pub trait LayerDefinitionBuilder {
     type DefinitionBuilder;
     fn layer(self, layer: &str) -> Self::DefinitionBuilder;
 }
 
-trait LayerAssertionBuilder {
+/// Starts the assertions on a layer of a [`layered_architecture`].
+pub trait LayerAssertionBuilder {
     type AssertionBuilder;
     fn where_layer(self, layer: &str) -> Self::AssertionBuilder;
 }
@@ -17,9 +21,9 @@ trait LayerAssertionBuilder {
 #[derive(Debug, PartialEq, Eq, Default)]
 pub struct LayeredArchitecture {
     // A named layer mapping to its actual module  path
-    layer_definitions: HashMap<String, String>,
+    pub(crate) layer_definitions: BTreeMap<String, String>,
     // Layer names mapped to their respective assertion
-    layer_assertions: HashMap<String, LayerAssertion>,
+    pub(crate) layer_assertions: BTreeMap<String, LayerAssertion>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -97,7 +101,7 @@ impl LayerAssertionBuilder for LayeredArchitectureDefinitionChainBuilder {
     type AssertionBuilder = LayerArchitectureAssertionBuilder;
 
     fn where_layer(self, layer: &str) -> Self::AssertionBuilder {
-        if self.architecture.layer_definitions.get(layer).is_none() {
+        if !self.architecture.layer_definitions.contains_key(layer) {
             panic!("Undefined layer: '{layer}'")
         }
 
@@ -113,7 +117,7 @@ impl LayerArchitectureAssertionBuilder {
         mut self,
         layer: &str,
     ) -> LayerArchitectureAssertionChainBuilder {
-        if self.architecture.layer_definitions.get(layer).is_none() {
+        if !self.architecture.layer_definitions.contains_key(layer) {
             panic!("Undefined layer: '{layer}' in assertion `may_only_be_accessed_by_layer('{layer}')`")
         }
         self.architecture.layer_assertions.insert(
@@ -130,12 +134,7 @@ impl LayerArchitectureAssertionBuilder {
         layers: &[&str],
     ) -> LayerArchitectureAssertionChainBuilder {
         for layer in layers {
-            if self
-                .architecture
-                .layer_definitions
-                .get(&layer.to_string())
-                .is_none()
-            {
+            if !self.architecture.layer_definitions.contains_key(*layer) {
                 panic!("Undefined layer: '{layer}' in assertion `may_only_be_accessed_by_layers('{layers:?}')`")
             }
         }
@@ -164,7 +163,7 @@ impl LayerAssertionBuilder for LayerArchitectureAssertionChainBuilder {
     type AssertionBuilder = LayerArchitectureAssertionBuilder;
 
     fn where_layer(self, layer: &str) -> LayerArchitectureAssertionBuilder {
-        if self.architecture.layer_definitions.get(layer).is_none() {
+        if !self.architecture.layer_definitions.contains_key(layer) {
             panic!("Undefined layer: '{layer}'")
         }
 
@@ -176,6 +175,16 @@ impl LayerAssertionBuilder for LayerArchitectureAssertionChainBuilder {
 }
 
 impl LayerArchitectureAssertionChainBuilder {
+    /// Evaluate the layer assertions without panicking.
+    pub fn evaluate(self) -> AssertionResult {
+        self.architecture.evaluate()
+    }
+
+    /// Evaluate the layer assertions against the given tree, see [`ModuleTree::load_from`].
+    pub fn evaluate_in(self, tree: &'static ModuleTree) -> AssertionResult {
+        self.architecture.evaluate_in(tree)
+    }
+
     pub fn check(self) -> Result<(), ErrReport> {
         if let Err(errs) = self.architecture.check() {
             for err in errs {
@@ -187,6 +196,18 @@ impl LayerArchitectureAssertionChainBuilder {
     }
 }
 
+impl From<LayeredArchitectureDefinitionChainBuilder> for LayeredArchitecture {
+    fn from(builder: LayeredArchitectureDefinitionChainBuilder) -> Self {
+        builder.architecture
+    }
+}
+
+impl From<LayerArchitectureAssertionChainBuilder> for LayeredArchitecture {
+    fn from(builder: LayerArchitectureAssertionChainBuilder) -> Self {
+        builder.architecture
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::layer_rule::{
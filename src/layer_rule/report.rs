@@ -1,4 +1,4 @@
-use crate::assertion_result::get_code_sample_region;
+use crate::assertion_result::{get_code_sample_region, get_relative_location};
 use crate::ast::CodeSpan;
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use std::fs;
@@ -28,19 +28,16 @@ impl ForbiddenLayerAccess {
     ) -> Self {
         let sample = fs::read_to_string(location).expect("path exists");
         let sample = get_code_sample_region(&sample, &span);
-        // TODO: make that a function, call it once in the upper level
-        let name = env!("CARGO_CRATE_NAME", "'CARGO_CRATE_NAME' should be set");
+        // The usage may start with `crate::` or the crate name, highlight the path after it
         let module_relative_path = layer_module
-            .strip_prefix(&format!("{name}::"))
-            .expect("module prefix ");
-        let start_hint = sample.find(module_relative_path).expect("path to exist");
-
-        let span = (start_hint, module_relative_path.len()).into();
-        let base = std::env::current_dir().expect("path to exist");
-        let location = location
-            .strip_prefix(base)
-            .expect("location to be in current dir");
-        let src = NamedSource::new(location.to_string_lossy(), sample);
+            .split_once("::")
+            .map_or(layer_module.as_str(), |(_, path)| path);
+        let span = sample
+            .find(module_relative_path)
+            .map(|start| (start, module_relative_path.len()))
+            .unwrap_or((0, sample.len()))
+            .into();
+        let src = NamedSource::new(get_relative_location(location), sample);
         ForbiddenLayerAccess {
             layer,
             layer_module,
@@ -1,3 +1,5 @@
+// `string_to_string` is gone from recent clippy releases, keep it for the older ones
+#![allow(renamed_and_removed_lints)]
 #![warn(
     clippy::todo,
     clippy::string_to_string,
@@ -8,38 +10,97 @@
 
 pub mod assertion_result;
 mod ast;
+pub mod config;
+pub mod crate_rule;
+pub mod graph;
+pub mod harness;
 pub mod layer_rule;
+pub mod metrics;
+pub mod plantuml_rule;
+pub mod public_api;
+pub mod report;
 pub mod rule;
 
-use ast::ModuleTree;
+pub use archunit_rs_macros::{archunit_test, test};
+pub use ast::cfg::{CfgOption, CfgPredicate, CfgSet};
+pub use ast::manifest::ManifestError;
+pub use ast::parse::{LoadError, LoadOptions, ModuleResolutionError};
+pub use ast::targets::{CrateTargets, Target, TargetKind};
+pub use ast::workspace::Workspace;
+pub use ast::{ModuleTree, Visibility};
+pub use config::check_config;
 pub use rule::modules::Modules;
 pub use rule::structs::Structs;
+use std::borrow::Cow;
 
 /// Control what to filters when running Archunit tests
-#[derive(Default, Debug, Clone)]
+///
+/// Modules, structs, enums and impl blocks are filtered on their `cfg` predicate. Options
+/// excluded with [`ExludeModules::exclude_cfg`] are considered unset, the others are unknown
+/// and items depending on them are kept, unless an active configuration is given with
+/// [`ExludeModules::active_cfg`].
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExludeModules<'a> {
-    pub(crate) exclude_cfg: Vec<&'a str>,
+    pub(crate) exclude_cfg: Vec<Cow<'a, str>>,
+    pub(crate) active_cfg: Option<CfgSet>,
 }
 
 impl<'a> ExludeModules<'a> {
     pub fn cfg_test() -> Self {
         Self {
-            exclude_cfg: vec!["test"],
+            exclude_cfg: vec![Cow::Borrowed("test")],
+            active_cfg: None,
         }
     }
-    /// Excludes all modules with the given cfg sattribute
-    pub fn exclude_cfg(mut self, cfg_attr: &'static str) -> Self {
-        self.exclude_cfg.push(cfg_attr);
+    /// Excludes all items only compiled with the given cfg option, either a name like `test`
+    /// or a key-value pair like `feature = "serde"`
+    pub fn exclude_cfg<S: Into<Cow<'a, str>>>(mut self, cfg_attr: S) -> Self {
+        self.exclude_cfg.push(cfg_attr.into());
         self
     }
 
+    /// Analyze the build with the given cfg options set, items not compiled in this
+    /// configuration are excluded.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::{CfgSet, ExludeModules, Structs};
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    ///
+    /// Structs::all_should(ExludeModules::default().active_cfg(CfgSet::new().flag("unix")))
+    ///     .implement_or_derive("Debug")
+    ///     .check();
+    /// ```
+    pub fn active_cfg(mut self, cfg: CfgSet) -> Self {
+        self.active_cfg = Some(cfg);
+        self
+    }
+
+    /// Whether an item compiled on `cfg` is kept.
+    pub(crate) fn is_enabled(&self, cfg: Option<&CfgPredicate>) -> bool {
+        let Some(cfg) = cfg else {
+            return true;
+        };
+
+        let known = |option: &CfgOption| {
+            let excluded = self.exclude_cfg.iter().any(|excluded| {
+                excluded
+                    .parse::<CfgOption>()
+                    .is_ok_and(|excluded| &excluded == option)
+            });
+
+            if excluded {
+                Some(false)
+            } else {
+                self.active_cfg.as_ref().map(|active| active.is_set(option))
+            }
+        };
+
+        cfg.evaluate(&known) != Some(false)
+    }
+
     fn filter(&self) -> impl FnMut(&&ModuleTree) -> bool + '_ {
-        move |module: &&ModuleTree| {
-            !module
-                .cfg_attr
-                .iter()
-                .any(|attr| self.exclude_cfg.contains(&attr.as_str()))
-        }
+        move |module: &&ModuleTree| self.is_enabled(module.cfg.as_ref())
     }
 }
 
@@ -48,8 +109,9 @@ mod thread_local_filter_test {
     use crate::rule::{ArchRuleBuilder, CheckRule};
     use crate::{ExludeModules, Structs};
 
-    // A struct that makes the arch test below fail (being private)
+    // A struct that makes the arch test below fail (being private), it is never constructed
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct RuleViolation;
 
     #[test]
@@ -0,0 +1,213 @@
+//! Robert C. Martin's package metrics, computed per module.
+//!
+//! - Afferent coupling (Ca): the number of modules depending on the module.
+//! - Efferent coupling (Ce): the number of modules the module depends on.
+//! - Instability: `Ce / (Ca + Ce)`, from 0 (stable) to 1 (unstable).
+//! - Abstractness: the ratio of traits to the types declared in the module.
+//! - Distance from the main sequence: `|A + I - 1|`, from 0 (balanced) to 1.
+//!
+//! Dependencies are resolved from `use` declarations and only count modules of the crate.
+//!
+//! **Example:**
+//! ```rust
+//! use archunit_rs::metrics::CouplingMetrics;
+//! use archunit_rs::ExludeModules;
+//!
+//! let metrics = CouplingMetrics::of_crate(&ExludeModules::cfg_test());
+//! let ast = metrics.get("archunit_rs::ast").expect("module exists");
+//!
+//! assert!(ast.afferent_coupling > 0);
+//! assert!(ast.instability() < 1.0);
+//! ```
+use crate::ast::module_tree;
+use crate::graph::resolve_use;
+use crate::{ExludeModules, ModuleTree};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// Metrics of all the modules of a crate.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CouplingMetrics {
+    pub modules: BTreeMap<String, ModuleMetrics>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ModuleMetrics {
+    /// Modules depending on this module.
+    pub dependents: BTreeSet<String>,
+    /// Modules this module depends on.
+    pub dependencies: BTreeSet<String>,
+    pub afferent_coupling: usize,
+    pub efferent_coupling: usize,
+    /// Traits declared in the module.
+    pub abstract_types: usize,
+    /// Structs and enums declared in the module.
+    pub concrete_types: usize,
+}
+
+impl CouplingMetrics {
+    /// Metrics of the crate under test.
+    pub fn of_crate(filters: &ExludeModules<'static>) -> Self {
+        Self::of(module_tree(), filters)
+    }
+
+    /// Metrics of the given tree, see [`ModuleTree::load_from`].
+    pub fn of(tree: &'static ModuleTree, filters: &ExludeModules<'static>) -> Self {
+        let modules = tree.flatten(filters).0;
+        let mut metrics = CouplingMetrics::default();
+
+        for (path, module) in &modules {
+            let entry = metrics.modules.entry(path.to_string()).or_default();
+            entry.abstract_types = module
+                .traits
+                .iter()
+                .filter(|trait_| filters.is_enabled(trait_.cfg.as_ref()))
+                .count();
+            entry.concrete_types = module
+                .structs
+                .iter()
+                .filter(|struct_| filters.is_enabled(struct_.cfg.as_ref()))
+                .count()
+                + module
+                    .enums
+                    .iter()
+                    .filter(|enum_| filters.is_enabled(enum_.cfg.as_ref()))
+                    .count();
+
+            for usage in &module.dependencies {
+                let Some(target) = resolve_use(tree, &modules, path, usage) else {
+                    continue;
+                };
+
+                if target == *path {
+                    continue;
+                }
+
+                metrics
+                    .modules
+                    .entry(path.to_string())
+                    .or_default()
+                    .dependencies
+                    .insert(target.to_string());
+                metrics
+                    .modules
+                    .entry(target.to_string())
+                    .or_default()
+                    .dependents
+                    .insert(path.to_string());
+            }
+        }
+
+        for module in metrics.modules.values_mut() {
+            module.afferent_coupling = module.dependents.len();
+            module.efferent_coupling = module.dependencies.len();
+        }
+
+        metrics
+    }
+
+    pub fn get(&self, module: &str) -> Option<&ModuleMetrics> {
+        self.modules.get(module)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ModuleMetrics)> {
+        self.modules
+            .iter()
+            .map(|(path, metrics)| (path.as_str(), metrics))
+    }
+}
+
+impl ModuleMetrics {
+    /// `Ce / (Ca + Ce)`, 0 for modules without any coupling.
+    pub fn instability(&self) -> f64 {
+        let coupling = self.afferent_coupling + self.efferent_coupling;
+        if coupling == 0 {
+            0.0
+        } else {
+            self.efferent_coupling as f64 / coupling as f64
+        }
+    }
+
+    /// Ratio of traits to declared types, 0 for modules without any type.
+    pub fn abstractness(&self) -> f64 {
+        let types = self.abstract_types + self.concrete_types;
+        if types == 0 {
+            0.0
+        } else {
+            self.abstract_types as f64 / types as f64
+        }
+    }
+
+    /// `|A + I - 1|`
+    pub fn distance_from_main_sequence(&self) -> f64 {
+        (self.abstractness() + self.instability() - 1.0).abs()
+    }
+}
+
+/// The allowed range of a module metric, thresholds are exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Bound {
+    Below(f64),
+    Above(f64),
+}
+
+impl Bound {
+    pub(crate) fn contains(&self, value: f64) -> bool {
+        match self {
+            Bound::Below(threshold) => value < *threshold,
+            Bound::Above(threshold) => value > *threshold,
+        }
+    }
+}
+
+impl Display for Bound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bound::Below(threshold) => write!(f, "below {threshold:.2}"),
+            Bound::Above(threshold) => write!(f, "above {threshold:.2}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::metrics::{CouplingMetrics, ModuleMetrics};
+    use crate::ExludeModules;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_compute_ratios() {
+        let metrics = ModuleMetrics {
+            afferent_coupling: 1,
+            efferent_coupling: 3,
+            abstract_types: 1,
+            concrete_types: 1,
+            ..ModuleMetrics::default()
+        };
+
+        assert_that!(metrics.instability()).is_equal_to(0.75);
+        assert_that!(metrics.abstractness()).is_equal_to(0.5);
+        assert_that!(metrics.distance_from_main_sequence()).is_equal_to(0.25);
+        assert_that!(ModuleMetrics::default().instability()).is_equal_to(0.0);
+    }
+
+    #[test]
+    fn should_count_coupling_between_modules() {
+        let metrics = CouplingMetrics::of_crate(&ExludeModules::cfg_test());
+        let metrics_module = metrics.get("archunit_rs::metrics").expect("module");
+        let graph = metrics.get("archunit_rs::graph").expect("module");
+
+        assert_that!(metrics_module.dependencies.contains("archunit_rs::graph")).is_true();
+        assert_that!(graph.dependents.contains("archunit_rs::metrics")).is_true();
+        assert_that!(metrics_module.afferent_coupling).is_equal_to(metrics_module.dependents.len());
+    }
+
+    #[test]
+    fn should_count_traits_as_abstract_types() {
+        let metrics = CouplingMetrics::of_crate(&ExludeModules::cfg_test());
+        let rule = metrics.get("archunit_rs::rule").expect("module");
+
+        assert_that!(rule.abstract_types).is_greater_than(0);
+        assert_that!(rule.abstractness()).is_greater_than(0.0);
+    }
+}
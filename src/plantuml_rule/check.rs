@@ -0,0 +1,73 @@
+use crate::assertion_result::AssertionResult;
+use crate::graph::resolve_use;
+use crate::plantuml_rule::report::UndeclaredDependency;
+use crate::plantuml_rule::{PlantUmlDiagram, PlantUmlRule};
+use crate::ModuleTree;
+use std::path::PathBuf;
+
+impl PlantUmlRule {
+    pub(crate) fn evaluate_tree(&self, tree: &'static ModuleTree) -> AssertionResult {
+        let mut result = AssertionResult::new();
+        result.push_expected(format!(
+            "Module dependencies should adhere to diagram '{}'",
+            self.diagram.display()
+        ));
+        result.rationale = self.rationale.clone();
+
+        let diagram = match PlantUmlDiagram::load(self.diagram_path(tree)) {
+            Ok(diagram) => diagram,
+            Err(err) => {
+                result.push_error(tree, err);
+                return result;
+            }
+        };
+
+        let modules = tree.flatten(&self.filters).0;
+        for (path, module) in &modules {
+            let sources = diagram.components_of(path.as_str());
+            if sources.is_empty() {
+                continue;
+            }
+
+            for usage in &module.dependencies {
+                let Some(target) = resolve_use(tree, &modules, path, usage) else {
+                    continue;
+                };
+
+                let targets = diagram.components_of(target.as_str());
+                let allowed = targets.is_empty()
+                    || sources.iter().any(|source| {
+                        targets.iter().any(|target| {
+                            source == target || diagram.arrows.contains(&(*source, *target))
+                        })
+                    });
+
+                if allowed {
+                    continue;
+                }
+
+                let error = UndeclaredDependency::from_span_and_location(
+                    usage.span,
+                    &module.real_path,
+                    path.to_string(),
+                    target.to_string(),
+                    &diagram.components[sources[0]].name,
+                    &diagram.components[targets[0]].name,
+                );
+
+                result.push_actual(path, &module.real_path, usage.span, error);
+            }
+        }
+
+        result.success = result.actual.is_empty();
+        result.sort_violations();
+        result
+    }
+
+    fn diagram_path(&self, tree: &ModuleTree) -> PathBuf {
+        match tree.manifest_dir() {
+            Some(dir) if self.diagram.is_relative() => dir.join(&self.diagram),
+            _ => self.diagram.clone(),
+        }
+    }
+}
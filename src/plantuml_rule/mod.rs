@@ -0,0 +1,118 @@
+//! Check that module dependencies follow a PlantUML component diagram.
+//!
+//! Components map to modules with stereotypes holding module patterns, arrows declare the
+//! allowed dependencies between components:
+//!
+//! ```text
+//! @startuml
+//! [Domain] <<my_crate::domain*>> as domain
+//! [Adapters] <<my_crate::infra*>> <<my_crate::web*>>
+//! [Adapters] --> domain
+//! @enduml
+//! ```
+//!
+//! Only dependencies between modules mapped to components are checked, dependencies within a
+//! component are always allowed.
+//!
+//! **Example:**
+//! ```rust,no_run
+//! use archunit_rs::plantuml_rule::adhere_to_plantuml_diagram;
+//! use archunit_rs::ExludeModules;
+//!
+//! adhere_to_plantuml_diagram(ExludeModules::cfg_test(), "docs/components.puml").check();
+//! ```
+use crate::assertion_result::AssertionResult;
+use crate::ast::module_tree;
+use crate::report::text::ReportConfig;
+use crate::rule::pattern::PathPattern;
+use crate::{ExludeModules, ModuleTree};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+pub mod check;
+pub mod parse;
+pub mod report;
+
+pub use parse::DiagramError;
+
+/// The components of a diagram and the arrows between them.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PlantUmlDiagram {
+    pub path: PathBuf,
+    pub components: Vec<Component>,
+    /// Allowed dependencies, as indices into `components`.
+    pub arrows: BTreeSet<(usize, usize)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Component {
+    pub name: String,
+    pub alias: Option<String>,
+    /// Module patterns, see [`Modules::reside_in_a_module`](crate::rule::modules::ModuleConditionBuilder::reside_in_a_module).
+    pub stereotypes: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct PlantUmlRule {
+    pub(crate) filters: ExludeModules<'static>,
+    pub(crate) diagram: PathBuf,
+    pub(crate) rationale: Option<String>,
+}
+
+/// Module dependencies should be declared by the component diagram at `path`, relative paths
+/// are resolved against the directory of the crate manifest.
+pub fn adhere_to_plantuml_diagram<P: AsRef<Path>>(
+    filters: ExludeModules<'static>,
+    path: P,
+) -> PlantUmlRule {
+    PlantUmlRule {
+        filters,
+        diagram: path.as_ref().to_path_buf(),
+        rationale: None,
+    }
+}
+
+impl PlantUmlDiagram {
+    /// The components the module at `path` belongs to.
+    pub fn components_of(&self, path: &str) -> Vec<usize> {
+        self.components
+            .iter()
+            .enumerate()
+            .filter(|(_, component)| {
+                component
+                    .stereotypes
+                    .iter()
+                    .any(|pattern| PathPattern::from(pattern.as_str()).matches_module_path(path))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+impl PlantUmlRule {
+    /// Document why this rule exists, the rationale is displayed along with rule violations.
+    pub fn because(mut self, rationale: &str) -> Self {
+        self.rationale = Some(rationale.to_owned());
+        self
+    }
+
+    /// Evaluate the rule against the crate under test.
+    pub fn evaluate(&self) -> AssertionResult {
+        self.evaluate_in(module_tree())
+    }
+
+    /// Evaluate the rule and panic on violations, the report is configured via
+    /// [`ReportConfig::from_env`].
+    pub fn check(&self) {
+        let result = self.evaluate();
+        if !result.is_success() {
+            panic!("{}", result.render(&ReportConfig::from_env()))
+        }
+    }
+
+    /// Same as [`PlantUmlRule::evaluate`], against the given module tree. A diagram that cannot
+    /// be loaded or parsed gives a failed result.
+    pub fn evaluate_in(&self, tree: &'static ModuleTree) -> AssertionResult {
+        self.evaluate_tree(tree)
+    }
+}
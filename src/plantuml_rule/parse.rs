@@ -0,0 +1,223 @@
+use crate::assertion_result::get_relative_location;
+use crate::plantuml_rule::{Component, PlantUmlDiagram};
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use thiserror::Error;
+
+/// A PlantUML component diagram that could not be read.
+#[derive(Error, Debug, Diagnostic)]
+pub enum DiagramError {
+    #[error("Unable to read {path}")]
+    #[diagnostic(code(archunit::plantuml::io))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Invalid component diagram: {message}")]
+    #[diagnostic(
+        code(archunit::plantuml::parse),
+        help("Components are declared as `[Name] <<module pattern>> as alias` and depend on each other with `Name --> Other`")
+    )]
+    Parse {
+        message: String,
+        #[source_code]
+        src: NamedSource,
+        #[label("{message}")]
+        span: SourceSpan,
+    },
+}
+
+impl PlantUmlDiagram {
+    /// Load a component diagram from a `.puml` file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, DiagramError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|source| DiagramError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let mut diagram =
+            Self::parse(&content).map_err(|(message, offset, len)| DiagramError::Parse {
+                message,
+                src: NamedSource::new(get_relative_location(path), content.clone()),
+                span: (offset, len).into(),
+            })?;
+        diagram.path = path.to_path_buf();
+        Ok(diagram)
+    }
+
+    /// Parse the components and arrows of a diagram, on error returns the message along with
+    /// the offset and length of the faulty text.
+    pub(crate) fn parse(content: &str) -> Result<Self, (String, usize, usize)> {
+        let mut diagram = PlantUmlDiagram::default();
+        let mut arrows = vec![];
+        let mut offset = 0;
+
+        for line in content.lines() {
+            let line_offset = offset + line.len() - line.trim_start().len();
+            offset += line.len() + 1;
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('\'') || line.starts_with('@') {
+                continue;
+            }
+
+            let statement = line.strip_prefix("component ").unwrap_or(line).trim_start();
+            let Some((left, rest)) = operand(statement) else {
+                continue;
+            };
+
+            if let Some((arrow, rest)) = arrow(rest.trim_start()) {
+                let rest = rest.trim_start();
+                let Some((right, _label)) = operand(rest) else {
+                    return Err(("Missing arrow target".to_owned(), line_offset, line.len()));
+                };
+
+                let (from, to) = if arrow.starts_with('<') {
+                    (right, left)
+                } else {
+                    (left, right)
+                };
+                arrows.push((from.to_owned(), to.to_owned(), line_offset, line.len()));
+            } else if left.starts_with('[') {
+                diagram.components.push(
+                    component(left, rest).map_err(|message| (message, line_offset, line.len()))?,
+                );
+            }
+        }
+
+        for (from, to, offset, len) in arrows {
+            let from = diagram
+                .component_index(&from)
+                .ok_or_else(|| (format!("Unknown component {from}"), offset, len))?;
+            let to = diagram
+                .component_index(&to)
+                .ok_or_else(|| (format!("Unknown component {to}"), offset, len))?;
+            diagram.arrows.insert((from, to));
+        }
+
+        Ok(diagram)
+    }
+
+    fn component_index(&self, reference: &str) -> Option<usize> {
+        self.components.iter().position(|component| {
+            component.alias.as_deref() == Some(reference)
+                || reference
+                    .strip_prefix('[')
+                    .and_then(|name| name.strip_suffix(']'))
+                    .is_some_and(|name| name == component.name)
+        })
+    }
+}
+
+fn component(name: &str, rest: &str) -> Result<Component, String> {
+    let name = &name[1..name.len() - 1];
+    let mut stereotypes = vec![];
+    let mut rest = rest.trim_start();
+    while let Some(stereotype) = rest.strip_prefix("<<") {
+        let Some((stereotype, remaining)) = stereotype.split_once(">>") else {
+            return Err(format!("Unclosed stereotype for component [{name}]"));
+        };
+        stereotypes.push(stereotype.trim().to_owned());
+        rest = remaining.trim_start();
+    }
+
+    if stereotypes.is_empty() {
+        return Err(format!(
+            "Component [{name}] should map to modules with a stereotype"
+        ));
+    }
+
+    let alias = rest
+        .strip_prefix("as ")
+        .map(|alias| alias.trim().to_owned())
+        .filter(|alias| !alias.is_empty());
+
+    Ok(Component {
+        name: name.to_owned(),
+        alias,
+        stereotypes,
+    })
+}
+
+/// A `[Component Name]` or an alias at the start of `text`, and the remaining text.
+fn operand(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with('[') {
+        let end = text.find(']')?;
+        return Some(text.split_at(end + 1));
+    }
+
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .unwrap_or(text.len());
+    (end > 0).then(|| text.split_at(end))
+}
+
+/// An arrow like `-->`, `..>`, `<-` or `-[#red]->` at the start of `text`.
+fn arrow(text: &str) -> Option<(&str, &str)> {
+    let mut end = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '-' | '.' | '<' | '>' => end = idx + 1,
+            '[' if end > 0 => {
+                let close = text[idx..].find(']')?;
+                end = idx + close + 1;
+                while chars.peek().is_some_and(|(next, _)| *next < end) {
+                    chars.next();
+                }
+            }
+            _ => break,
+        }
+    }
+
+    let arrow = &text[..end];
+    let directed = arrow.starts_with('<') || arrow.ends_with('>');
+    let lines = arrow.contains('-') || arrow.contains('.');
+    (directed && lines).then(|| text.split_at(end))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::plantuml_rule::PlantUmlDiagram;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_parse_components_and_arrows() {
+        let diagram = PlantUmlDiagram::parse(
+            "@startuml
+' The application core
+[Domain] <<app::domain*>> as domain
+component [Adapters] <<app::infra*>> <<app::web*>>
+[Adapters] -[#blue]-> domain : uses
+domain <.. [Adapters]
+@enduml
+",
+        )
+        .expect("valid diagram");
+
+        assert_that!(diagram.components).has_length(2);
+        assert_that!(diagram.components[1].stereotypes)
+            .is_equal_to(vec!["app::infra*".to_owned(), "app::web*".to_owned()]);
+        assert_that!(diagram.components[0].alias).is_equal_to(Some("domain".to_owned()));
+        assert_that!(diagram.arrows.iter().copied().collect::<Vec<_>>()).is_equal_to(vec![(1, 0)]);
+    }
+
+    #[test]
+    fn should_reject_unknown_components() {
+        let error = PlantUmlDiagram::parse("[Domain] <<app::domain>>\n[Domain] --> infra\n")
+            .expect_err("unknown component");
+
+        assert_that!(error).is_equal_to(("Unknown component infra".to_owned(), 25, 18));
+    }
+
+    #[test]
+    fn should_require_stereotypes() {
+        let error = PlantUmlDiagram::parse("[Domain]\n").expect_err("missing stereotype");
+
+        assert_that!(error.0.as_str())
+            .is_equal_to("Component [Domain] should map to modules with a stereotype");
+    }
+}
@@ -0,0 +1,51 @@
+use crate::assertion_result::{get_code_sample_region, get_relative_location};
+use crate::ast::CodeSpan;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("Dependency of '{module}' on '{dependency}' is not declared in the diagram")]
+#[diagnostic(help(
+    "Add an arrow from [{from}] to [{to}] to the diagram, or remove the dependency"
+))]
+pub struct UndeclaredDependency {
+    module: String,
+    dependency: String,
+    from: String,
+    to: String,
+    #[source_code]
+    src: NamedSource,
+    #[label("Undeclared dependency")]
+    span: SourceSpan,
+}
+
+impl UndeclaredDependency {
+    pub fn from_span_and_location(
+        span: CodeSpan,
+        location: &Path,
+        module: String,
+        dependency: String,
+        from: &str,
+        to: &str,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let name = dependency.rsplit("::").next().unwrap_or(&dependency);
+        let span = sample
+            .find(name)
+            .map(|start| (start, name.len()))
+            .unwrap_or((0, sample.len()))
+            .into();
+
+        UndeclaredDependency {
+            module,
+            dependency,
+            from: from.to_owned(),
+            to: to.to_owned(),
+            src: NamedSource::new(get_relative_location(location), sample),
+            span,
+        }
+    }
+}
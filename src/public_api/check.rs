@@ -0,0 +1,387 @@
+use crate::assertion_result::AssertionResult;
+use crate::ast::enums::Enum;
+use crate::ast::impl_blocks::Impl;
+use crate::ast::structs::{Field, Struct};
+use crate::ast::{CodeSpan, ItemPath, LineColumn, Visibility};
+use crate::public_api::report::{
+    ApiAddition, ApiRemoval, MissingSnapshot, SnapshotIoError, SnapshotUpdated,
+};
+use crate::public_api::{update_snapshots, ApiEntry, ApiSnapshot, PublicApiRule};
+use crate::{ExludeModules, ModuleTree};
+use std::io;
+use std::path::{Path, PathBuf};
+
+impl PublicApiRule {
+    pub(crate) fn evaluate_tree(&self, tree: &ModuleTree) -> AssertionResult {
+        self.evaluate_snapshot(tree, update_snapshots())
+    }
+
+    /// Compare the API of `tree` against the snapshot, or record it when `update` is set.
+    fn evaluate_snapshot(&self, tree: &ModuleTree, update: bool) -> AssertionResult {
+        let mut result = AssertionResult::new();
+        result.push_expected(format!(
+            "Public API should match snapshot '{}'",
+            self.snapshot.display()
+        ));
+        result.rationale = self.rationale.clone();
+
+        let path = self.snapshot_path(tree);
+        let current = ApiSnapshot::of(tree, &self.filters);
+
+        // Updates fail the rule as well, so that they cannot go unnoticed on CI
+        if update {
+            match current.write(&path) {
+                Ok(()) => result.push_error(tree, SnapshotUpdated::new(&path)),
+                Err(err) => result.push_error(tree, SnapshotIoError::write(&path, err)),
+            }
+            return result;
+        }
+
+        let baseline = match ApiSnapshot::read(&path) {
+            Ok(baseline) => baseline,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                result.push_error(tree, MissingSnapshot::new(&path));
+                return result;
+            }
+            Err(err) => {
+                result.push_error(tree, SnapshotIoError::read(&path, err));
+                return result;
+            }
+        };
+
+        let diff = current.diff(&baseline);
+        for entry in diff.added {
+            let error = ApiAddition::from_entry(entry);
+            result.push_actual(&entry.module, &entry.location, entry.span, error);
+        }
+
+        for entry in diff.removed {
+            let error = ApiRemoval::from_entry(entry);
+            result.push_actual(&entry.module, &entry.location, entry.span, error);
+        }
+
+        result.success = result.actual.is_empty();
+        result.sort_violations();
+        result
+    }
+
+    fn snapshot_path(&self, tree: &ModuleTree) -> PathBuf {
+        match tree.manifest_dir() {
+            Some(dir) if self.snapshot.is_relative() => dir.join(&self.snapshot),
+            _ => self.snapshot.clone(),
+        }
+    }
+}
+
+impl ApiSnapshot {
+    pub(crate) fn parse(content: &str, location: &Path) -> Self {
+        let mut snapshot = ApiSnapshot::default();
+        for (idx, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let item = trimmed.split_whitespace().last().unwrap_or(trimmed);
+            let start = line.len() - line.trim_start().len();
+            let span = CodeSpan {
+                start: LineColumn::from((idx + 1, start)),
+                end: LineColumn::from((idx + 1, start + trimmed.len())),
+            };
+
+            snapshot.insert(ApiEntry {
+                line: trimmed.to_owned(),
+                module: ItemPath::new(item.to_owned()).parent(),
+                location: location.to_path_buf(),
+                span,
+                name: trimmed.to_owned(),
+            });
+        }
+
+        snapshot
+    }
+
+    pub(crate) fn collect(&mut self, tree: &ModuleTree, filters: &ExludeModules<'static>) {
+        let mut reachable = vec![];
+        reachable_modules(tree, filters, &mut reachable);
+
+        for module in &reachable {
+            if module.path != tree.path {
+                self.insert_module(module);
+            }
+
+            for struct_ in module
+                .structs
+                .iter()
+                .filter(|struct_| struct_.is_public())
+                .filter(|struct_| filters.is_enabled(struct_.cfg.as_ref()))
+            {
+                self.insert_struct(struct_);
+            }
+
+            for enum_ in module
+                .enums
+                .iter()
+                .filter(|enum_| enum_.is_public())
+                .filter(|enum_| filters.is_enabled(enum_.cfg.as_ref()))
+            {
+                self.insert_enum(enum_);
+            }
+        }
+
+        // Impl blocks of public types may live in private modules
+        let types = self
+            .entries
+            .values()
+            .filter(|entry| entry.line.starts_with("struct ") || entry.line.starts_with("enum "))
+            .map(|entry| {
+                entry
+                    .line
+                    .split_whitespace()
+                    .last()
+                    .unwrap_or_default()
+                    .to_owned()
+            })
+            .collect::<Vec<String>>();
+
+        let mut modules = vec![];
+        enabled_modules(tree, filters, &mut modules);
+        for module in modules {
+            for imp in module
+                .impl_blocks
+                .iter()
+                .filter(|imp| filters.is_enabled(imp.cfg.as_ref()))
+            {
+                if let Some(self_ty) = resolve_self_ty(tree, imp, &types) {
+                    self.insert_impl(module, imp, self_ty);
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, entry: ApiEntry) {
+        self.entries.insert(entry.line.clone(), entry);
+    }
+
+    fn insert_module(&mut self, module: &ModuleTree) {
+        let (location, span) = match &module.declaration {
+            Some(declaration) => (declaration.real_path.clone(), declaration.span),
+            None => (module.real_path.clone(), module.span.unwrap_or_default()),
+        };
+
+        self.insert(ApiEntry {
+            line: format!("mod {}", module.path),
+            module: module.path.parent(),
+            location,
+            span,
+            name: module.ident.clone(),
+        });
+    }
+
+    fn insert_struct(&mut self, struct_: &Struct) {
+        self.insert(ApiEntry {
+            line: format!("struct {}", struct_.path),
+            module: struct_.path.parent(),
+            location: struct_.real_path.clone(),
+            span: struct_.span,
+            name: struct_.ident.clone(),
+        });
+
+        let fields = struct_
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.visibility == Visibility::Public);
+
+        for (idx, field) in fields {
+            self.insert_field(
+                &struct_.path.parent(),
+                &struct_.path,
+                &struct_.real_path,
+                idx,
+                field,
+            );
+        }
+    }
+
+    fn insert_enum(&mut self, enum_: &Enum) {
+        self.insert(ApiEntry {
+            line: format!("enum {}", enum_.path),
+            module: enum_.path.parent(),
+            location: enum_.location.clone(),
+            span: enum_.span,
+            name: enum_.ident.clone(),
+        });
+
+        for variant in &enum_.variants {
+            let path = enum_.path.join(&variant.ident);
+            self.insert(ApiEntry {
+                line: format!("variant {path}"),
+                module: enum_.path.parent(),
+                location: enum_.location.clone(),
+                span: variant.span,
+                name: variant.ident.clone(),
+            });
+
+            // Variant fields share the visibility of the enum
+            for (idx, field) in variant.fields.iter().enumerate() {
+                self.insert_field(&enum_.path.parent(), &path, &enum_.location, idx, field);
+            }
+        }
+    }
+
+    fn insert_field(
+        &mut self,
+        module: &ItemPath,
+        parent: &ItemPath,
+        location: &Path,
+        idx: usize,
+        field: &Field,
+    ) {
+        let name = field.name.clone().unwrap_or_else(|| idx.to_string());
+        self.insert(ApiEntry {
+            line: format!("field {}", parent.join(&name)),
+            module: module.clone(),
+            location: location.to_path_buf(),
+            span: field.span,
+            name: field.name.clone().unwrap_or_default(),
+        });
+    }
+
+    fn insert_impl(&mut self, module: &ModuleTree, imp: &Impl, self_ty: &str) {
+        let unsafety = if imp.is_unsafe { "unsafe " } else { "" };
+        let line = match &imp.trait_impl {
+            Some(trait_) => format!("{unsafety}impl {trait_} for {self_ty}"),
+            None => format!("{unsafety}impl {self_ty}"),
+        };
+
+        self.insert(ApiEntry {
+            line,
+            module: module.path.clone(),
+            location: module.real_path.clone(),
+            span: imp.span,
+            name: imp.self_ty.name().to_owned(),
+        });
+    }
+}
+
+/// Modules reachable from outside the crate, the crate root and the public modules declared
+/// in reachable modules.
+fn reachable_modules<'a>(
+    module: &'a ModuleTree,
+    filters: &ExludeModules<'static>,
+    modules: &mut Vec<&'a ModuleTree>,
+) {
+    modules.push(module);
+    module
+        .submodules
+        .iter()
+        .filter(|submodule| submodule.is_public())
+        .filter(filters.filter())
+        .for_each(|submodule| reachable_modules(submodule, filters, modules));
+}
+
+fn enabled_modules<'a>(
+    module: &'a ModuleTree,
+    filters: &ExludeModules<'static>,
+    modules: &mut Vec<&'a ModuleTree>,
+) {
+    modules.push(module);
+    module
+        .submodules
+        .iter()
+        .filter(filters.filter())
+        .for_each(|submodule| enabled_modules(submodule, filters, modules));
+}
+
+/// Resolve the implemented type of `imp` to one of the public `types`, relative to the module
+/// of the impl block, or by its simple name when the type is imported there.
+fn resolve_self_ty<'a>(tree: &ModuleTree, imp: &Impl, types: &'a [String]) -> Option<&'a str> {
+    let mut resolved = imp.path.clone();
+    let mut segments = vec![];
+    for segment in imp.self_ty.as_str().split("::") {
+        match segment {
+            "crate" => resolved = tree.path.clone(),
+            "self" => {}
+            "super" => resolved = resolved.parent(),
+            segment => segments.push(segment),
+        }
+    }
+
+    let resolved = segments
+        .iter()
+        .fold(resolved, |resolved, segment| resolved.join(segment));
+
+    if let Some(self_ty) = types.iter().find(|ty| *ty == resolved.as_str()) {
+        return Some(self_ty);
+    }
+
+    let mut candidates = types
+        .iter()
+        .filter(|ty| ItemPath::new(ty.to_string()).name() == imp.self_ty.name());
+
+    match (candidates.next(), candidates.next()) {
+        (Some(self_ty), None) => Some(self_ty),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::module_tree;
+    use crate::public_api::{ApiSnapshot, PublicApi};
+    use crate::ExludeModules;
+    use speculoos::prelude::*;
+    use std::env;
+    use std::path::Path;
+
+    #[test]
+    fn should_parse_snapshot_lines() {
+        let snapshot = ApiSnapshot::parse(
+            "# public API\nstruct foo::Bar\n\n  impl Display for foo::Bar\n",
+            Path::new("api.txt"),
+        );
+
+        assert_that!(snapshot.lines().collect::<Vec<&str>>())
+            .is_equal_to(vec!["impl Display for foo::Bar", "struct foo::Bar"]);
+
+        let entry = snapshot.entries().next().expect("entry");
+        assert_that!(entry.module.as_str()).is_equal_to("foo");
+        assert_that!(entry.span.start().line).is_equal_to(4);
+        assert_that!(entry.span.start().column).is_equal_to(2);
+    }
+
+    #[test]
+    fn should_diff_snapshots() {
+        let baseline = ApiSnapshot::parse("struct foo::Bar\nstruct foo::Baz\n", Path::new("a"));
+        let current = ApiSnapshot::parse("struct foo::Bar\nenum foo::Qux\n", Path::new("b"));
+
+        let diff = current.diff(&baseline);
+
+        assert_that!(diff
+            .added
+            .iter()
+            .map(|entry| entry.line.as_str())
+            .collect::<Vec<_>>())
+        .is_equal_to(vec!["enum foo::Qux"]);
+        assert_that!(diff
+            .removed
+            .iter()
+            .map(|entry| entry.line.as_str())
+            .collect::<Vec<_>>())
+        .is_equal_to(vec!["struct foo::Baz"]);
+    }
+
+    #[test]
+    fn should_fail_when_updating_snapshot() {
+        let path = env::temp_dir().join("archunit_rs_public_api.txt");
+        let rule = PublicApi::should_match_snapshot(ExludeModules::cfg_test(), &path);
+
+        let updated = rule.evaluate_snapshot(module_tree(), true);
+        let checked = rule.evaluate_snapshot(module_tree(), false);
+
+        assert_that!(updated.is_success()).is_false();
+        assert_that!(updated.actual[0].message()).ends_with("was updated");
+        assert_that!(checked.is_success()).is_true();
+    }
+}
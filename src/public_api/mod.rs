@@ -0,0 +1,196 @@
+//! Snapshots of the public API of a crate, to catch accidental changes in review.
+//!
+//! The snapshot lists the items reachable from outside the crate: public modules declared in
+//! public modules, and the public structs, enums, fields, enum variants and impl blocks they
+//! contain. Each item is rendered on its own line, sorted, so that the committed snapshot
+//! diffs nicely. Re-exports are not followed.
+//!
+//! ```text
+//! mod my_crate::shapes
+//! struct my_crate::shapes::Circle
+//! field my_crate::shapes::Circle::radius
+//! enum my_crate::shapes::Shape
+//! variant my_crate::shapes::Shape::Circle
+//! field my_crate::shapes::Shape::Circle::0
+//! impl Display for my_crate::shapes::Shape
+//! ```
+//!
+//! **Example:**
+//! ```rust,no_run
+//! use archunit_rs::public_api::PublicApi;
+//! use archunit_rs::ExludeModules;
+//!
+//! PublicApi::should_match_snapshot(ExludeModules::cfg_test(), "tests/public_api.txt").check();
+//! ```
+//!
+//! Run the rule with `ARCHUNIT_UPDATE_SNAPSHOTS=1` to record the current API in the snapshot
+//! file instead of checking it, the rule then fails with a reminder to commit the snapshot.
+use crate::assertion_result::AssertionResult;
+use crate::ast::{module_tree, CodeSpan, ItemPath};
+use crate::report::text::ReportConfig;
+use crate::{ExludeModules, ModuleTree};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Formatter;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+pub mod check;
+pub mod report;
+
+/// Environment variable enabling snapshot updates, set it to `1` or `true`.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "ARCHUNIT_UPDATE_SNAPSHOTS";
+
+/// Entry point for public API rules.
+#[derive(Debug)]
+pub struct PublicApi;
+
+/// The public API of a crate, one line per item.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ApiSnapshot {
+    entries: BTreeMap<String, ApiEntry>,
+}
+
+/// An item of an [`ApiSnapshot`] along with where it is declared.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ApiEntry {
+    pub line: String,
+    /// The enclosing module, or the enclosing type for the fields and variants read from a
+    /// snapshot file.
+    pub module: ItemPath,
+    pub location: PathBuf,
+    pub span: CodeSpan,
+    pub(crate) name: String,
+}
+
+/// Items added and removed between two snapshots.
+#[derive(Debug)]
+pub struct ApiDiff<'a> {
+    pub added: Vec<&'a ApiEntry>,
+    pub removed: Vec<&'a ApiEntry>,
+}
+
+#[derive(Debug)]
+pub struct PublicApiRule {
+    pub(crate) filters: ExludeModules<'static>,
+    pub(crate) snapshot: PathBuf,
+    pub(crate) rationale: Option<String>,
+}
+
+impl PublicApi {
+    /// The public API of the crate under test.
+    pub fn snapshot(filters: &ExludeModules<'static>) -> ApiSnapshot {
+        ApiSnapshot::of(module_tree(), filters)
+    }
+
+    /// Compare the public API against the snapshot committed at `path`, relative paths are
+    /// resolved against the directory of the crate manifest.
+    pub fn should_match_snapshot<P: AsRef<Path>>(
+        filters: ExludeModules<'static>,
+        path: P,
+    ) -> PublicApiRule {
+        PublicApiRule {
+            filters,
+            snapshot: path.as_ref().to_path_buf(),
+            rationale: None,
+        }
+    }
+}
+
+impl ApiSnapshot {
+    /// The public API of the given module tree.
+    pub fn of(tree: &ModuleTree, filters: &ExludeModules<'static>) -> Self {
+        let mut snapshot = ApiSnapshot::default();
+        snapshot.collect(tree, filters);
+        snapshot
+    }
+
+    /// Read a snapshot file, blank lines and lines starting with `#` are ignored.
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        Ok(Self::parse(&content, path))
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ApiEntry> {
+        self.entries.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Items of this snapshot missing from `baseline`, and items of `baseline` missing from
+    /// this snapshot.
+    pub fn diff<'a>(&'a self, baseline: &'a ApiSnapshot) -> ApiDiff<'a> {
+        ApiDiff {
+            added: self
+                .entries
+                .iter()
+                .filter(|(line, _)| !baseline.entries.contains_key(*line))
+                .map(|(_, entry)| entry)
+                .collect(),
+            removed: baseline
+                .entries
+                .iter()
+                .filter(|(line, _)| !self.entries.contains_key(*line))
+                .map(|(_, entry)| entry)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for ApiSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for line in self.lines() {
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ApiDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl PublicApiRule {
+    /// Document why this rule exists, the rationale is displayed along with rule violations.
+    pub fn because(mut self, rationale: &str) -> Self {
+        self.rationale = Some(rationale.to_owned());
+        self
+    }
+
+    /// Evaluate the rule against the crate under test.
+    pub fn evaluate(&self) -> AssertionResult {
+        self.evaluate_in(module_tree())
+    }
+
+    /// Evaluate the rule and panic on violations, the report is configured via
+    /// [`ReportConfig::from_env`].
+    pub fn check(&self) {
+        let result = self.evaluate();
+        if !result.is_success() {
+            panic!("{}", result.render(&ReportConfig::from_env()))
+        }
+    }
+
+    /// Same as [`PublicApiRule::evaluate`], against the given module tree.
+    pub fn evaluate_in(&self, tree: &ModuleTree) -> AssertionResult {
+        self.evaluate_tree(tree)
+    }
+}
+
+pub(crate) fn update_snapshots() -> bool {
+    std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok_and(|value| value == "1" || value == "true")
+}
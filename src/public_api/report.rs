@@ -0,0 +1,132 @@
+use crate::assertion_result::{get_code_sample_region, get_relative_location};
+use crate::public_api::ApiEntry;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use std::path::Path;
+use std::{fs, io};
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("'{item}' was added to the public API")]
+#[diagnostic(help(
+    "Restrict its visibility, or record the change with ARCHUNIT_UPDATE_SNAPSHOTS=1 if it is intended"
+))]
+pub struct ApiAddition {
+    item: String,
+    #[source_code]
+    src: NamedSource,
+    #[label("Not in the API snapshot")]
+    span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("'{item}' was removed from the public API")]
+#[diagnostic(help(
+    "Restore it, or record the change with ARCHUNIT_UPDATE_SNAPSHOTS=1 if it is intended"
+))]
+pub struct ApiRemoval {
+    item: String,
+    #[source_code]
+    src: NamedSource,
+    #[label("Removed item")]
+    span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("API snapshot '{path}' does not exist")]
+#[diagnostic(help(
+    "Record the current API with ARCHUNIT_UPDATE_SNAPSHOTS=1 cargo test, then commit the snapshot"
+))]
+pub struct MissingSnapshot {
+    path: String,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("Unable to {action} API snapshot '{path}'")]
+pub struct SnapshotIoError {
+    action: &'static str,
+    path: String,
+    #[source]
+    source: io::Error,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("API snapshot '{path}' was updated")]
+#[diagnostic(
+    severity(Warning),
+    help("Review and commit the snapshot, then run the tests without ARCHUNIT_UPDATE_SNAPSHOTS")
+)]
+pub struct SnapshotUpdated {
+    path: String,
+}
+
+impl MissingSnapshot {
+    pub(crate) fn new(path: &Path) -> Self {
+        MissingSnapshot {
+            path: get_relative_location(path),
+        }
+    }
+}
+
+impl SnapshotIoError {
+    pub(crate) fn read(path: &Path, source: io::Error) -> Self {
+        SnapshotIoError {
+            action: "read",
+            path: get_relative_location(path),
+            source,
+        }
+    }
+
+    pub(crate) fn write(path: &Path, source: io::Error) -> Self {
+        SnapshotIoError {
+            action: "write",
+            path: get_relative_location(path),
+            source,
+        }
+    }
+}
+
+impl SnapshotUpdated {
+    pub(crate) fn new(path: &Path) -> Self {
+        SnapshotUpdated {
+            path: get_relative_location(path),
+        }
+    }
+}
+
+impl ApiAddition {
+    pub fn from_entry(entry: &ApiEntry) -> Self {
+        let (src, span) = highlight(entry);
+        ApiAddition {
+            item: entry.line.clone(),
+            src,
+            span,
+        }
+    }
+}
+
+impl ApiRemoval {
+    pub fn from_entry(entry: &ApiEntry) -> Self {
+        let (src, span) = highlight(entry);
+        ApiRemoval {
+            item: entry.line.clone(),
+            src,
+            span,
+        }
+    }
+}
+
+fn highlight(entry: &ApiEntry) -> (NamedSource, SourceSpan) {
+    let sample = fs::read_to_string(&entry.location).unwrap_or_default();
+    let sample = get_code_sample_region(&sample, &entry.span);
+    let span = sample
+        .find(&entry.name)
+        .filter(|_| !entry.name.is_empty())
+        .map(|start| (start, entry.name.len()))
+        .unwrap_or((0, sample.len()))
+        .into();
+
+    (
+        NamedSource::new(get_relative_location(&entry.location), sample),
+        span,
+    )
+}
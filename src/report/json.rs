@@ -0,0 +1,138 @@
+use crate::assertion_result::{AssertionResult, Violation};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A JSON report listing every evaluated rule along with its violations.
+///
+/// **Example:**
+/// ```rust
+/// use archunit_rs::ExludeModules;
+/// use archunit_rs::report::json::JsonReport;
+/// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+/// use archunit_rs::rule::structs::Structs;
+///
+/// let result = Structs::that(ExludeModules::default())
+///     .have_simple_name("AssertionResult")
+///     .should()
+///     .be_public()
+///     .evaluate();
+///
+/// let mut report = JsonReport::new();
+/// report.add_rule_set("visibility", &[result]);
+/// let _json = report.to_json();
+/// ```
+#[derive(Debug, Default, Serialize)]
+pub struct JsonReport {
+    rules: Vec<JsonRule>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRule {
+    id: String,
+    rule_set: String,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rationale: Option<String>,
+    success: bool,
+    violations: Vec<JsonViolation>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonViolation {
+    file: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    module: String,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<String>,
+}
+
+impl JsonReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the results of the given rule set.
+    pub fn add_rule_set(&mut self, rule_set: &str, results: &[AssertionResult]) -> &mut Self {
+        self.rules
+            .extend(results.iter().map(|result| JsonRule::new(rule_set, result)));
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("JSON report should serialize")
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+impl JsonRule {
+    fn new(rule_set: &str, result: &AssertionResult) -> Self {
+        JsonRule {
+            id: result.rule_id(),
+            rule_set: rule_set.to_owned(),
+            description: result.expected.clone(),
+            rationale: result.rationale.clone(),
+            success: result.is_success(),
+            violations: result.actual.iter().map(JsonViolation::from).collect(),
+        }
+    }
+}
+
+impl From<&Violation> for JsonViolation {
+    fn from(violation: &Violation) -> Self {
+        // Columns are 1-based, as displayed by editors and compact reports
+        let start = violation.span.start();
+        let end = violation.span.end();
+
+        JsonViolation {
+            file: violation.relative_location(),
+            line: start.line,
+            column: start.column + 1,
+            end_line: end.line,
+            end_column: end.column + 1,
+            module: violation.module.to_string(),
+            message: violation.message(),
+            help: violation.help(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::report::json::JsonReport;
+    use crate::rule::enums::Enums;
+    use crate::rule::{ArchRuleBuilder, CheckRule};
+    use crate::ExludeModules;
+    use serde_json::Value;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_list_rules_and_violations() {
+        let failing = Enums::that(ExludeModules::default())
+            .have_simple_name("ModuleRuleViolation")
+            .should()
+            .derive("Deserialize")
+            .evaluate();
+
+        let mut report = JsonReport::new();
+        report.add_rule_set("enums", &[failing]);
+        let json: Value = serde_json::from_str(&report.to_json()).expect("valid json");
+
+        let rule = &json["rules"][0];
+        assert_that!(rule["rule_set"]).is_equal_to(Value::from("enums"));
+        assert_that!(rule["success"]).is_equal_to(Value::from(false));
+        let violation = &rule["violations"][0];
+        assert_that!(violation["file"]).is_equal_to(Value::from("src/rule/modules/report.rs"));
+        assert_that!(violation["line"]).is_equal_to(Value::from(8));
+        assert_that!(violation["module"])
+            .is_equal_to(Value::from("archunit_rs::rule::modules::report"));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::assertion_result::AssertionResult;
+use crate::report::text::compact_line;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A JUnit XML report, each evaluated rule is written as a `<testcase>` and each rule set
+/// as a `<testsuite>`.
+///
+/// **Example:**
+/// ```rust
+/// use archunit_rs::ExludeModules;
+/// use archunit_rs::report::junit::JUnitReport;
+/// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+/// use archunit_rs::rule::structs::Structs;
+///
+/// let result = Structs::that(ExludeModules::default())
+///     .have_simple_name("AssertionResult")
+///     .should()
+///     .be_public()
+///     .evaluate();
+///
+/// let mut report = JUnitReport::new();
+/// report.add_suite("visibility", &[result]);
+/// let _xml = report.to_xml();
+/// ```
+#[derive(Debug, Default)]
+pub struct JUnitReport {
+    suites: Vec<TestSuite>,
+}
+
+#[derive(Debug)]
+struct TestSuite {
+    name: String,
+    cases: Vec<TestCase>,
+}
+
+#[derive(Debug)]
+struct TestCase {
+    name: String,
+    failure: Option<Failure>,
+}
+
+#[derive(Debug)]
+struct Failure {
+    message: String,
+    body: String,
+}
+
+impl JUnitReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a test suite for the given rule set.
+    pub fn add_suite(&mut self, rule_set: &str, results: &[AssertionResult]) -> &mut Self {
+        let cases = results.iter().map(TestCase::from).collect();
+        self.suites.push(TestSuite {
+            name: rule_set.to_owned(),
+            cases,
+        });
+        self
+    }
+
+    pub fn to_xml(&self) -> String {
+        let tests: usize = self.suites.iter().map(|suite| suite.cases.len()).sum();
+        let failures: usize = self.suites.iter().map(TestSuite::failures).sum();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"archunit\" tests=\"{tests}\" failures=\"{failures}\">"
+        );
+
+        for suite in &self.suites {
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+                escape(&suite.name),
+                suite.cases.len(),
+                suite.failures()
+            );
+
+            for case in &suite.cases {
+                let _ = write!(
+                    xml,
+                    "    <testcase classname=\"{}\" name=\"{}\"",
+                    escape(&suite.name),
+                    escape(&case.name)
+                );
+
+                match &case.failure {
+                    None => xml.push_str("/>\n"),
+                    Some(failure) => {
+                        let _ = writeln!(
+                            xml,
+                            ">\n      <failure type=\"ArchRuleViolation\" message=\"{}\">{}</failure>\n    </testcase>",
+                            escape(&failure.message),
+                            escape(&failure.body)
+                        );
+                    }
+                }
+            }
+
+            xml.push_str("  </testsuite>\n");
+        }
+
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_xml())
+    }
+}
+
+impl TestSuite {
+    fn failures(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.failure.is_some())
+            .count()
+    }
+}
+
+impl From<&AssertionResult> for TestCase {
+    fn from(result: &AssertionResult) -> Self {
+        let failure = (!result.is_success()).then(|| {
+            let mut body = String::new();
+            if let Some(rationale) = &result.rationale {
+                let _ = writeln!(body, "Because {rationale}");
+            }
+
+            for violation in &result.actual {
+                let _ = writeln!(body, "{}", compact_line(violation));
+            }
+
+            Failure {
+                message: format!(
+                    "Expected {} but found {} violations",
+                    result.expected,
+                    result.actual.len()
+                ),
+                body,
+            }
+        });
+
+        TestCase {
+            name: result.expected.clone(),
+            failure,
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use crate::report::junit::JUnitReport;
+    use crate::rule::enums::Enums;
+    use crate::rule::{ArchRuleBuilder, CheckRule};
+    use crate::ExludeModules;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_write_one_testcase_per_rule() {
+        let failing = Enums::that(ExludeModules::default())
+            .have_simple_name("ModuleRuleViolation")
+            .should()
+            .derive("Deserialize")
+            .evaluate();
+
+        let passing = Enums::that(ExludeModules::default())
+            .have_simple_name("Visibility")
+            .should()
+            .derive("Debug")
+            .evaluate();
+
+        let mut report = JUnitReport::new();
+        report.add_suite("enums", &[failing, passing]);
+        let xml = report.to_xml();
+
+        assert_that!(xml).contains("<testsuites name=\"archunit\" tests=\"2\" failures=\"1\">");
+        assert_that!(xml).contains(
+            "<testcase classname=\"enums\" name=\"Structs that have simple name &apos;Visibility&apos; to derive &apos;Debug&apos;\"/>",
+        );
+        assert_that!(xml).contains("src/rule/modules/report.rs:8:1: Enum &apos;ModuleRuleViolation&apos; should derive &apos;Deserialize&apos;");
+    }
+}
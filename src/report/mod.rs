@@ -0,0 +1,10 @@
+//! Text and machine-readable outputs for evaluated rules.
+//!
+//! Rules are evaluated with [`CheckRule::evaluate`](crate::rule::CheckRule::evaluate) instead of
+//! [`CheckRule::check`](crate::rule::CheckRule::check), the resulting
+//! [`AssertionResult`](crate::assertion_result::AssertionResult)s can then be fed to a reporter.
+
+pub mod json;
+pub mod junit;
+pub mod sarif;
+pub mod text;
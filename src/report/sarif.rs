@@ -0,0 +1,289 @@
+use crate::assertion_result::{AssertionResult, Violation};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// A [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log
+/// containing one run per evaluated rule set.
+///
+/// **Example:**
+/// ```rust
+/// use archunit_rs::ExludeModules;
+/// use archunit_rs::report::sarif::SarifReport;
+/// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+/// use archunit_rs::rule::structs::Structs;
+///
+/// let result = Structs::that(ExludeModules::default())
+///     .have_simple_name("AssertionResult")
+///     .should()
+///     .be_public()
+///     .because("it is part of the public API")
+///     .evaluate();
+///
+/// let mut report = SarifReport::new();
+/// report.add_run("visibility", &[result]);
+/// let _json = report.to_json();
+/// ```
+#[derive(Debug, Default)]
+pub struct SarifReport {
+    runs: Vec<Run>,
+}
+
+impl SarifReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a run for the given rule set.
+    pub fn add_run(&mut self, rule_set: &str, results: &[AssertionResult]) -> &mut Self {
+        let rules = results.iter().map(ReportingDescriptor::from).collect();
+        let results = results
+            .iter()
+            .enumerate()
+            .filter(|(_, result)| !result.is_success())
+            .flat_map(|(idx, result)| {
+                result
+                    .actual
+                    .iter()
+                    .map(move |violation| SarifResult::new(idx, result, violation))
+            })
+            .collect();
+
+        self.runs.push(Run {
+            tool: Tool {
+                driver: Driver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: "https://github.com/oknozor/archunit_rs",
+                    rules,
+                },
+            },
+            automation_details: AutomationDetails {
+                id: format!("{rule_set}/"),
+            },
+            results,
+        });
+
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: &self.runs,
+        })
+        .expect("SARIF log should serialize")
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: &'a [Run],
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    automation_details: AutomationDetails,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Debug, Serialize)]
+struct AutomationDetails {
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: String,
+    short_description: Message,
+    full_description: Message,
+    help: Message,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<RuleProperties>,
+}
+
+#[derive(Debug, Serialize)]
+struct RuleProperties {
+    rationale: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl From<&AssertionResult> for ReportingDescriptor {
+    fn from(result: &AssertionResult) -> Self {
+        let full_description = match &result.rationale {
+            Some(rationale) => format!("{}, because {rationale}", result.expected),
+            None => result.expected.clone(),
+        };
+
+        let help = result
+            .actual
+            .iter()
+            .find_map(Violation::help)
+            .unwrap_or_else(|| result.expected.clone());
+
+        ReportingDescriptor {
+            id: result.rule_id(),
+            short_description: Message {
+                text: result.expected.clone(),
+            },
+            full_description: Message {
+                text: full_description,
+            },
+            help: Message { text: help },
+            properties: result.rationale.as_ref().map(|rationale| RuleProperties {
+                rationale: rationale.clone(),
+            }),
+        }
+    }
+}
+
+impl SarifResult {
+    fn new(rule_index: usize, result: &AssertionResult, violation: &Violation) -> Self {
+        let text = match violation.help() {
+            Some(help) => format!("{}\n{help}", violation.message()),
+            None => violation.message(),
+        };
+
+        // SARIF columns are 1-based while proc-macro2 columns are 0-based
+        let start = violation.span.start();
+        let end = violation.span.end();
+
+        SarifResult {
+            rule_id: result.rule_id(),
+            rule_index,
+            level: "error",
+            message: Message { text },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: violation.relative_location(),
+                    },
+                    region: Region {
+                        start_line: start.line,
+                        start_column: start.column + 1,
+                        end_line: end.line,
+                        end_column: end.column + 1,
+                    },
+                },
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::report::sarif::SarifReport;
+    use crate::rule::enums::Enums;
+    use crate::rule::{ArchRuleBuilder, CheckRule};
+    use crate::ExludeModules;
+    use serde_json::Value;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_emit_sarif_results_with_locations() {
+        let failing = Enums::that(ExludeModules::default())
+            .have_simple_name("ModuleRuleViolation")
+            .should()
+            .derive("Deserialize")
+            .because("violations are sent over the wire")
+            .evaluate();
+
+        let passing = Enums::that(ExludeModules::default())
+            .have_simple_name("Visibility")
+            .should()
+            .derive("Debug")
+            .evaluate();
+
+        let mut report = SarifReport::new();
+        report.add_run("enums", &[failing, passing]);
+        let sarif: Value = serde_json::from_str(&report.to_json()).expect("valid json");
+
+        assert_that!(sarif["version"]).is_equal_to(Value::from("2.1.0"));
+        let run = &sarif["runs"][0];
+        assert_that!(run["automationDetails"]["id"]).is_equal_to(Value::from("enums/"));
+
+        let rules = run["tool"]["driver"]["rules"].as_array().expect("rules");
+        assert_that!(rules).has_length(2);
+        assert_that!(rules[0]["properties"]["rationale"])
+            .is_equal_to(Value::from("violations are sent over the wire"));
+
+        let results = run["results"].as_array().expect("results");
+        assert_that!(results).has_length(1);
+        let result = &results[0];
+        assert_that!(result["ruleId"]).is_equal_to(&rules[0]["id"]);
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_that!(location["artifactLocation"]["uri"])
+            .is_equal_to(Value::from("src/rule/modules/report.rs"));
+        assert_that!(location["region"]["startLine"]).is_equal_to(Value::from(8));
+    }
+}
@@ -0,0 +1,257 @@
+use crate::assertion_result::{AssertionResult, Violation};
+use miette::{GraphicalReportHandler, GraphicalTheme};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Environment variable used to select the [`ReportFormat`] of panicking rules.
+pub const REPORT_FORMAT_ENV: &str = "ARCHUNIT_REPORT_FORMAT";
+/// Environment variable used to cap the number of violations rendered per rule.
+pub const MAX_VIOLATIONS_ENV: &str = "ARCHUNIT_MAX_VIOLATIONS";
+
+/// How violations are rendered in text reports.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// miette graphical reports with code samples and ANSI colors.
+    #[default]
+    Fancy,
+    /// miette graphical reports, ASCII only and without colors.
+    Plain,
+    /// One `file:line:column: message` line per violation.
+    Compact,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "fancy" => Ok(ReportFormat::Fancy),
+            "plain" => Ok(ReportFormat::Plain),
+            "compact" => Ok(ReportFormat::Compact),
+            other => Err(format!(
+                "Unknown report format '{other}', expected one of 'fancy', 'plain' or 'compact'"
+            )),
+        }
+    }
+}
+
+/// Control how [`AssertionResult`]s are rendered when a rule fails.
+///
+/// **Example:**
+/// ```rust
+/// use archunit_rs::ExludeModules;
+/// use archunit_rs::report::text::ReportConfig;
+/// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+/// use archunit_rs::rule::structs::Structs;
+///
+/// Structs::that(ExludeModules::default())
+///     .have_simple_name("AssertionResult")
+///     .should()
+///     .be_public()
+///     .check_with(&ReportConfig::compact().with_max_violations(10));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportConfig {
+    pub format: ReportFormat,
+    pub max_violations: Option<usize>,
+    pub summary: bool,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        ReportConfig {
+            format: ReportFormat::Fancy,
+            max_violations: None,
+            summary: true,
+        }
+    }
+}
+
+impl ReportConfig {
+    pub fn fancy() -> Self {
+        Self::default()
+    }
+
+    pub fn plain() -> Self {
+        ReportConfig {
+            format: ReportFormat::Plain,
+            ..Self::default()
+        }
+    }
+
+    pub fn compact() -> Self {
+        ReportConfig {
+            format: ReportFormat::Compact,
+            ..Self::default()
+        }
+    }
+
+    /// Render at most `max` violations per rule.
+    pub fn with_max_violations(mut self, max: usize) -> Self {
+        self.max_violations = Some(max);
+        self
+    }
+
+    /// Do not render the summary footer.
+    pub fn without_summary(mut self) -> Self {
+        self.summary = false;
+        self
+    }
+
+    /// Read the configuration from `ARCHUNIT_REPORT_FORMAT` and `ARCHUNIT_MAX_VIOLATIONS`,
+    /// falling back to the default fancy report.
+    pub fn from_env() -> Self {
+        let mut config = ReportConfig::default();
+
+        if let Ok(format) = env::var(REPORT_FORMAT_ENV) {
+            match format.parse() {
+                Ok(format) => config.format = format,
+                Err(err) => eprintln!("{err}"),
+            }
+        }
+
+        if let Ok(max) = env::var(MAX_VIOLATIONS_ENV) {
+            match max.parse() {
+                Ok(max) => config.max_violations = Some(max),
+                Err(_) => eprintln!("Invalid value '{max}' for {MAX_VIOLATIONS_ENV}"),
+            }
+        }
+
+        config
+    }
+}
+
+impl AssertionResult {
+    /// Render this result as text according to the given [`ReportConfig`].
+    pub fn render(&self, config: &ReportConfig) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Expected {} but found {} violations",
+            self.expected,
+            self.actual.len()
+        );
+
+        if let Some(rationale) = &self.rationale {
+            let _ = writeln!(out, "Because {rationale}");
+        }
+
+        let max = config.max_violations.unwrap_or(self.actual.len());
+        for violation in self.actual.iter().take(max) {
+            match config.format {
+                ReportFormat::Fancy => {
+                    let _ = writeln!(out, "{:?}", violation.report);
+                }
+                ReportFormat::Plain => {
+                    let handler = GraphicalReportHandler::new_themed(GraphicalTheme::none());
+                    let _ = handler.render_report(&mut out, violation.report.as_ref());
+                    out.push('\n');
+                }
+                ReportFormat::Compact => {
+                    let _ = writeln!(out, "{}", compact_line(violation));
+                }
+            }
+        }
+
+        if self.actual.len() > max {
+            let _ = writeln!(out, "... and {} more violations", self.actual.len() - max);
+        }
+
+        if config.summary && !self.actual.is_empty() {
+            out.push_str(&self.summary());
+        }
+
+        out
+    }
+
+    fn summary(&self) -> String {
+        let mut per_file: BTreeMap<String, usize> = BTreeMap::new();
+        let mut per_module: BTreeMap<String, usize> = BTreeMap::new();
+        for violation in &self.actual {
+            *per_file.entry(violation.relative_location()).or_default() += 1;
+            *per_module.entry(violation.module.to_string()).or_default() += 1;
+        }
+
+        let mut summary = format!("Summary: {} violations\n", self.actual.len());
+        summary.push_str("  per file:\n");
+        for (file, count) in per_file {
+            let _ = writeln!(summary, "    {file}: {count}");
+        }
+
+        summary.push_str("  per module:\n");
+        for (module, count) in per_module {
+            let _ = writeln!(summary, "    {module}: {count}");
+        }
+
+        summary
+    }
+}
+
+/// A single line `file:line:column: message (help)` representation of a violation.
+pub(crate) fn compact_line(violation: &Violation) -> String {
+    let start = violation.span.start();
+    let mut line = format!(
+        "{}:{}:{}: {}",
+        violation.relative_location(),
+        start.line,
+        start.column + 1,
+        violation.message()
+    );
+
+    if let Some(help) = violation.help() {
+        let _ = write!(line, " ({help})");
+    }
+
+    line
+}
+
+#[cfg(test)]
+mod test {
+    use crate::report::text::{ReportConfig, ReportFormat};
+    use crate::rule::structs::Structs;
+    use crate::rule::{ArchRuleBuilder, CheckRule};
+    use crate::ExludeModules;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_parse_report_format() {
+        assert_that!("compact".parse::<ReportFormat>()).is_ok_containing(ReportFormat::Compact);
+        assert_that!("PLAIN".parse::<ReportFormat>()).is_ok_containing(ReportFormat::Plain);
+        assert_that!("html".parse::<ReportFormat>()).is_err();
+    }
+
+    #[test]
+    fn should_render_compact_report_with_cap_and_summary() {
+        let result = Structs::that(ExludeModules::default())
+            .reside_in_a_module("archunit_rs::report::*")
+            .should()
+            .be_private()
+            .evaluate();
+
+        let report = result.render(&ReportConfig::compact().with_max_violations(1));
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_that!(lines[1]).starts_with("src/report/");
+        assert_that!(lines[2]).starts_with("... and ");
+        assert_that!(report).contains("Summary: ");
+        assert_that!(report).contains("  per module:\n    archunit_rs::report::");
+        assert_that!(report).does_not_contain("\u{1b}[");
+    }
+
+    #[test]
+    fn should_render_plain_report_without_ansi_colors() {
+        let result = Structs::that(ExludeModules::default())
+            .have_simple_name("AssertionResult")
+            .should()
+            .be_private()
+            .evaluate();
+
+        let report = result.render(&ReportConfig::plain().without_summary());
+
+        assert_that!(report).contains("Struct 'AssertionResult' should be private");
+        assert_that!(report).does_not_contain("\u{1b}[");
+        assert_that!(report).does_not_contain("Summary");
+    }
+}
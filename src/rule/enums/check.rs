@@ -1,5 +1,7 @@
 use crate::assertion_result::AssertionResult;
-use crate::ast::enums::Enum;
+use crate::ast::cfg::requires_feature;
+use crate::ast::enums::{Enum, Variant};
+use crate::ast::Visibility;
 use crate::rule::assertable::Assertable;
 use crate::rule::enums::reports::EnumRuleViolation;
 use crate::rule::enums::{
@@ -7,8 +9,10 @@ use crate::rule::enums::{
     EnumPredicateConjunctionBuilder, SimpleAssertions,
 };
 use crate::rule::impl_block::impl_matches;
-use crate::rule::{ArchRule, CheckRule};
+use crate::rule::naming::NameConvention;
+use crate::rule::{parent_visibility, ArchRule, CheckRule};
 use std::collections::HashSet;
+use wildmatch::WildMatch;
 
 impl
     CheckRule<
@@ -57,6 +61,19 @@ impl Assertable<ConditionToken, AssertionToken, EnumMatches>
                         .push_expected(format!("have simple name '{name}'"));
                     match_against.enums_that(|enum_| enum_.ident == name)
                 }
+                ConditionToken::AreGatedByFeature(feature) => {
+                    self.assertion_results
+                        .push_expected(format!("are gated by feature '{feature}'"));
+                    let tree = self.tree;
+                    match_against.enums_that(|enum_| {
+                        let chain = tree.cfg_chain(&enum_.path.parent());
+                        requires_feature(chain.into_iter().chain(&enum_.cfg), &feature)
+                    })
+                }
+                ConditionToken::AreFieldless => {
+                    self.assertion_results.push_expected("are fieldless");
+                    match_against.enums_that(Enum::is_fieldless)
+                }
                 ConditionToken::ResidesInAModule(name) => {
                     self.assertion_results
                         .push_expected(format!("resides in a modules that match '{name}'"));
@@ -84,7 +101,7 @@ impl Assertable<ConditionToken, AssertionToken, EnumMatches>
                 ConditionToken::Implement(trait_) => {
                     let expected = format!("implement {trait_}");
                     self.assertion_results.push_expected(&expected);
-                    let imps = impl_matches(&self.filters)
+                    let imps = impl_matches(self.tree, &self.filters)
                         .impl_that(|imp| matches!(&imp.trait_impl, Some(t) if t.contains(&trait_)));
                     let types = imps.types();
                     match_against.enums_that(|enum_| types.contains(&enum_.ident.as_str()))
@@ -120,6 +137,36 @@ impl Assertable<ConditionToken, AssertionToken, EnumMatches>
                         self.assert_implement_or_derive(&trait_)
                     }
                     SimpleAssertions::Derive(trait_) => self.assert_derives(&trait_),
+                    SimpleAssertions::HaveAtMostVariants(max) => self.assert_at_most_variants(max),
+                    SimpleAssertions::HaveVariantsMatching(pattern) => {
+                        self.assert_variants_matching(&pattern)
+                    }
+                    SimpleAssertions::BeFieldless => self.assert_fieldless(),
+                    SimpleAssertions::HaveName(convention) => self.assert_name(&convention),
+                    SimpleAssertions::BeCrateVisible => {
+                        self.assert_visibility_level("be crate visible", |vis, _| {
+                            (!vis.is_crate_visible())
+                                .then(|| ("pub(crate)".to_owned(), Visibility::Crate))
+                        })
+                    }
+                    SimpleAssertions::HaveVisibilityAtMost(max) => self.assert_visibility_level(
+                        &format!("have visibility at most {max}"),
+                        |vis, _| {
+                            (!vis.is_at_most(&max)).then(|| (format!("at most {max}"), max.clone()))
+                        },
+                    ),
+                    SimpleAssertions::NotBeMoreVisibleThanParentModule => self
+                        .assert_visibility_level(
+                            "not be more visible than parent module",
+                            |vis, parent| {
+                                (!vis.is_at_most(parent)).then(|| {
+                                    (format!("at most {parent} like its module"), parent.clone())
+                                })
+                            },
+                        ),
+                    SimpleAssertions::HaveNoVariantCarryingType(pattern) => {
+                        self.assert_no_variant_carrying_type(&pattern)
+                    }
                 },
                 AssertionToken::Conjunction(a) => match a {
                     AssertionConjunction::AndShould => {
@@ -146,6 +193,10 @@ impl Assertable<ConditionToken, AssertionToken, EnumMatches>
         &self.assertion_results
     }
 
+    fn take_assertion_results(&mut self) -> AssertionResult {
+        std::mem::replace(&mut self.assertion_results, AssertionResult::new())
+    }
+
     fn has_conditions(&self) -> bool {
         !self.conditions.is_empty()
     }
@@ -156,19 +207,22 @@ impl ArchRule<ConditionToken, AssertionToken, EnumMatches> {
         self.assertion_results.push_expected("be public");
         let non_public_struct = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|enum_| !enum_.is_public())
             .collect::<Vec<_>>();
 
         for enum_ in &non_public_struct {
-            self.assertion_results
-                .push_actual(EnumRuleViolation::be_public(
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::be_public(
                     enum_.span,
                     &enum_.location,
                     enum_.ident.clone(),
-                    enum_.visibility,
-                ));
+                    enum_.visibility.clone(),
+                ),
+            );
         }
 
         non_public_struct.is_empty()
@@ -178,19 +232,22 @@ impl ArchRule<ConditionToken, AssertionToken, EnumMatches> {
         self.assertion_results.push_expected("be private");
         let public_enum = self
             .subject
-            .0
-            .iter()
-            .filter(|enum_| enum_.is_public())
+            .iter_sorted()
+            .filter(|enum_| !enum_.visibility.is_private())
             .collect::<Vec<_>>();
 
         for enum_ in &public_enum {
-            self.assertion_results
-                .push_actual(EnumRuleViolation::be_private(
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::be_private(
                     enum_.span,
                     &enum_.location,
                     enum_.ident.clone(),
-                    enum_.visibility,
-                ))
+                    enum_.visibility.clone(),
+                ),
+            )
         }
 
         public_enum.is_empty()
@@ -201,43 +258,203 @@ impl ArchRule<ConditionToken, AssertionToken, EnumMatches> {
             .push_expected(format!("have simple name '{name}'"));
         let enum_with_non_matching_name = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|enum_| enum_.ident != name)
             .collect::<Vec<_>>();
 
         for enum_ in &enum_with_non_matching_name {
-            self.assertion_results
-                .push_actual(EnumRuleViolation::have_simple(
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::have_simple(
                     enum_.span,
                     name.to_owned(),
                     &enum_.location,
                     enum_.ident.clone(),
-                ))
+                ),
+            )
+        }
+
+        enum_with_non_matching_name.is_empty()
+    }
+
+    /// `violation` gets the enum visibility and the one of its parent module, and returns what
+    /// is expected and the visibility to suggest when they do not fit.
+    fn assert_visibility_level<F>(&mut self, expected: &str, violation: F) -> bool
+    where
+        F: Fn(&Visibility, &Visibility) -> Option<(String, Visibility)>,
+    {
+        self.assertion_results.push_expected(expected);
+        let mut success = true;
+
+        for enum_ in self.subject.iter_sorted() {
+            let parent = parent_visibility(self.tree, &enum_.path.parent());
+            let Some((expected, allowed)) = violation(&enum_.visibility, &parent) else {
+                continue;
+            };
+
+            success = false;
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::visibility_level(
+                    enum_.span,
+                    &enum_.location,
+                    enum_.ident.clone(),
+                    expected,
+                    &enum_.visibility,
+                    &allowed,
+                ),
+            );
+        }
+
+        success
+    }
+
+    fn assert_name(&mut self, convention: &NameConvention) -> bool {
+        self.assertion_results
+            .push_expected(format!("have a name that should {convention}"));
+        let matches = convention.matcher();
+        let enum_with_non_matching_name = self
+            .subject
+            .iter_sorted()
+            .filter(|enum_| !matches(&enum_.ident))
+            .collect::<Vec<_>>();
+
+        for enum_ in &enum_with_non_matching_name {
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::name_convention(
+                    enum_.span,
+                    &enum_.location,
+                    enum_.ident.clone(),
+                    convention,
+                ),
+            );
         }
 
         enum_with_non_matching_name.is_empty()
     }
 
+    fn assert_at_most_variants(&mut self, max: usize) -> bool {
+        self.assertion_results
+            .push_expected(format!("have at most {max} variants"));
+        let enum_with_too_many_variants = self
+            .subject
+            .iter_sorted()
+            .filter(|enum_| enum_.variants.len() > max)
+            .collect::<Vec<_>>();
+
+        for enum_ in &enum_with_too_many_variants {
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::have_at_most_variants(
+                    enum_.span,
+                    &enum_.location,
+                    enum_.ident.clone(),
+                    enum_.variants.len(),
+                    max,
+                ),
+            )
+        }
+
+        enum_with_too_many_variants.is_empty()
+    }
+
+    /// Reports each variant for which `violation` returns a reason and a label.
+    fn assert_variants<F>(&mut self, expected: String, violation: F) -> bool
+    where
+        F: Fn(&Variant) -> Option<(String, &'static str)>,
+    {
+        self.assertion_results.push_expected(expected);
+        let mut success = true;
+
+        for enum_ in self.subject.iter_sorted() {
+            for variant in &enum_.variants {
+                let Some((reason, label)) = violation(variant) else {
+                    continue;
+                };
+
+                success = false;
+                self.assertion_results.push_actual(
+                    &enum_.path.parent(),
+                    &enum_.location,
+                    variant.span,
+                    EnumRuleViolation::variant(
+                        &enum_.location,
+                        enum_.ident.clone(),
+                        variant,
+                        reason,
+                        label,
+                    ),
+                );
+            }
+        }
+
+        success
+    }
+
+    fn assert_variants_matching(&mut self, pattern: &str) -> bool {
+        let wildcard = WildMatch::new(pattern);
+        self.assert_variants(format!("have variants matching '{pattern}'"), |variant| {
+            (!wildcard.matches(&variant.ident))
+                .then(|| (format!("should match '{pattern}'"), "does not match"))
+        })
+    }
+
+    fn assert_fieldless(&mut self) -> bool {
+        self.assert_variants("be fieldless".to_owned(), |variant| {
+            (!variant.fields.is_empty()).then(|| {
+                (
+                    format!("should be fieldless, found {} fields", variant.fields.len()),
+                    "carries data",
+                )
+            })
+        })
+    }
+
+    fn assert_no_variant_carrying_type(&mut self, pattern: &str) -> bool {
+        self.assert_variants(
+            format!("have no variant carrying type '{pattern}'"),
+            |variant| {
+                variant.carries_type_matching(pattern).map(|field| {
+                    (
+                        format!("carries type '{}' matching '{pattern}'", field.type_),
+                        "forbidden type",
+                    )
+                })
+            },
+        )
+    }
+
     fn assert_derives(&mut self, trait_: &String) -> bool {
         self.assertion_results
             .push_expected(format!("derive '{trait_}'"));
 
         let enum_without_expected_derive = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|enum_| !enum_.derives.contains(trait_))
             .collect::<Vec<_>>();
 
         for enum_ in &enum_without_expected_derive {
-            self.assertion_results
-                .push_actual(EnumRuleViolation::derive(
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::derive(
                     enum_.span,
                     &enum_.location,
                     enum_.ident.clone(),
                     trait_.clone(),
-                ))
+                ),
+            )
         }
 
         enum_without_expected_derive.is_empty()
@@ -247,13 +464,13 @@ impl ArchRule<ConditionToken, AssertionToken, EnumMatches> {
         self.assertion_results
             .push_expected(format!("implement '{trait_}'"));
 
+        let impls = impl_matches(self.tree, &self.filters);
         let enum_without_expected_impl = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|enum_| {
-                let imp_for_type = impl_matches(&self.filters)
-                    .impl_that(|imp| imp.self_ty.name() == enum_.ident.as_str());
+                let imp_for_type =
+                    impls.impl_that(|imp| imp.self_ty.name() == enum_.ident.as_str());
                 let imp_for_type = imp_for_type
                     .impl_that(|imp| matches!(&imp.trait_impl, Some(t) if t.contains(trait_)));
                 imp_for_type.is_empty()
@@ -261,13 +478,17 @@ impl ArchRule<ConditionToken, AssertionToken, EnumMatches> {
             .collect::<Vec<_>>();
 
         for enum_ in &enum_without_expected_impl {
-            self.assertion_results
-                .push_actual(EnumRuleViolation::implement(
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::implement(
                     enum_.span,
                     &enum_.location,
                     enum_.ident.clone(),
                     trait_.clone(),
-                ))
+                ),
+            )
         }
 
         enum_without_expected_impl.is_empty()
@@ -279,18 +500,17 @@ impl ArchRule<ConditionToken, AssertionToken, EnumMatches> {
 
         let derive_set = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| !struct_.derives.contains(trait_))
             .collect::<HashSet<_>>();
 
+        let impls = impl_matches(self.tree, &self.filters);
         let impl_set = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| {
-                let imp_for_type = impl_matches(&self.filters)
-                    .impl_that(|imp| imp.self_ty.name() == struct_.ident.as_str());
+                let imp_for_type =
+                    impls.impl_that(|imp| imp.self_ty.name() == struct_.ident.as_str());
 
                 let imp_for_type = imp_for_type
                     .impl_that(|imp| matches!(&imp.trait_impl, Some(t) if t.contains(trait_)));
@@ -299,15 +519,23 @@ impl ArchRule<ConditionToken, AssertionToken, EnumMatches> {
             })
             .collect::<HashSet<_>>();
 
-        let intersection: Vec<&&&Enum> = impl_set.intersection(&derive_set).collect();
+        let intersection: Vec<&Enum> = self
+            .subject
+            .iter_sorted()
+            .filter(|enum_| impl_set.contains(enum_) && derive_set.contains(enum_))
+            .collect();
         for enum_ in &intersection {
-            self.assertion_results
-                .push_actual(EnumRuleViolation::implement_or_derive(
+            self.assertion_results.push_actual(
+                &enum_.path.parent(),
+                &enum_.location,
+                enum_.span,
+                EnumRuleViolation::implement_or_derive(
                     enum_.span,
                     &enum_.location,
                     enum_.ident.clone(),
                     trait_.clone(),
-                ))
+                ),
+            )
         }
 
         intersection.is_empty()
@@ -323,7 +551,6 @@ mod condition_test {
     #[test]
     #[should_panic(
         expected = r#"Expected Structs that resides in a modules that match '*::report' to derive 'Deserialize' but found 1 violations
-
   × Enum 'ModuleRuleViolation' should derive 'Deserialize'
    ╭─[src/rule/modules/report.rs:1:1]
  1 │ #[derive(Error, Debug, Diagnostic)]
@@ -344,6 +571,41 @@ mod condition_test {
             .check();
     }
 
+    #[test]
+    fn should_report_enums_with_too_many_variants() {
+        let result = Enums::that(ExludeModules::default())
+            .have_simple_name("Visibility")
+            .should()
+            .have_at_most_variants(1)
+            .evaluate();
+
+        assert!(!result.is_success());
+        assert_eq!(
+            result.actual[0].message(),
+            "Enum 'Visibility' has 4 variants, at most 1 allowed"
+        );
+    }
+
+    #[test]
+    fn should_report_variants_not_matching_pattern() {
+        let result = Enums::that(ExludeModules::default())
+            .have_simple_name("AssertionConjunction")
+            .and()
+            .reside_in_a_module("archunit_rs::rule::enums")
+            .should()
+            .have_variants_matching("And*")
+            .and_should()
+            .be_fieldless()
+            .evaluate();
+
+        assert!(!result.is_success());
+        assert_eq!(result.actual.len(), 1);
+        assert_eq!(
+            result.actual[0].message(),
+            "Variant 'OrShould' of enum 'AssertionConjunction' should match 'And*'"
+        );
+    }
+
     #[test]
     fn should_not_panic_when_enum_does_derive() {
         Enums::that(ExludeModules::default())
@@ -386,7 +648,7 @@ mod condition_test {
     #[should_panic]
     fn should_check_with_or_condition_operator() {
         Enums::that(ExludeModules::default())
-            .have_simple_name("ConditionToken")
+            .have_simple_name("ConfiguredRule")
             .or()
             .have_simple_name("AssertionResult")
             .should()
@@ -7,9 +7,12 @@ impl ModuleTree {
     pub(crate) fn flatten_enums(&'static self, filters: &ExludeModules<'static>) -> EnumMatches {
         let mut enums = HashSet::new();
 
-        self.enums.iter().for_each(|enum_| {
-            enums.insert(enum_);
-        });
+        self.enums
+            .iter()
+            .filter(|enum_| filters.is_enabled(enum_.cfg.as_ref()))
+            .for_each(|enum_| {
+                enums.insert(enum_);
+            });
 
         self.submodules
             .iter()
@@ -3,12 +3,14 @@ mod condition;
 mod reports;
 
 use crate::ast::enums::Enum;
-use crate::ast::module_tree;
+use crate::ast::Visibility;
+use crate::rule::naming::NameConvention;
 use crate::rule::{
     ArchRuleBuilder, Assertion, Condition, ConditionBuilder, ConditionConjunctionBuilder,
     PredicateBuilder, PredicateConjunctionBuilder, Subject,
 };
 use crate::ExludeModules;
+use crate::ModuleTree;
 use std::collections::HashSet;
 
 /// A unit enum giving access to enum assertions.
@@ -50,6 +52,13 @@ impl EnumMatches {
     pub fn extends(&mut self, other: EnumMatches) {
         self.0.extend(other.0)
     }
+
+    /// Iterate over matching enums in source order (file path, then line).
+    pub fn iter_sorted(&self) -> impl Iterator<Item = &'static Enum> {
+        let mut enums: Vec<&'static Enum> = self.0.iter().copied().collect();
+        enums.sort_by(|a, b| (&a.location, a.span, &a.path).cmp(&(&b.location, b.span, &b.path)));
+        enums.into_iter()
+    }
 }
 
 impl ArchRuleBuilder<ConditionToken, AssertionToken, EnumMatches> for Enums {}
@@ -73,12 +82,12 @@ impl Condition for ConditionToken {}
 impl Assertion for AssertionToken {}
 
 impl Subject for EnumMatches {
-    fn init(filters: &ExludeModules<'static>) -> Self {
-        module_tree().flatten_enums(filters)
+    fn init(tree: &'static ModuleTree, filters: &ExludeModules<'static>) -> Self {
+        tree.flatten_enums(filters)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ConditionToken {
     AreDeclaredPublic,
     ResidesInAModule(String),
@@ -86,6 +95,8 @@ pub enum ConditionToken {
     HaveSimpleName(String),
     Derives(String),
     Implement(String),
+    AreGatedByFeature(String),
+    AreFieldless,
     And,
     Or,
     Should,
@@ -105,6 +116,14 @@ pub enum SimpleAssertions {
     Implement(String),
     ImplementOrDerive(String),
     Derive(String),
+    HaveAtMostVariants(usize),
+    HaveVariantsMatching(String),
+    BeFieldless,
+    HaveNoVariantCarryingType(String),
+    HaveName(NameConvention),
+    BeCrateVisible,
+    HaveVisibilityAtMost(Visibility),
+    NotBeMoreVisibleThanParentModule,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -146,6 +165,15 @@ impl EnumConditionBuilder {
         ConditionConjunctionBuilder(self.0)
     }
 
+    /// filter enum only compiled when `feature` is enabled, by its own `cfg` attribute or
+    /// the one of an enclosing module
+    pub fn are_gated_by_feature(mut self, feature: &str) -> EnumConditionConjunctionBuilder {
+        self.0
+            .conditions
+            .push_front(ConditionToken::AreGatedByFeature(feature.to_owned()));
+        ConditionConjunctionBuilder(self.0)
+    }
+
     /// filter enum that derives the given trait
     pub fn derives(mut self, trait_name: &str) -> EnumConditionConjunctionBuilder {
         self.0
@@ -154,6 +182,12 @@ impl EnumConditionBuilder {
         ConditionConjunctionBuilder(self.0)
     }
 
+    /// filter enum whose variants carry no data
+    pub fn are_fieldless(mut self) -> EnumConditionConjunctionBuilder {
+        self.0.conditions.push_front(ConditionToken::AreFieldless);
+        ConditionConjunctionBuilder(self.0)
+    }
+
     /// filter enum that implement the given trait
     pub fn implement(mut self, trait_name: &str) -> EnumConditionConjunctionBuilder {
         self.0
@@ -194,6 +228,82 @@ impl EnumPredicateBuilder {
         PredicateConjunctionBuilder(self.0)
     }
 
+    /// Predicate matching enum whose name matches the given regular expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is not a valid regular expression.
+    pub fn have_name_matching_regex(mut self, pattern: &str) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::regex(pattern),
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum whose name starts with `prefix`.
+    pub fn have_name_starting_with(mut self, prefix: &str) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::StartingWith(prefix.to_owned()),
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum whose name ends with `suffix`.
+    pub fn have_name_ending_with(mut self, suffix: &str) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::EndingWith(suffix.to_owned()),
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum whose name is `UpperCamelCase`.
+    pub fn have_upper_camel_case_name(mut self) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::UpperCamelCase,
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum declared `pub(crate)`.
+    pub fn be_crate_visible(mut self) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::BeCrateVisible,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum visible to no more code than with `max`, e.g.
+    /// `Visibility::Crate` accepts `pub(crate)`, `pub(super)` and private enum.
+    pub fn have_visibility_at_most(mut self, max: Visibility) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveVisibilityAtMost(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum not declared more visible than their parent module, the
+    /// crate root counts as public.
+    pub fn not_be_more_visible_than_parent_module(mut self) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::NotBeMoreVisibleThanParentModule,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
     /// Predicate matching public structs.
     pub fn be_public(mut self) -> EnumPredicateConjunctionBuilder {
         self.0
@@ -203,6 +313,9 @@ impl EnumPredicateBuilder {
     }
 
     /// Predicate matching private structs.
+    ///
+    /// `pub(crate)`, `pub(super)` and `pub(in path)` are not private, use
+    /// [`Self::have_visibility_at_most`] to allow them.
     pub fn be_private(mut self) -> EnumPredicateConjunctionBuilder {
         self.0
             .assertions
@@ -242,6 +355,64 @@ impl EnumPredicateBuilder {
 
         PredicateConjunctionBuilder(self.0)
     }
+
+    pub fn have_at_most_variants(mut self, max: usize) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveAtMostVariants(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum whose variant names all match the given pattern.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::enums::Enums;
+    ///
+    /// Enums::that(ExludeModules::cfg_test())
+    ///     .have_simple_name("AssertionConjunction")
+    ///     .should()
+    ///     .have_variants_matching("*Should")
+    ///     .and_should()
+    ///     .be_fieldless()
+    ///     .check();
+    /// ```
+    pub fn have_variants_matching(mut self, pattern: &str) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveVariantsMatching(pattern.to_owned()),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum whose variants carry no data.
+    pub fn be_fieldless(mut self) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::BeFieldless,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching enum without variant fields whose type, or one of the types it
+    /// refers to, matches the given pattern.
+    pub fn have_no_variant_carrying_type(
+        mut self,
+        pattern: &str,
+    ) -> EnumPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveNoVariantCarryingType(pattern.to_owned()),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
 }
 
 impl EnumPredicateConjunctionBuilder {
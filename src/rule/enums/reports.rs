@@ -1,4 +1,6 @@
 use crate::assertion_result::{get_code_sample_region, get_relative_location};
+use crate::ast::enums::Variant;
+use crate::ast::parse::byte_offset;
 use crate::ast::{CodeSpan, Visibility};
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use std::fs;
@@ -76,6 +78,55 @@ if that is not possible write the impl block `impl {trait_name} for {enum_name}
         #[source_code]
         src: NamedSource,
     },
+    #[error("Enum '{enum_name}' name should {convention}")]
+    #[diagnostic(help("Try renaming '{enum_name}' accordingly"))]
+    NameConvention {
+        enum_name: String,
+        convention: String,
+        location: String,
+        #[label("does not {convention}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Enum '{enum_name}' should be {expected}, found {found}")]
+    #[diagnostic(help("Try declaring '{enum_name}' with `{allowed}` visibility"))]
+    VisibilityLevel {
+        enum_name: String,
+        expected: String,
+        found: String,
+        allowed: String,
+        location: String,
+        #[label("{found}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Enum '{enum_name}' has {actual} variants, at most {allowed} allowed")]
+    #[diagnostic(help("Try splitting '{enum_name}' into smaller enums"))]
+    HaveAtMostVariants {
+        enum_name: String,
+        actual: usize,
+        allowed: usize,
+        location: String,
+        #[label("{actual} variants")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Variant '{variant_name}' of enum '{enum_name}' {reason}")]
+    #[diagnostic(help("Try changing variant '{variant_name}' of '{enum_name}' accordingly"))]
+    Variant {
+        enum_name: String,
+        variant_name: String,
+        reason: String,
+        location: String,
+        #[label("{label}")]
+        span: SourceSpan,
+        label: String,
+        #[source_code]
+        src: NamedSource,
+    },
 }
 
 impl EnumRuleViolation {
@@ -203,4 +254,138 @@ impl EnumRuleViolation {
             src,
         }
     }
+
+    pub(crate) fn have_at_most_variants(
+        span: CodeSpan,
+        location: &PathBuf,
+        enum_name: String,
+        actual: usize,
+        allowed: usize,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&enum_name).expect("enum name");
+        let span = (start_hint, enum_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        EnumRuleViolation::HaveAtMostVariants {
+            enum_name,
+            actual,
+            allowed,
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// Labels the offending variant rather than the enum.
+    pub(crate) fn variant(
+        location: &PathBuf,
+        enum_name: String,
+        variant: &Variant,
+        reason: String,
+        label: &str,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &variant.span);
+        // the sample starts at the variant's line, its span is the span of the ident
+        let start = byte_offset(&sample, 1, variant.span.start.column);
+        let span = (start, variant.ident.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        EnumRuleViolation::Variant {
+            enum_name,
+            variant_name: variant.ident.clone(),
+            reason,
+            location,
+            span,
+            label: label.to_owned(),
+            src,
+        }
+    }
+    pub(crate) fn name_convention(
+        span: CodeSpan,
+        location: &PathBuf,
+        enum_name: String,
+        convention: &crate::rule::naming::NameConvention,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&enum_name).expect("enum name");
+        let span = (start_hint, enum_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        EnumRuleViolation::NameConvention {
+            enum_name,
+            convention: convention.to_string(),
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// `expected` describes the allowed visibility, `allowed` is the one to suggest.
+    pub(crate) fn visibility_level(
+        span: CodeSpan,
+        location: &PathBuf,
+        enum_name: String,
+        expected: String,
+        found: &Visibility,
+        allowed: &Visibility,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&enum_name).expect("enum name");
+        let span = (start_hint, enum_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        EnumRuleViolation::VisibilityLevel {
+            enum_name,
+            expected,
+            found: found.to_string(),
+            allowed: allowed.to_string(),
+            location,
+            span,
+            src,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::enums::Variant;
+    use crate::ast::structs::StructKind;
+    use crate::ast::{CodeSpan, LineColumn};
+    use crate::rule::enums::reports::EnumRuleViolation;
+    use std::{env, fs};
+
+    #[test]
+    fn should_label_variant_sharing_its_line_with_a_longer_name() {
+        let path = env::temp_dir().join("archunit_rs_enum_variant.rs");
+        fs::write(&path, "enum T { AB, A }\n").expect("temp file");
+        let variant = Variant {
+            ident: "A".to_owned(),
+            span: CodeSpan {
+                start: LineColumn::from((1, 13)),
+                end: LineColumn::from((1, 14)),
+            },
+            kind: StructKind::Unit,
+            fields: vec![],
+            discriminant: None,
+            attributes: vec![],
+        };
+
+        let violation = EnumRuleViolation::variant(
+            &path,
+            "T".to_owned(),
+            &variant,
+            "should be renamed".to_owned(),
+            "this variant",
+        );
+
+        assert!(matches!(
+            violation,
+            EnumRuleViolation::Variant { span, .. } if span.offset() == 13 && span.len() == 1
+        ));
+    }
 }
@@ -1,12 +1,13 @@
 use crate::ast::impl_blocks::Impl;
-use crate::ast::module_tree;
 use crate::{ExludeModules, ModuleTree};
-use once_cell::sync::OnceCell;
 use std::collections::HashSet;
 
-pub(crate) fn impl_matches(filters: &ExludeModules<'static>) -> &'static ImplMatchesTODO {
-    static INSTANCE: OnceCell<ImplMatchesTODO> = OnceCell::new();
-    INSTANCE.get_or_init(|| module_tree().flatten_impls(filters))
+/// Impl blocks of the given tree, collected once per rule evaluation.
+pub(crate) fn impl_matches(
+    tree: &'static ModuleTree,
+    filters: &ExludeModules<'static>,
+) -> ImplMatchesTODO {
+    tree.flatten_impls(filters)
 }
 
 impl ModuleTree {
@@ -14,16 +15,13 @@ impl ModuleTree {
         &'static self,
         filters: &ExludeModules<'static>,
     ) -> ImplMatchesTODO {
-        let mut impls = HashSet::new();
-
-        self.impl_blocks.iter().for_each(|impl_block| {
-            impls.insert(impl_block);
-        });
-
-        self.submodules
-            .iter()
-            .flat_map(|sub| sub.flatten(filters).0)
-            .for_each(|(_, module)| impls.extend(module.flatten_impls(filters).0));
+        let impls = self
+            .flatten(filters)
+            .0
+            .into_values()
+            .flat_map(|module| &module.impl_blocks)
+            .filter(|impl_block| filters.is_enabled(impl_block.cfg.as_ref()))
+            .collect();
 
         ImplMatchesTODO(impls)
     }
@@ -1,16 +1,28 @@
 use crate::assertion_result::AssertionResult;
-use crate::ExludeModules;
+use crate::ast::{module_tree, ItemPath, Visibility};
+use crate::report::text::ReportConfig;
+use crate::{ExludeModules, ModuleTree};
 use std::collections::VecDeque;
 use std::fmt::Debug;
 
 pub mod enums;
 pub mod impl_block;
 pub mod modules;
+pub mod naming;
 pub mod pattern;
 pub mod structs;
 
+/// The visibility of the module at `path`, the crate root counts as public.
+pub(crate) fn parent_visibility(tree: &ModuleTree, path: &ItemPath) -> Visibility {
+    match tree.find(path) {
+        Some(module) if module.declaration.is_some() => module.visibility.clone(),
+        _ => Visibility::Public,
+    }
+}
+
 #[derive(Debug)]
 pub struct ArchRule<C: Condition + Debug, A: Assertion + Debug + Clone, S: Subject> {
+    pub(crate) tree: &'static ModuleTree,
     pub(crate) conditions: VecDeque<C>,
     pub(crate) assertions: VecDeque<A>,
     pub(crate) filters: ExludeModules<'static>,
@@ -24,7 +36,7 @@ pub struct ArchRule<C: Condition + Debug, A: Assertion + Debug + Clone, S: Subje
 /// and provide your custom implementation:
 /// ```
 pub trait Subject: Default {
-    fn init(filters: &ExludeModules<'static>) -> Self;
+    fn init(tree: &'static ModuleTree, filters: &ExludeModules<'static>) -> Self;
 }
 
 /// [`Condition`] are used to filter matching [`Subjects`].
@@ -53,7 +65,22 @@ pub trait Assertion: Debug + PartialEq + Clone {}
 pub trait CheckRule<C: Condition, A: Assertion, S: Subject, T: assertable::Assertable<C, A, S>>:
     Sized
 {
+    /// Evaluate the rule and panic on violations, the report is configured via
+    /// [`ReportConfig::from_env`].
     fn check(self) {
+        self.check_with(&ReportConfig::from_env())
+    }
+
+    /// Evaluate the rule and panic on violations, rendering the report with the given config.
+    fn check_with(self, config: &ReportConfig) {
+        let result = self.evaluate();
+        if !result.is_success() {
+            panic!("{}", result.render(config))
+        }
+    }
+
+    /// Evaluate the rule without panicking, returning the assertion outcome and its violations.
+    fn evaluate(self) -> AssertionResult {
         let mut rule = self.get_rule();
 
         // If there are no condition we are matching on all items
@@ -62,10 +89,10 @@ pub trait CheckRule<C: Condition, A: Assertion, S: Subject, T: assertable::Asser
         }
 
         let success = rule.apply_assertions();
-        if !success {
-            let result = rule.assertion_results();
-            panic!("{result}")
-        }
+        let mut result = rule.take_assertion_results();
+        result.success = success;
+        result.sort_violations();
+        result
     }
 
     fn get_rule(self) -> T;
@@ -79,6 +106,7 @@ pub(super) mod assertable {
         fn apply_conditions(&mut self);
         fn apply_assertions(&mut self) -> bool;
         fn assertion_results(&self) -> &AssertionResult;
+        fn take_assertion_results(&mut self) -> AssertionResult;
         fn has_conditions(&self) -> bool;
     }
 }
@@ -89,8 +117,9 @@ where
     A: Assertion,
     S: Subject,
 {
-    fn new(filters: ExludeModules<'static>) -> Self {
+    fn new(tree: &'static ModuleTree, filters: ExludeModules<'static>) -> Self {
         ArchRule {
+            tree,
             conditions: VecDeque::new(),
             assertions: VecDeque::new(),
             filters,
@@ -100,19 +129,37 @@ where
     }
 
     fn init_subject(&self) -> S {
-        S::init(&self.filters)
+        S::init(self.tree, &self.filters)
     }
 }
 
 pub trait ArchRuleBuilder<C: Condition, P: Assertion, S: Subject>: Sized {
     /// Builder function for arch rule assertions, see [`ConditionBuilder`].
     fn that(filters: ExludeModules<'static>) -> ConditionBuilder<C, P, S> {
-        ConditionBuilder(ArchRule::<C, P, S>::new(filters))
+        Self::that_in(module_tree(), filters)
     }
 
     /// Match all and returns a [`PredicateBuilder`].
     fn all_should(filters: ExludeModules<'static>) -> PredicateBuilder<C, P, S> {
-        let mut rule = ArchRule::<C, P, S>::new(filters);
+        Self::all_should_in(module_tree(), filters)
+    }
+
+    /// Same as [`ArchRuleBuilder::that`], evaluated against the given tree instead of the
+    /// crate under test, see [`ModuleTree::load_from`].
+    fn that_in(
+        tree: &'static ModuleTree,
+        filters: ExludeModules<'static>,
+    ) -> ConditionBuilder<C, P, S> {
+        ConditionBuilder(ArchRule::<C, P, S>::new(tree, filters))
+    }
+
+    /// Same as [`ArchRuleBuilder::all_should`], evaluated against the given tree instead of
+    /// the crate under test.
+    fn all_should_in(
+        tree: &'static ModuleTree,
+        filters: ExludeModules<'static>,
+    ) -> PredicateBuilder<C, P, S> {
+        let mut rule = ArchRule::<C, P, S>::new(tree, filters);
         rule.subject = rule.init_subject();
         PredicateBuilder(rule)
     }
@@ -134,3 +181,34 @@ pub struct DependencyPredicateConjunctionBuilder<C: Condition, P: Assertion, S:
 
 #[derive(Debug)]
 pub struct PredicateConjunctionBuilder<C: Condition, P: Assertion, S: Subject>(ArchRule<C, P, S>);
+
+impl<C: Condition, P: Assertion, S: Subject> PredicateConjunctionBuilder<C, P, S> {
+    /// Build a rule from tokens given in declaration order, as if the builder functions were
+    /// called one after another. Conditions must end with the subject `Should` token.
+    pub(crate) fn from_tokens(
+        tree: &'static ModuleTree,
+        filters: ExludeModules<'static>,
+        conditions: Vec<C>,
+        assertions: Vec<P>,
+    ) -> Self {
+        let mut rule = ArchRule::<C, P, S>::new(tree, filters);
+        if conditions.is_empty() {
+            rule.subject = rule.init_subject();
+        }
+
+        conditions
+            .into_iter()
+            .for_each(|condition| rule.conditions.push_front(condition));
+        assertions
+            .into_iter()
+            .for_each(|assertion| rule.assertions.push_front(assertion));
+
+        PredicateConjunctionBuilder(rule)
+    }
+
+    /// Document why this rule exists, the rationale is displayed along with rule violations.
+    pub fn because(mut self, rationale: &str) -> Self {
+        self.0.assertion_results.rationale = Some(rationale.to_owned());
+        self
+    }
+}
@@ -1,15 +1,21 @@
 use crate::assertion_result::AssertionResult;
-use crate::ast::{ItemPath, ModuleUse};
+use crate::ast::cfg::{requires_feature, CfgPredicate};
+use crate::ast::manifest::{declared_features, ManifestError};
+use crate::ast::{CodeSpan, ItemPath, LineColumn, ModuleUse, Visibility};
+use crate::metrics::{Bound, CouplingMetrics, ModuleMetrics};
 use crate::rule::modules::report::ModuleRuleViolation;
 use crate::rule::modules::ModuleMatches;
 use crate::rule::modules::{
     AssertionConjunction, AssertionToken, ConditionToken, DependencyAssertion,
-    DependencyAssertionConjunction, ModulePredicateConjunctionBuilder, SimpleAssertions,
+    DependencyAssertionConjunction, FunctionSize, ModulePredicateConjunctionBuilder, ModuleSize,
+    SimpleAssertions,
 };
+use crate::rule::naming::NameConvention;
 use crate::rule::pattern::PathPattern;
-use crate::rule::{assertable::Assertable, ArchRule, CheckRule};
-use crate::ModuleTree;
-use std::collections::HashMap;
+use crate::rule::{assertable::Assertable, parent_visibility, ArchRule, CheckRule};
+use crate::{ExludeModules, ModuleTree};
+use std::collections::BTreeMap;
+use std::path::Path;
 
 impl
     CheckRule<
@@ -54,7 +60,7 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                         .flat_map(|module| {
                             module.module_that(|sub| sub.is_public(), &self.filters).0
                         })
-                        .collect::<HashMap<&ItemPath, &ModuleTree>>()
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
                 }
                 ConditionToken::AreDeclaredPrivate => {
                     self.assertion_results.push_expected("are declared private");
@@ -64,7 +70,7 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                         .flat_map(|module| {
                             module.module_that(|sub| !sub.is_public(), &self.filters).0
                         })
-                        .collect::<HashMap<&ItemPath, &ModuleTree>>()
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
                 }
                 ConditionToken::HaveSimpleName(name) => {
                     self.assertion_results
@@ -76,7 +82,7 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                         .flat_map(|module| {
                             module.module_that(|sub| sub.ident == name, &self.filters).0
                         })
-                        .collect::<HashMap<&ItemPath, &ModuleTree>>()
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
                 }
                 ConditionToken::HaveSimpleEndingWith(pattern) => {
                     self.assertion_results
@@ -89,7 +95,7 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                                 .module_that(|sub| sub.ident.ends_with(&pattern), &self.filters)
                                 .0
                         })
-                        .collect::<HashMap<&ItemPath, &ModuleTree>>()
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
                 }
                 ConditionToken::HaveSimpleStartingWith(pattern) => {
                     self.assertion_results
@@ -103,7 +109,7 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                                 .module_that(|sub| sub.ident.starts_with(&pattern), &self.filters)
                                 .0
                         })
-                        .collect::<HashMap<&ItemPath, &ModuleTree>>()
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
                 }
                 ConditionToken::ResidesInAModule(name) => {
                     self.assertion_results
@@ -116,7 +122,7 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                                 .module_that(|sub| sub.path_match(&name), &self.filters)
                                 .0
                         })
-                        .collect::<HashMap<&ItemPath, &ModuleTree>>()
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
                 }
                 ConditionToken::NotResidesInAModule(name) => {
                     self.assertion_results
@@ -130,7 +136,25 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                                 .module_that(|sub| !sub.path_match(&name), &self.filters)
                                 .0
                         })
-                        .collect::<HashMap<&ItemPath, &ModuleTree>>()
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
+                }
+                ConditionToken::AreGatedByFeature(feature) => {
+                    self.assertion_results
+                        .push_expected(format!("are gated by feature '{feature}'"));
+                    let tree = self.tree;
+
+                    match_against
+                        .0
+                        .values()
+                        .flat_map(|module| {
+                            module
+                                .module_that(
+                                    |sub| requires_feature(tree.cfg_chain(&sub.path), &feature),
+                                    &self.filters,
+                                )
+                                .0
+                        })
+                        .collect::<BTreeMap<&ItemPath, &ModuleTree>>()
                 }
                 ConditionToken::And => {
                     self.assertion_results.push_expected(" and ");
@@ -173,6 +197,63 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
                     SimpleAssertions::BePrivate => self.assert_private(),
                     SimpleAssertions::HaveSimpleName(name) => self.assert_simple_name(&name),
                     SimpleAssertions::NotHaveSimpleName(name) => self.assert_not_simple_name(&name),
+                    SimpleAssertions::HaveName(convention) => self.assert_name(&convention),
+                    SimpleAssertions::BeCrateVisible => {
+                        self.assert_visibility_level("be crate visible", |vis, _| {
+                            (!vis.is_crate_visible())
+                                .then(|| ("pub(crate)".to_owned(), Visibility::Crate))
+                        })
+                    }
+                    SimpleAssertions::HaveVisibilityAtMost(max) => self.assert_visibility_level(
+                        &format!("have visibility at most {max}"),
+                        |vis, _| {
+                            (!vis.is_at_most(&max)).then(|| (format!("at most {max}"), max.clone()))
+                        },
+                    ),
+                    SimpleAssertions::NotBeMoreVisibleThanParentModule => self
+                        .assert_visibility_level(
+                            "not be more visible than parent module",
+                            |vis, parent| {
+                                (!vis.is_at_most(parent)).then(|| {
+                                    (format!("at most {parent} like its module"), parent.clone())
+                                })
+                            },
+                        ),
+                    SimpleAssertions::BeFeatureGated => self.assert_feature_gated(None),
+                    SimpleAssertions::BeGatedByFeature(feature) => {
+                        self.assert_feature_gated(Some(&feature))
+                    }
+                    SimpleAssertions::OnlyUseDeclaredFeatures => {
+                        self.assert_only_declared_features()
+                    }
+                    SimpleAssertions::HaveAtMostItems(max) => {
+                        self.assert_at_most(ModuleSize::Items, max)
+                    }
+                    SimpleAssertions::HaveAtMostLines(max) => {
+                        self.assert_at_most(ModuleSize::Lines, max)
+                    }
+                    SimpleAssertions::OnlyHaveFunctionsWithAtMostParameters(max) => {
+                        self.assert_functions_at_most(FunctionSize::Parameters, max)
+                    }
+                    SimpleAssertions::OnlyHaveFunctionsWithAtMostLines(max) => {
+                        self.assert_functions_at_most(FunctionSize::Lines, max)
+                    }
+                    SimpleAssertions::HaveInstabilityBelow(threshold) => self.assert_metric(
+                        "instability",
+                        ModuleMetrics::instability,
+                        Bound::Below(threshold),
+                    ),
+                    SimpleAssertions::HaveAbstractnessAbove(threshold) => self.assert_metric(
+                        "abstractness",
+                        ModuleMetrics::abstractness,
+                        Bound::Above(threshold),
+                    ),
+                    SimpleAssertions::HaveDistanceFromMainSequenceBelow(threshold) => self
+                        .assert_metric(
+                            "distance from the main sequence",
+                            ModuleMetrics::distance_from_main_sequence,
+                            Bound::Below(threshold),
+                        ),
                 },
                 AssertionToken::Conjunction(a) => match a {
                     AssertionConjunction::AndShould => {
@@ -244,6 +325,10 @@ impl Assertable<ConditionToken, AssertionToken, ModuleMatches>
         &self.assertion_results
     }
 
+    fn take_assertion_results(&mut self) -> AssertionResult {
+        std::mem::replace(&mut self.assertion_results, AssertionResult::new())
+    }
+
     fn has_conditions(&self) -> bool {
         !self.conditions.is_empty()
     }
@@ -260,13 +345,17 @@ impl ArchRule<ConditionToken, AssertionToken, ModuleMatches> {
             .collect::<Vec<_>>();
         for module in &non_public_modules {
             let declaration = module.declaration.as_ref().expect("should be declared");
-            self.assertion_results
-                .push_actual(ModuleRuleViolation::be_public(
+            self.assertion_results.push_actual(
+                &module.path,
+                &declaration.real_path,
+                declaration.span,
+                ModuleRuleViolation::be_public(
                     declaration.span,
                     &declaration.real_path,
                     declaration.ident.clone(),
-                    declaration.vis,
-                ))
+                    declaration.vis.clone(),
+                ),
+            )
         }
         non_public_modules.is_empty()
     }
@@ -277,24 +366,104 @@ impl ArchRule<ConditionToken, AssertionToken, ModuleMatches> {
             .subject
             .0
             .values()
-            .filter(|module| module.is_public())
+            .filter(|module| module.declaration.is_some() && !module.visibility.is_private())
             .collect::<Vec<_>>();
 
         for module in &public_modules {
-            self.assertion_results
-                .push_actual(ModuleRuleViolation::be_private(
-                    module
-                        .span
-                        .expect("Should not try to get span for crate root"),
+            let span = module
+                .span
+                .expect("Should not try to get span for crate root");
+            self.assertion_results.push_actual(
+                &module.path,
+                &module.real_path,
+                span,
+                ModuleRuleViolation::be_private(
+                    span,
                     &module.real_path,
                     module.ident.clone(),
-                    module.visibility,
-                ))
+                    module.visibility.clone(),
+                ),
+            )
         }
 
         public_modules.is_empty()
     }
 
+    /// `violation` gets the module visibility and the one of its parent module, and returns what
+    /// is expected and the visibility to suggest when they do not fit. The crate root is skipped.
+    fn assert_visibility_level<F>(&mut self, expected: &str, violation: F) -> bool
+    where
+        F: Fn(&Visibility, &Visibility) -> Option<(String, Visibility)>,
+    {
+        self.assertion_results.push_expected(expected);
+        let mut success = true;
+
+        for module in self.subject.0.values() {
+            let Some(declaration) = module.declaration.as_ref() else {
+                continue;
+            };
+            let parent = parent_visibility(self.tree, &module.path.parent());
+            let Some((expected, allowed)) = violation(&declaration.vis, &parent) else {
+                continue;
+            };
+
+            success = false;
+            self.assertion_results.push_actual(
+                &module.path,
+                &declaration.real_path,
+                declaration.span,
+                ModuleRuleViolation::visibility_level(
+                    declaration.span,
+                    &declaration.real_path,
+                    declaration.ident.clone(),
+                    expected,
+                    &declaration.vis,
+                    &allowed,
+                ),
+            );
+        }
+
+        success
+    }
+
+    /// The crate root has no declaration to rename and is skipped.
+    fn assert_name(&mut self, convention: &NameConvention) -> bool {
+        self.assertion_results
+            .push_expected(format!("have a name that should {convention}"));
+        let matches = convention.matcher();
+        let module_with_non_matching_name = self
+            .subject
+            .0
+            .values()
+            .filter(|module| {
+                module
+                    .declaration
+                    .as_ref()
+                    .is_some_and(|declaration| !matches(&declaration.ident))
+            })
+            .collect::<Vec<_>>();
+
+        for module in &module_with_non_matching_name {
+            let declaration = module
+                .declaration
+                .as_ref()
+                .expect("module should have declaration");
+            self.assertion_results.push_actual(
+                &module.path,
+                &declaration.real_path,
+                declaration.span,
+                ModuleRuleViolation::name_convention(
+                    declaration.span,
+                    &declaration.real_path,
+                    declaration.ident.clone(),
+                    convention,
+                ),
+            )
+        }
+
+        module_with_non_matching_name.is_empty()
+    }
+
     fn assert_simple_name(&mut self, name: &str) -> bool {
         self.assertion_results
             .push_expected(format!("have simple name '{name}'"));
@@ -310,13 +479,17 @@ impl ArchRule<ConditionToken, AssertionToken, ModuleMatches> {
                 .declaration
                 .as_ref()
                 .expect("module should have declaration");
-            self.assertion_results
-                .push_actual(ModuleRuleViolation::have_name_matching(
+            self.assertion_results.push_actual(
+                &module.path,
+                &declaration.real_path,
+                declaration.span,
+                ModuleRuleViolation::have_name_matching(
                     declaration.span,
                     name.to_owned(),
                     &declaration.real_path,
                     declaration.ident.clone(),
-                ))
+                ),
+            )
         }
 
         module_with_non_matching_name.is_empty()
@@ -337,18 +510,318 @@ impl ArchRule<ConditionToken, AssertionToken, ModuleMatches> {
                 .declaration
                 .as_ref()
                 .expect("module should have declaration");
-            self.assertion_results
-                .push_actual(ModuleRuleViolation::not_have_name_matching(
+            self.assertion_results.push_actual(
+                &module.path,
+                &declaration.real_path,
+                declaration.span,
+                ModuleRuleViolation::not_have_name_matching(
                     declaration.span,
                     name.to_owned(),
                     &declaration.real_path,
                     declaration.ident.clone(),
-                ))
+                ),
+            )
         }
 
         module_with_matching_name.is_empty()
     }
 
+    fn assert_feature_gated(&mut self, feature: Option<&str>) -> bool {
+        match feature {
+            Some(feature) => self
+                .assertion_results
+                .push_expected(format!("be gated by feature '{feature}'")),
+            None => self.assertion_results.push_expected("be feature gated"),
+        }
+
+        let tree = self.tree;
+        let not_gated = self
+            .subject
+            .0
+            .values()
+            .filter(|module| {
+                let chain = tree.cfg_chain(&module.path);
+                match feature {
+                    Some(feature) => !requires_feature(chain, feature),
+                    None => !chain.iter().any(|cfg| {
+                        cfg.features()
+                            .iter()
+                            .any(|feature| requires_feature(chain.iter().copied(), feature))
+                    }),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for module in &not_gated {
+            // The crate root is compiled whatever the enabled features
+            let Some(declaration) = module.declaration.as_ref() else {
+                continue;
+            };
+
+            self.assertion_results.push_actual(
+                &module.path,
+                &declaration.real_path,
+                declaration.span,
+                ModuleRuleViolation::be_feature_gated(
+                    declaration.span,
+                    &declaration.real_path,
+                    declaration.ident.clone(),
+                    feature,
+                ),
+            )
+        }
+
+        not_gated.iter().all(|module| module.declaration.is_none())
+    }
+
+    fn assert_metric(
+        &mut self,
+        metric: &str,
+        value: fn(&ModuleMetrics) -> f64,
+        bound: Bound,
+    ) -> bool {
+        self.assertion_results
+            .push_expected(format!("have {metric} {bound}"));
+
+        let metrics = CouplingMetrics::of(self.tree, &self.filters);
+        let mut success = true;
+        for module in self.subject.0.values() {
+            let Some(module_metrics) = metrics.get(module.path.as_str()) else {
+                continue;
+            };
+
+            let actual = value(module_metrics);
+            if bound.contains(actual) {
+                continue;
+            }
+
+            success = false;
+            let (location, span, name) = declaration_site(module);
+            self.assertion_results.push_actual(
+                &module.path,
+                location,
+                span,
+                ModuleRuleViolation::metric_out_of_bounds(
+                    span,
+                    location,
+                    module.path.to_string(),
+                    name,
+                    metric,
+                    actual,
+                    bound,
+                    module_metrics,
+                ),
+            )
+        }
+
+        success
+    }
+
+    fn assert_at_most(&mut self, measure: ModuleSize, max: usize) -> bool {
+        self.assertion_results
+            .push_expected(format!("have at most {max} {measure}"));
+
+        let filters = &self.filters;
+        let mut success = true;
+        for module in self.subject.0.values() {
+            let actual = match measure {
+                ModuleSize::Lines => module.lines,
+                ModuleSize::Items => item_count(module, filters),
+            };
+
+            if actual <= max {
+                continue;
+            }
+
+            success = false;
+            let (location, span, name) = declaration_site(module);
+            self.assertion_results.push_actual(
+                &module.path,
+                location,
+                span,
+                ModuleRuleViolation::have_at_most(
+                    span,
+                    location,
+                    module.path.to_string(),
+                    name,
+                    measure,
+                    actual,
+                    max,
+                ),
+            )
+        }
+
+        success
+    }
+
+    fn assert_functions_at_most(&mut self, measure: FunctionSize, max: usize) -> bool {
+        self.assertion_results
+            .push_expected(format!("only have functions with at most {max} {measure}"));
+
+        let mut success = true;
+        for module in self.subject.0.values() {
+            for function in module
+                .functions
+                .iter()
+                .filter(|function| self.filters.is_enabled(function.cfg.as_ref()))
+            {
+                let actual = match measure {
+                    FunctionSize::Lines => function.lines,
+                    FunctionSize::Parameters => function.parameters,
+                };
+
+                if actual <= max {
+                    continue;
+                }
+
+                success = false;
+                self.assertion_results.push_actual(
+                    &module.path,
+                    &function.real_path,
+                    function.span,
+                    ModuleRuleViolation::function_have_at_most(
+                        function.span,
+                        &function.real_path,
+                        function.path.to_string(),
+                        function.ident.clone(),
+                        measure,
+                        actual,
+                        max,
+                    ),
+                )
+            }
+        }
+
+        success
+    }
+
+    fn assert_only_declared_features(&mut self) -> bool {
+        self.assertion_results
+            .push_expected("only use features declared in the crate manifest");
+
+        let declared = self
+            .tree
+            .manifest_dir()
+            .ok_or_else(|| ManifestError::Missing {
+                dir: self.tree.real_path.clone(),
+            })
+            .and_then(|manifest_dir| Ok((manifest_dir, declared_features(manifest_dir)?)));
+        let (manifest_dir, declared) = match declared {
+            Ok(declared) => declared,
+            Err(err) => {
+                self.assertion_results.push_error(self.tree, err);
+                return false;
+            }
+        };
+        let manifest = manifest_dir.join("Cargo.toml");
+
+        let mut gated = vec![];
+        for module in self.subject.0.values() {
+            if let Some(declaration) = &module.declaration {
+                gated.push((
+                    &module.path,
+                    cfg_features(module.cfg.as_ref()),
+                    declaration.real_path.as_path(),
+                    declaration.span,
+                    declaration.ident.as_str(),
+                ));
+            }
+
+            let filters = &self.filters;
+            for struct_ in module
+                .structs
+                .iter()
+                .filter(|struct_| filters.is_enabled(struct_.cfg.as_ref()))
+            {
+                gated.push((
+                    &module.path,
+                    cfg_features(struct_.cfg.as_ref()),
+                    struct_.real_path.as_path(),
+                    struct_.span,
+                    struct_.ident.as_str(),
+                ));
+            }
+
+            for enum_ in module
+                .enums
+                .iter()
+                .filter(|enum_| filters.is_enabled(enum_.cfg.as_ref()))
+            {
+                gated.push((
+                    &module.path,
+                    cfg_features(enum_.cfg.as_ref()),
+                    enum_.location.as_path(),
+                    enum_.span,
+                    enum_.ident.as_str(),
+                ));
+            }
+
+            let impl_blocks = module
+                .impl_blocks
+                .iter()
+                .filter(|imp| filters.is_enabled(imp.cfg.as_ref()))
+                .collect::<Vec<_>>();
+            for imp in &impl_blocks {
+                gated.push((
+                    &module.path,
+                    cfg_features(imp.cfg.as_ref()),
+                    module.real_path.as_path(),
+                    imp.span,
+                    imp.self_ty.name(),
+                ));
+            }
+
+            for function in module
+                .functions
+                .iter()
+                .filter(|function| filters.is_enabled(function.cfg.as_ref()))
+            {
+                // Methods inherit the cfg of their impl block, already reported above
+                let mut features = cfg_features(function.cfg.as_ref());
+                if let Some(self_ty) = &function.self_ty {
+                    let inherited = impl_blocks
+                        .iter()
+                        .filter(|imp| imp.self_ty.name() == self_ty)
+                        .flat_map(|imp| cfg_features(imp.cfg.as_ref()))
+                        .collect::<Vec<_>>();
+                    features.retain(|feature| !inherited.contains(feature));
+                }
+
+                gated.push((
+                    &module.path,
+                    features,
+                    function.real_path.as_path(),
+                    function.span,
+                    function.ident.as_str(),
+                ));
+            }
+        }
+
+        let mut success = true;
+        for (path, features, location, span, ident) in gated {
+            for feature in features
+                .into_iter()
+                .filter(|feature| !declared.contains(*feature))
+            {
+                success = false;
+                self.assertion_results.push_actual(
+                    path,
+                    location,
+                    span,
+                    ModuleRuleViolation::undeclared_feature(
+                        span,
+                        location,
+                        ident.to_owned(),
+                        feature.to_owned(),
+                        &manifest,
+                    ),
+                )
+            }
+        }
+
+        success
+    }
+
     fn assert_dependencies_name_match(&mut self, pattern: &str) -> bool {
         self.assertion_results.push_expected(format!(
             "only have dependencies matching pattern '{pattern}'"
@@ -378,6 +851,9 @@ impl ArchRule<ConditionToken, AssertionToken, ModuleMatches> {
             for (path, real_path, usage) in per_module_mismatch {
                 usage.into_iter().for_each(|usage| {
                     self.assertion_results.push_actual(
+                        path,
+                        real_path,
+                        usage.span,
                         ModuleRuleViolation::only_have_dependencies_with_simple_name(
                             usage.span,
                             real_path,
@@ -421,6 +897,9 @@ impl ArchRule<ConditionToken, AssertionToken, ModuleMatches> {
             for (path, real_path, usage) in per_module_mismatch {
                 usage.into_iter().for_each(|usage| {
                     self.assertion_results.push_actual(
+                        path,
+                        real_path,
+                        usage.span,
                         ModuleRuleViolation::only_have_dependencies_without_simple_name(
                             usage.span,
                             real_path,
@@ -436,14 +915,74 @@ impl ArchRule<ConditionToken, AssertionToken, ModuleMatches> {
     }
 }
 
+/// The features referenced by `cfg`, sorted and deduplicated.
+fn cfg_features(cfg: Option<&CfgPredicate>) -> Vec<&str> {
+    let mut features = cfg.map(CfgPredicate::features).unwrap_or_default();
+    features.sort();
+    features.dedup();
+    features
+}
+
+/// Where to report a violation on a whole module: its declaration, or the top of its file for
+/// the crate root.
+fn declaration_site(module: &ModuleTree) -> (&Path, CodeSpan, Option<String>) {
+    match module.declaration.as_ref() {
+        Some(declaration) => (
+            &declaration.real_path,
+            declaration.span,
+            Some(declaration.ident.clone()),
+        ),
+        None => (
+            &module.real_path,
+            CodeSpan {
+                start: LineColumn::from((1, 0)),
+                end: LineColumn::from((1, 0)),
+            },
+            None,
+        ),
+    }
+}
+
+/// Structs, enums, traits, impl blocks, free functions and submodules compiled with `filters`.
+fn item_count(module: &ModuleTree, filters: &ExludeModules) -> usize {
+    module
+        .structs
+        .iter()
+        .filter(|struct_| filters.is_enabled(struct_.cfg.as_ref()))
+        .count()
+        + module
+            .enums
+            .iter()
+            .filter(|enum_| filters.is_enabled(enum_.cfg.as_ref()))
+            .count()
+        + module
+            .traits
+            .iter()
+            .filter(|trait_| filters.is_enabled(trait_.cfg.as_ref()))
+            .count()
+        + module
+            .impl_blocks
+            .iter()
+            .filter(|imp| filters.is_enabled(imp.cfg.as_ref()))
+            .count()
+        + module
+            .functions
+            .iter()
+            .filter(|function| function.self_ty.is_none())
+            .filter(|function| filters.is_enabled(function.cfg.as_ref()))
+            .count()
+        + module.submodules.iter().filter(filters.filter()).count()
+}
+
 impl ModuleUse {
     pub fn matching(&self, pattern: &str) -> bool {
         PathPattern::from(pattern).matches_module_path(&self.parts)
     }
 
-    pub fn starts_with(&self, path: &str) -> bool {
+    /// Whether this use points into `path`, `crate::` paths are resolved against `crate_name`.
+    pub fn starts_with(&self, crate_name: &str, path: &str) -> bool {
         if self.parts.starts_with("crate") {
-            let name = env!("CARGO_CRATE_NAME", "'CARGO_CRATE_NAME' should be set");
+            let name = crate_name;
             let relative_path = &self.parts[5..];
             let parts_canonical = &format!("{name}{relative_path}");
             let pattern = format!("{path}*");
@@ -470,9 +1009,9 @@ mod condition_test {
             span: CodeSpan::default(),
         };
 
-        assert_that!(module_usage.starts_with("archunit_rs::rule")).is_true();
-        assert_that!(module_usage.starts_with("archunit_rs::ast")).is_false();
-        assert_that!(module_usage.starts_with("ast")).is_false();
+        assert_that!(module_usage.starts_with("archunit_rs", "archunit_rs::rule")).is_true();
+        assert_that!(module_usage.starts_with("archunit_rs", "archunit_rs::ast")).is_false();
+        assert_that!(module_usage.starts_with("archunit_rs", "ast")).is_false();
     }
 
     #[test]
@@ -482,9 +1021,9 @@ mod condition_test {
             span: CodeSpan::default(),
         };
 
-        assert_that!(module_usage.starts_with("archunit_rs::rule")).is_true();
-        assert_that!(module_usage.starts_with("archunit_rs::ast")).is_false();
-        assert_that!(module_usage.starts_with("ast")).is_false();
+        assert_that!(module_usage.starts_with("archunit_rs", "archunit_rs::rule")).is_true();
+        assert_that!(module_usage.starts_with("archunit_rs", "archunit_rs::ast")).is_false();
+        assert_that!(module_usage.starts_with("archunit_rs", "ast")).is_false();
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use crate::ast::{ItemPath, ModuleTree};
 use crate::rule::modules::{ModuleDependencies, ModuleMatches};
 use crate::ExludeModules;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 impl ModuleTree {
     pub(crate) fn module_that<P>(
@@ -17,13 +17,13 @@ impl ModuleTree {
             .0
             .into_iter()
             .filter(|(_, module)| predicate(module))
-            .collect::<HashMap<&ItemPath, &ModuleTree>>();
+            .collect::<BTreeMap<&ItemPath, &ModuleTree>>();
 
         ModuleMatches(matches)
     }
 
     pub(crate) fn flatten(&'static self, filters: &ExludeModules<'static>) -> ModuleMatches {
-        let mut modules = HashMap::new();
+        let mut modules = BTreeMap::new();
         modules.insert(&self.path, self);
 
         self.submodules
@@ -41,7 +41,7 @@ impl ModuleTree {
         &'static self,
         filters: &ExludeModules<'static>,
     ) -> ModuleDependencies {
-        let mut modules = HashMap::new();
+        let mut modules = BTreeMap::new();
         modules.insert(&self.path, (&self.real_path, &self.dependencies));
 
         self.submodules
@@ -111,7 +111,7 @@ mod condition_test {
             .collect::<Vec<&str>>();
 
         assert_that!(arch_rule.0.assertion_results.expected).is_equal_to(
-            &"Modules that resides in a modules that match '*::modules' and have simple name 'condition'"
+            "Modules that resides in a modules that match '*::modules' and have simple name 'condition'"
                 .to_owned(),
         );
 
@@ -136,7 +136,7 @@ mod condition_test {
             .collect::<Vec<&str>>();
 
         assert_that!(arch_rule.assertion_results.expected).is_equal_to(
-            &"Modules that resides in a modules that match 'archunit_rs::rule::modules::*' or have simple name 'ast'"
+            "Modules that resides in a modules that match 'archunit_rs::rule::modules::*' or have simple name 'ast'"
                 .to_owned(),
         );
 
@@ -165,7 +165,7 @@ mod condition_test {
             .collect::<Vec<&str>>();
 
         assert_that!(arch_rule.assertion_results.expected).is_equal_to(
-            &"Modules that not resides in a modules that match 'archunit_rs::rule::modules*'"
+            "Modules that not resides in a modules that match 'archunit_rs::rule::modules*'"
                 .to_owned(),
         );
 
@@ -1,10 +1,12 @@
-use crate::ast::{module_tree, ItemPath, ModuleUse};
+use crate::ast::{ItemPath, ModuleUse, Visibility};
+use crate::rule::naming::NameConvention;
 use crate::rule::{
     ArchRuleBuilder, Assertion, Condition, ConditionBuilder, ConditionConjunctionBuilder,
     DependencyPredicateConjunctionBuilder, PredicateBuilder, PredicateConjunctionBuilder, Subject,
 };
 use crate::{ExludeModules, ModuleTree};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
 
 mod check;
@@ -28,11 +30,11 @@ mod report;
 pub struct Modules;
 
 #[derive(Default, Debug)]
-pub struct ModuleMatches(pub HashMap<&'static ItemPath, &'static ModuleTree>);
+pub struct ModuleMatches(pub BTreeMap<&'static ItemPath, &'static ModuleTree>);
 
 #[derive(Default, Debug)]
 pub struct ModuleDependencies(
-    pub HashMap<&'static ItemPath, (&'static PathBuf, &'static Vec<ModuleUse>)>,
+    pub BTreeMap<&'static ItemPath, (&'static PathBuf, &'static Vec<ModuleUse>)>,
 );
 
 impl ModuleMatches {
@@ -55,12 +57,12 @@ impl Condition for ConditionToken {}
 impl Assertion for AssertionToken {}
 
 impl Subject for ModuleMatches {
-    fn init(filters: &ExludeModules<'static>) -> Self {
-        module_tree().flatten(filters)
+    fn init(tree: &'static ModuleTree, filters: &ExludeModules<'static>) -> Self {
+        tree.flatten(filters)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ConditionToken {
     AreDeclaredPublic,
     ResidesInAModule(String),
@@ -69,12 +71,13 @@ pub enum ConditionToken {
     HaveSimpleName(String),
     HaveSimpleEndingWith(String),
     HaveSimpleStartingWith(String),
+    AreGatedByFeature(String),
     And,
     Or,
     Should,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum AssertionToken {
     SimpleAssertion(SimpleAssertions),
     Conjunction(AssertionConjunction),
@@ -92,12 +95,59 @@ pub enum DependencyAssertion {
     That,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum SimpleAssertions {
     BePublic,
     BePrivate,
     HaveSimpleName(String),
     NotHaveSimpleName(String),
+    BeFeatureGated,
+    BeGatedByFeature(String),
+    OnlyUseDeclaredFeatures,
+    HaveAtMostItems(usize),
+    HaveAtMostLines(usize),
+    OnlyHaveFunctionsWithAtMostParameters(usize),
+    OnlyHaveFunctionsWithAtMostLines(usize),
+    HaveInstabilityBelow(f64),
+    HaveAbstractnessAbove(f64),
+    HaveDistanceFromMainSequenceBelow(f64),
+    HaveName(NameConvention),
+    BeCrateVisible,
+    HaveVisibilityAtMost(Visibility),
+    NotBeMoreVisibleThanParentModule,
+}
+
+/// How the size of a module is measured by the `have_at_most_*` assertions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ModuleSize {
+    Lines,
+    Items,
+}
+
+impl fmt::Display for ModuleSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleSize::Lines => write!(f, "lines"),
+            ModuleSize::Items => write!(f, "items"),
+        }
+    }
+}
+
+/// How the size of a function is measured by the `only_have_functions_with_at_most_*`
+/// assertions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FunctionSize {
+    Lines,
+    Parameters,
+}
+
+impl fmt::Display for FunctionSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FunctionSize::Lines => write!(f, "lines"),
+            FunctionSize::Parameters => write!(f, "parameters"),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -146,6 +196,15 @@ impl ModuleConditionBuilder {
             .push_front(ConditionToken::HaveSimpleName(name.to_owned()));
         ConditionConjunctionBuilder(self.0)
     }
+
+    /// Modules only compiled when `feature` is enabled, by their own `cfg` attribute or the
+    /// one of an enclosing module.
+    pub fn are_gated_by_feature(mut self, feature: &str) -> ModuleConditionConjunctionBuilder {
+        self.0
+            .conditions
+            .push_front(ConditionToken::AreGatedByFeature(feature.to_owned()));
+        ConditionConjunctionBuilder(self.0)
+    }
 }
 
 impl ModuleConditionConjunctionBuilder {
@@ -175,6 +234,16 @@ impl ModulePredicateBuilder {
         PredicateConjunctionBuilder(self.0)
     }
 
+    /// Predicate matching module whose name is `snake_case`.
+    pub fn have_snake_case_name(mut self) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::SnakeCase,
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
     pub fn does_not_have_simple_name(mut self, name: &str) -> ModulePredicateConjunctionBuilder {
         self.0
             .assertions
@@ -191,6 +260,38 @@ impl ModulePredicateBuilder {
         PredicateConjunctionBuilder(self.0)
     }
 
+    /// Predicate matching module declared `pub(crate)`.
+    pub fn be_crate_visible(mut self) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::BeCrateVisible,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching module visible to no more code than with `max`, e.g.
+    /// `Visibility::Crate` accepts `pub(crate)`, `pub(super)` and private module.
+    pub fn have_visibility_at_most(mut self, max: Visibility) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveVisibilityAtMost(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching module not declared more visible than their parent module, the
+    /// crate root counts as public.
+    pub fn not_be_more_visible_than_parent_module(mut self) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::NotBeMoreVisibleThanParentModule,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
     pub fn be_private(mut self) -> ModulePredicateConjunctionBuilder {
         self.0
             .assertions
@@ -198,6 +299,165 @@ impl ModulePredicateBuilder {
         PredicateConjunctionBuilder(self.0)
     }
 
+    /// Modules should only be compiled when some cargo feature is enabled.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::modules::Modules;
+    ///
+    /// let result = Modules::that(ExludeModules::default())
+    ///     .reside_in_a_module("archunit_rs::report::*")
+    ///     .should()
+    ///     .be_feature_gated()
+    ///     .evaluate();
+    ///
+    /// assert!(!result.is_success());
+    /// ```
+    pub fn be_feature_gated(mut self) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::BeFeatureGated,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Modules should only be compiled when `feature` is enabled.
+    pub fn be_gated_by_feature(mut self, feature: &str) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::BeGatedByFeature(feature.to_owned()),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// The modules, structs, enums, impl blocks and functions declared in the modules should
+    /// only be gated by features declared in the `[features]` table of the crate manifest, or
+    /// by optional dependencies. A missing or invalid manifest fails the assertion.
+    pub fn only_use_declared_features(mut self) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::OnlyUseDeclaredFeatures,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Modules should declare at most `max` items, counting structs, enums, traits, impl
+    /// blocks, free functions and submodules.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::modules::Modules;
+    ///
+    /// Modules::that(ExludeModules::cfg_test())
+    ///     .reside_in_a_module("archunit_rs::rule::*")
+    ///     .should()
+    ///     .have_at_most_items(50)
+    ///     .and_should()
+    ///     .have_at_most_lines(2000)
+    ///     .check();
+    /// ```
+    pub fn have_at_most_items(mut self, max: usize) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveAtMostItems(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Modules should span at most `max` lines, their whole file or their inline block.
+    pub fn have_at_most_lines(mut self, max: usize) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveAtMostLines(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Free functions and methods declared in the modules should take at most `max`
+    /// parameters, `self` included.
+    pub fn only_have_functions_with_at_most_parameters(
+        mut self,
+        max: usize,
+    ) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::OnlyHaveFunctionsWithAtMostParameters(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Free functions and methods declared in the modules should span at most `max` lines,
+    /// from their signature to the end of their body.
+    pub fn only_have_functions_with_at_most_lines(
+        mut self,
+        max: usize,
+    ) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::OnlyHaveFunctionsWithAtMostLines(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Modules should have an instability, `Ce / (Ca + Ce)`, strictly below `threshold`, see
+    /// [`metrics`](crate::metrics).
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::modules::Modules;
+    ///
+    /// Modules::that(ExludeModules::cfg_test())
+    ///     .reside_in_a_module("archunit_rs::ast")
+    ///     .should()
+    ///     .have_instability_below(0.9)
+    ///     .check();
+    /// ```
+    pub fn have_instability_below(mut self, threshold: f64) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveInstabilityBelow(threshold),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Modules should have a ratio of traits to declared types strictly above `threshold`.
+    pub fn have_abstractness_above(mut self, threshold: f64) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveAbstractnessAbove(threshold),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Modules should have a distance from the main sequence, `|A + I - 1|`, strictly below
+    /// `threshold`.
+    pub fn have_distance_from_main_sequence_below(
+        mut self,
+        threshold: f64,
+    ) -> ModulePredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveDistanceFromMainSequenceBelow(threshold),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
     pub fn only_have_dependency_module(mut self) -> ModuleDependencyPredicateConjunctionBuilder {
         self.0
             .assertions
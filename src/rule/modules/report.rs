@@ -2,7 +2,7 @@ use crate::assertion_result::{get_code_sample_region, get_relative_location};
 use crate::ast::{CodeSpan, Visibility};
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug, Diagnostic)]
@@ -40,6 +40,30 @@ pub(crate) enum ModuleRuleViolation {
         #[source_code]
         src: NamedSource,
     },
+    #[error("Module '{module_name}' name should {convention}")]
+    #[diagnostic(help("Try renaming '{module_name}' accordingly"))]
+    NameConvention {
+        module_name: String,
+        convention: String,
+        location: String,
+        #[label("does not {convention}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Module '{module_name}' should be {expected}, found {found}")]
+    #[diagnostic(help("Try declaring '{module_name}' with `{allowed}` visibility"))]
+    VisibilityLevel {
+        module_name: String,
+        expected: String,
+        found: String,
+        allowed: String,
+        location: String,
+        #[label("{found}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
     #[error("Module '{module_name}' name should not match pattern '{pattern}'")]
     #[diagnostic(help("Try renaming '{module_name}' accordingly"))]
     DoesNotHaveNameMatching {
@@ -75,6 +99,70 @@ pub(crate) enum ModuleRuleViolation {
         #[source_code]
         src: NamedSource,
     },
+    #[error("Module '{module_name}' should be {gate}")]
+    #[diagnostic(help("Try adding `#[cfg(feature = \"...\")]` to the module declaration"))]
+    BeFeatureGated {
+        module_name: String,
+        gate: String,
+        location: String,
+        #[label("not gated")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("'{item_name}' is gated by undeclared feature '{feature}'")]
+    #[diagnostic(help("Declare '{feature}' in the [features] table of {manifest}"))]
+    UndeclaredFeature {
+        item_name: String,
+        feature: String,
+        manifest: String,
+        location: String,
+        #[label("gated by '{feature}'")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Module '{module_name}' has {actual} {measure}, at most {allowed} allowed")]
+    #[diagnostic(help("Try splitting '{module_name}' into smaller modules"))]
+    HaveAtMost {
+        module_name: String,
+        measure: String,
+        actual: usize,
+        allowed: usize,
+        location: String,
+        #[label("{actual} {measure}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Function '{function_name}' has {actual} {measure}, at most {allowed} allowed")]
+    #[diagnostic(help("{help}"))]
+    FunctionHaveAtMost {
+        function_name: String,
+        measure: String,
+        actual: usize,
+        allowed: usize,
+        help: String,
+        location: String,
+        #[label("{actual} {measure}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Module '{module_name}' has {metric} {actual}, expected {bound}")]
+    #[diagnostic(help("{details}"))]
+    MetricOutOfBounds {
+        module_name: String,
+        metric: String,
+        actual: String,
+        bound: String,
+        details: String,
+        location: String,
+        #[label("{metric} {actual}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
 }
 
 impl ModuleRuleViolation {
@@ -217,4 +305,220 @@ impl ModuleRuleViolation {
             src,
         }
     }
+
+    /// `feature` is `None` when any feature would do.
+    pub(crate) fn be_feature_gated(
+        span: CodeSpan,
+        location: &PathBuf,
+        module_name: String,
+        feature: Option<&str>,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample
+            .find(&module_name)
+            .expect("Module name should be present in code sample");
+        let span = (start_hint, module_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        let gate = match feature {
+            Some(feature) => format!("gated by feature '{feature}'"),
+            None => "feature gated".to_owned(),
+        };
+
+        ModuleRuleViolation::BeFeatureGated {
+            module_name,
+            gate,
+            location,
+            span,
+            src,
+        }
+    }
+
+    pub(crate) fn undeclared_feature(
+        span: CodeSpan,
+        location: &Path,
+        item_name: String,
+        feature: String,
+        manifest: &Path,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        // Skip the attributes the item name may appear in
+        let span = sample
+            .find(&format!(" {item_name}"))
+            .map(|start| (start + 1, item_name.len()))
+            .unwrap_or((0, sample.len()))
+            .into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+
+        ModuleRuleViolation::UndeclaredFeature {
+            item_name,
+            feature,
+            manifest: get_relative_location(manifest),
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// `ident` is `None` for the crate root, labelled at the top of its file.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn metric_out_of_bounds(
+        span: CodeSpan,
+        location: &Path,
+        module_name: String,
+        ident: Option<String>,
+        metric: &str,
+        actual: f64,
+        bound: crate::metrics::Bound,
+        metrics: &crate::metrics::ModuleMetrics,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let span = ident
+            .and_then(|ident| sample.find(&ident).map(|start| (start, ident.len())))
+            .unwrap_or((0, sample.len()))
+            .into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        let details = format!(
+            "Ca = {}, Ce = {}, {} trait(s) and {} struct(s) or enum(s) declared",
+            metrics.afferent_coupling,
+            metrics.efferent_coupling,
+            metrics.abstract_types,
+            metrics.concrete_types
+        );
+
+        ModuleRuleViolation::MetricOutOfBounds {
+            module_name,
+            metric: metric.to_owned(),
+            actual: format!("{actual:.2}"),
+            bound: bound.to_string(),
+            details,
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// `ident` is `None` for the crate root, labelled at the top of its file.
+    pub(crate) fn have_at_most(
+        span: CodeSpan,
+        location: &Path,
+        module_name: String,
+        ident: Option<String>,
+        measure: crate::rule::modules::ModuleSize,
+        actual: usize,
+        allowed: usize,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let span = ident
+            .and_then(|ident| sample.find(&ident).map(|start| (start, ident.len())))
+            .unwrap_or((0, sample.len()))
+            .into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+
+        ModuleRuleViolation::HaveAtMost {
+            module_name,
+            measure: measure.to_string(),
+            actual,
+            allowed,
+            location,
+            span,
+            src,
+        }
+    }
+
+    pub(crate) fn function_have_at_most(
+        span: CodeSpan,
+        location: &Path,
+        function_name: String,
+        ident: String,
+        measure: crate::rule::modules::FunctionSize,
+        actual: usize,
+        allowed: usize,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let span = sample
+            .find(&format!("fn {ident}"))
+            .map(|start| (start + 3, ident.len()))
+            .unwrap_or((0, sample.len()))
+            .into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        let help = match measure {
+            crate::rule::modules::FunctionSize::Lines => {
+                format!("Try extracting parts of '{ident}' into smaller functions")
+            }
+            crate::rule::modules::FunctionSize::Parameters => {
+                "Try grouping related parameters into a struct".to_owned()
+            }
+        };
+
+        ModuleRuleViolation::FunctionHaveAtMost {
+            function_name,
+            measure: measure.to_string(),
+            actual,
+            allowed,
+            help,
+            location,
+            span,
+            src,
+        }
+    }
+    pub(crate) fn name_convention(
+        span: CodeSpan,
+        location: &PathBuf,
+        module_name: String,
+        convention: &crate::rule::naming::NameConvention,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample
+            .find(&module_name)
+            .expect("Module name should be present in code sample");
+        let span = (start_hint, module_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        ModuleRuleViolation::NameConvention {
+            module_name,
+            convention: convention.to_string(),
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// `expected` describes the allowed visibility, `allowed` is the one to suggest.
+    pub(crate) fn visibility_level(
+        span: CodeSpan,
+        location: &PathBuf,
+        module_name: String,
+        expected: String,
+        found: &Visibility,
+        allowed: &Visibility,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample
+            .find(&module_name)
+            .expect("Module name should be present in code sample");
+        let span = (start_hint, module_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        ModuleRuleViolation::VisibilityLevel {
+            module_name,
+            expected,
+            found: found.to_string(),
+            allowed: allowed.to_string(),
+            location,
+            span,
+            src,
+        }
+    }
 }
@@ -0,0 +1,120 @@
+use regex::Regex;
+use std::fmt;
+
+/// A naming convention item names are checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameConvention {
+    /// The name matches the regular expression, anchors are not implied.
+    Regex(String),
+    StartingWith(String),
+    EndingWith(String),
+    /// `UpperCamelCase`, consecutive capitals like `HTTPClient` are accepted.
+    UpperCamelCase,
+    /// `snake_case`, digits are accepted after the first character.
+    SnakeCase,
+}
+
+impl fmt::Display for NameConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameConvention::Regex(pattern) => write!(f, "match regex '{pattern}'"),
+            NameConvention::StartingWith(prefix) => write!(f, "start with '{prefix}'"),
+            NameConvention::EndingWith(suffix) => write!(f, "end with '{suffix}'"),
+            NameConvention::UpperCamelCase => write!(f, "be UpperCamelCase"),
+            NameConvention::SnakeCase => write!(f, "be snake_case"),
+        }
+    }
+}
+
+impl NameConvention {
+    /// # Panics
+    ///
+    /// Panics if `pattern` is not a valid regular expression.
+    pub(crate) fn regex(pattern: &str) -> Self {
+        Self::try_regex(pattern)
+            .unwrap_or_else(|err| panic!("Invalid name pattern '{pattern}': {err}"))
+    }
+
+    pub(crate) fn try_regex(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(|_| NameConvention::Regex(pattern.to_owned()))
+    }
+
+    /// Compiles the convention once, to be applied to every name of a rule subject.
+    pub(crate) fn matcher(&self) -> impl Fn(&str) -> bool + '_ {
+        let regex = match self {
+            NameConvention::Regex(pattern) => Regex::new(pattern).ok(),
+            _ => None,
+        };
+
+        move |name: &str| {
+            let name = name.strip_prefix("r#").unwrap_or(name);
+            match self {
+                NameConvention::Regex(_) => {
+                    regex.as_ref().is_some_and(|regex| regex.is_match(name))
+                }
+                NameConvention::StartingWith(prefix) => name.starts_with(prefix.as_str()),
+                NameConvention::EndingWith(suffix) => name.ends_with(suffix.as_str()),
+                NameConvention::UpperCamelCase => is_upper_camel_case(name),
+                NameConvention::SnakeCase => is_snake_case(name),
+            }
+        }
+    }
+}
+
+fn is_upper_camel_case(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_uppercase) && name.chars().all(char::is_alphanumeric)
+}
+
+fn is_snake_case(name: &str) -> bool {
+    let name = name.trim_start_matches('_');
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && !name.contains("__")
+        && name
+            .chars()
+            .all(|c| c.is_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rule::naming::NameConvention;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_check_case_styles() {
+        let camel = NameConvention::UpperCamelCase;
+        let camel = camel.matcher();
+        let snake = NameConvention::SnakeCase;
+        let snake = snake.matcher();
+
+        assert_that!(camel("HTTPClient")).is_true();
+        assert_that!(camel("Http_Client")).is_false();
+        assert_that!(camel("httpClient")).is_false();
+        assert_that!(snake("http_client2")).is_true();
+        assert_that!(snake("r#type")).is_true();
+        assert_that!(snake("HttpClient")).is_false();
+        assert_that!(snake("http__client")).is_false();
+    }
+
+    #[test]
+    fn should_match_regex_and_affixes() {
+        let regex = NameConvention::regex("^Sql.*Repository$");
+
+        assert_that!(regex.matcher()("SqlOrderRepository")).is_true();
+        assert_that!(regex.matcher()("OrderRepository")).is_false();
+        assert_that!(NameConvention::EndingWith("Error".to_owned()).matcher()(
+            "IoError"
+        ))
+        .is_true();
+        assert_that!(NameConvention::StartingWith("Io".to_owned()).matcher()(
+            "FsError"
+        ))
+        .is_false();
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid name pattern")]
+    fn should_reject_invalid_regex() {
+        NameConvention::regex("(unclosed");
+    }
+}
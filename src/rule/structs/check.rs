@@ -1,13 +1,16 @@
 use crate::assertion_result::AssertionResult;
-use crate::ast::structs::Struct;
+use crate::ast::cfg::requires_feature;
+use crate::ast::structs::{Struct, StructKind};
+use crate::ast::{ItemPath, Visibility};
 use crate::rule::assertable::Assertable;
 use crate::rule::impl_block::impl_matches;
+use crate::rule::naming::NameConvention;
 use crate::rule::structs::reports::StructRuleViolation;
 use crate::rule::structs::{
     AssertionConjunction, AssertionToken, ConditionToken, SimpleAssertions, StructMatches,
     StructPredicateConjunctionBuilder,
 };
-use crate::rule::{ArchRule, CheckRule};
+use crate::rule::{parent_visibility, ArchRule, CheckRule};
 use std::collections::HashSet;
 use wildmatch::WildMatch;
 
@@ -72,6 +75,33 @@ impl Assertable<ConditionToken, AssertionToken, StructMatches>
                     match_against
                         .structs_that(|struct_| WildMatch::new(&pattern).matches(&struct_.ident))
                 }
+                ConditionToken::AreGatedByFeature(feature) => {
+                    self.assertion_results
+                        .push_expected(format!("are gated by feature '{feature}'"));
+                    let tree = self.tree;
+                    match_against.structs_that(|struct_| {
+                        let chain = tree.cfg_chain(&struct_.path.parent());
+                        requires_feature(chain.into_iter().chain(&struct_.cfg), &feature)
+                    })
+                }
+                ConditionToken::HaveFieldOfType(pattern) => {
+                    self.assertion_results
+                        .push_expected(format!("have a field of type '{pattern}'"));
+                    match_against.structs_that(|struct_| {
+                        struct_
+                            .fields
+                            .iter()
+                            .any(|field| field.has_type_matching(&pattern))
+                    })
+                }
+                ConditionToken::HaveKind(kind) => {
+                    self.assertion_results.push_expected(format!("are {kind}"));
+                    match_against.structs_that(|struct_| struct_.kind == kind)
+                }
+                ConditionToken::AreGeneric => {
+                    self.assertion_results.push_expected("are generic");
+                    match_against.structs_that(Struct::is_generic)
+                }
                 ConditionToken::ResidesInAModule(name) => {
                     self.assertion_results
                         .push_expected(format!("resides in a modules that match '{name}'"));
@@ -99,7 +129,7 @@ impl Assertable<ConditionToken, AssertionToken, StructMatches>
                 ConditionToken::Implement(trait_) => {
                     let expected = format!("implement {trait_}");
                     self.assertion_results.push_expected(&expected);
-                    let imps = impl_matches(&self.filters)
+                    let imps = impl_matches(self.tree, &self.filters)
                         .impl_that(|imp| matches!(&imp.trait_impl, Some(t) if t.contains(&trait_)));
                     let types = imps.types();
                     match_against.structs_that(|struct_| types.contains(&struct_.ident.as_str()))
@@ -137,6 +167,58 @@ impl Assertable<ConditionToken, AssertionToken, StructMatches>
                     }
                     SimpleAssertions::OnlyHavePrivateFields => self.assert_private_fields(),
                     SimpleAssertions::OnlyHavePublicFields => self.assert_public_fields(),
+                    SimpleAssertions::HaveAtMostFields(max) => self.assert_at_most_fields(max),
+                    SimpleAssertions::HaveFieldOfType(pattern) => {
+                        self.assert_field_of_type(&pattern)
+                    }
+                    SimpleAssertions::NotHaveFieldsOfTypeMatching(pattern) => {
+                        self.assert_no_field_of_type(&pattern)
+                    }
+                    SimpleAssertions::OnlyHaveFieldsOfTypesInModules(modules) => {
+                        self.assert_field_types_in_modules(&modules)
+                    }
+                    SimpleAssertions::HaveKind(kind) => self.assert_kind(kind),
+                    SimpleAssertions::HaveName(convention) => self.assert_name(&convention),
+                    SimpleAssertions::BeCrateVisible => {
+                        self.assert_visibility_level("be crate visible", |vis, _| {
+                            (!vis.is_crate_visible())
+                                .then(|| ("pub(crate)".to_owned(), Visibility::Crate))
+                        })
+                    }
+                    SimpleAssertions::HaveVisibilityAtMost(max) => self.assert_visibility_level(
+                        &format!("have visibility at most {max}"),
+                        |vis, _| {
+                            (!vis.is_at_most(&max)).then(|| (format!("at most {max}"), max.clone()))
+                        },
+                    ),
+                    SimpleAssertions::NotBeMoreVisibleThanParentModule => self
+                        .assert_visibility_level(
+                            "not be more visible than parent module",
+                            |vis, parent| {
+                                (!vis.is_at_most(parent)).then(|| {
+                                    (format!("at most {parent} like its module"), parent.clone())
+                                })
+                            },
+                        ),
+                    SimpleAssertions::HaveExactlyFields(count) => self.assert_exactly_fields(count),
+                    SimpleAssertions::BeGeneric => self.assert_shape("be generic", |struct_| {
+                        (!struct_.is_generic()).then(|| "no generic parameters".to_owned())
+                    }),
+                    SimpleAssertions::NotBeGeneric => self
+                        .assert_shape("not be generic", |struct_| {
+                            struct_.is_generic().then(|| struct_.generic_params())
+                        }),
+                    SimpleAssertions::NotHaveLifetimes => {
+                        self.assert_shape("not have lifetimes", |struct_| {
+                            (!struct_.lifetimes.is_empty()).then(|| struct_.lifetimes.join(", "))
+                        })
+                    }
+                    SimpleAssertions::NotHaveWhereClause => {
+                        self.assert_shape("not have a where clause", |struct_| {
+                            (!struct_.where_predicates.is_empty())
+                                .then(|| format!("where {}", struct_.where_predicates.join(", ")))
+                        })
+                    }
                 },
                 AssertionToken::Conjunction(a) => match a {
                     AssertionConjunction::AndShould => {
@@ -169,6 +251,10 @@ impl Assertable<ConditionToken, AssertionToken, StructMatches>
         &self.assertion_results
     }
 
+    fn take_assertion_results(&mut self) -> AssertionResult {
+        std::mem::replace(&mut self.assertion_results, AssertionResult::new())
+    }
+
     fn has_conditions(&self) -> bool {
         !self.conditions.is_empty()
     }
@@ -179,19 +265,22 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
         self.assertion_results.push_expected("be public");
         let non_public_struct = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| !struct_.is_public())
             .collect::<Vec<_>>();
 
         for struct_ in &non_public_struct {
-            self.assertion_results
-                .push_actual(StructRuleViolation::be_public(
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::be_public(
                     struct_.span,
                     &struct_.real_path,
                     struct_.ident.clone(),
-                    struct_.visibility,
-                ));
+                    struct_.visibility.clone(),
+                ),
+            );
         }
         non_public_struct.is_empty()
     }
@@ -200,19 +289,22 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
         self.assertion_results.push_expected("be private");
         let public_structs = self
             .subject
-            .0
-            .iter()
-            .filter(|struct_| struct_.is_public())
+            .iter_sorted()
+            .filter(|struct_| !struct_.visibility.is_private())
             .collect::<Vec<_>>();
 
         for struct_ in &public_structs {
-            self.assertion_results
-                .push_actual(StructRuleViolation::be_private(
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::be_private(
                     struct_.span,
                     &struct_.real_path,
                     struct_.ident.clone(),
-                    struct_.visibility,
-                ));
+                    struct_.visibility.clone(),
+                ),
+            );
         }
 
         public_structs.is_empty()
@@ -223,19 +315,83 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
             .push_expected(format!("have simple name '{name}'"));
         let struct_with_non_matching_name = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| struct_.ident != name)
             .collect::<Vec<_>>();
 
         for struct_ in &struct_with_non_matching_name {
-            self.assertion_results
-                .push_actual(StructRuleViolation::have_name_matching(
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::have_name_matching(
                     struct_.span,
                     &struct_.real_path,
                     struct_.ident.clone(),
                     name.to_owned(),
-                ));
+                ),
+            );
+        }
+
+        struct_with_non_matching_name.is_empty()
+    }
+
+    /// `violation` gets the struct visibility and the one of its parent module, and returns what
+    /// is expected and the visibility to suggest when they do not fit.
+    fn assert_visibility_level<F>(&mut self, expected: &str, violation: F) -> bool
+    where
+        F: Fn(&Visibility, &Visibility) -> Option<(String, Visibility)>,
+    {
+        self.assertion_results.push_expected(expected);
+        let mut success = true;
+
+        for struct_ in self.subject.iter_sorted() {
+            let parent = parent_visibility(self.tree, &struct_.path.parent());
+            let Some((expected, allowed)) = violation(&struct_.visibility, &parent) else {
+                continue;
+            };
+
+            success = false;
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::visibility_level(
+                    struct_.span,
+                    &struct_.real_path,
+                    struct_.ident.clone(),
+                    expected,
+                    &struct_.visibility,
+                    &allowed,
+                ),
+            );
+        }
+
+        success
+    }
+
+    fn assert_name(&mut self, convention: &NameConvention) -> bool {
+        self.assertion_results
+            .push_expected(format!("have a name that should {convention}"));
+        let matches = convention.matcher();
+        let struct_with_non_matching_name = self
+            .subject
+            .iter_sorted()
+            .filter(|struct_| !matches(&struct_.ident))
+            .collect::<Vec<_>>();
+
+        for struct_ in &struct_with_non_matching_name {
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::name_convention(
+                    struct_.span,
+                    &struct_.real_path,
+                    struct_.ident.clone(),
+                    convention,
+                ),
+            );
         }
 
         struct_with_non_matching_name.is_empty()
@@ -247,19 +403,22 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
 
         let struct_without_expected_derive = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| !struct_.derives.contains(trait_))
             .collect::<Vec<_>>();
 
         for struct_ in &struct_without_expected_derive {
-            self.assertion_results
-                .push_actual(StructRuleViolation::derive(
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::derive(
                     struct_.span,
                     &struct_.real_path,
                     struct_.ident.clone(),
                     trait_.to_string(),
-                ));
+                ),
+            );
         }
 
         struct_without_expected_derive.is_empty()
@@ -269,13 +428,13 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
         self.assertion_results
             .push_expected(format!("implement '{trait_}'"));
 
+        let impls = impl_matches(self.tree, &self.filters);
         let struct_without_expected_impl = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| {
-                let imp_for_type = impl_matches(&self.filters)
-                    .impl_that(|imp| imp.self_ty.name() == struct_.ident.as_str());
+                let imp_for_type =
+                    impls.impl_that(|imp| imp.self_ty.name() == struct_.ident.as_str());
 
                 let imp_for_type = imp_for_type
                     .impl_that(|imp| matches!(&imp.trait_impl, Some(t) if t.contains(trait_)));
@@ -285,13 +444,17 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
             .collect::<Vec<_>>();
 
         for struct_ in &struct_without_expected_impl {
-            self.assertion_results
-                .push_actual(StructRuleViolation::implement(
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::implement(
                     struct_.span,
                     &struct_.real_path,
                     struct_.ident.clone(),
                     trait_.to_string(),
-                ));
+                ),
+            );
         }
 
         struct_without_expected_impl.is_empty()
@@ -303,18 +466,17 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
 
         let derive_set = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| !struct_.derives.contains(trait_))
             .collect::<HashSet<_>>();
 
+        let impls = impl_matches(self.tree, &self.filters);
         let impl_set = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| {
-                let imp_for_type = impl_matches(&self.filters)
-                    .impl_that(|imp| imp.self_ty.name() == struct_.ident.as_str());
+                let imp_for_type =
+                    impls.impl_that(|imp| imp.self_ty.name() == struct_.ident.as_str());
 
                 let imp_for_type = imp_for_type
                     .impl_that(|imp| matches!(&imp.trait_impl, Some(t) if t.contains(trait_)));
@@ -323,15 +485,23 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
             })
             .collect::<HashSet<_>>();
 
-        let intersection: Vec<&&&Struct> = impl_set.intersection(&derive_set).collect();
+        let intersection: Vec<&Struct> = self
+            .subject
+            .iter_sorted()
+            .filter(|struct_| impl_set.contains(struct_) && derive_set.contains(struct_))
+            .collect();
         for struct_ in &intersection {
-            self.assertion_results
-                .push_actual(StructRuleViolation::implement_or_derive(
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::implement_or_derive(
                     struct_.span,
                     &struct_.real_path,
                     struct_.ident.clone(),
                     trait_.to_string(),
-                ));
+                ),
+            );
         }
 
         intersection.is_empty()
@@ -342,8 +512,7 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
             .push_expected("only have private fields");
         let struct_with_only_public_fields = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| !struct_.fields.is_empty())
             .filter(|struct_| !struct_.has_non_public_field())
             .collect::<Vec<_>>();
@@ -355,7 +524,14 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
                 &struct_.fields,
             )
             .into_iter()
-            .for_each(|report| self.assertion_results.push_actual(report));
+            .for_each(|(span, report)| {
+                self.assertion_results.push_actual(
+                    &struct_.path.parent(),
+                    &struct_.real_path,
+                    span,
+                    report,
+                )
+            });
         }
         struct_with_only_public_fields.is_empty()
     }
@@ -365,8 +541,7 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
             .push_expected("only have public fields");
         let struct_with_non_public_fields = self
             .subject
-            .0
-            .iter()
+            .iter_sorted()
             .filter(|struct_| !struct_.fields.is_empty())
             .filter(|struct_| struct_.has_non_public_field())
             .collect::<Vec<_>>();
@@ -378,18 +553,240 @@ impl ArchRule<ConditionToken, AssertionToken, StructMatches> {
                 &struct_.fields,
             )
             .into_iter()
-            .for_each(|report| self.assertion_results.push_actual(report));
+            .for_each(|(span, report)| {
+                self.assertion_results.push_actual(
+                    &struct_.path.parent(),
+                    &struct_.real_path,
+                    span,
+                    report,
+                )
+            });
         }
 
         struct_with_non_public_fields.is_empty()
     }
+
+    fn assert_field_of_type(&mut self, pattern: &str) -> bool {
+        self.assertion_results
+            .push_expected(format!("have a field of type '{pattern}'"));
+        let struct_without_field = self
+            .subject
+            .iter_sorted()
+            .filter(|struct_| {
+                !struct_
+                    .fields
+                    .iter()
+                    .any(|field| field.has_type_matching(pattern))
+            })
+            .collect::<Vec<_>>();
+
+        for struct_ in &struct_without_field {
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::have_field_of_type(
+                    struct_.span,
+                    &struct_.real_path,
+                    struct_.ident.clone(),
+                    pattern.to_owned(),
+                ),
+            );
+        }
+
+        struct_without_field.is_empty()
+    }
+
+    fn assert_no_field_of_type(&mut self, pattern: &str) -> bool {
+        self.assertion_results
+            .push_expected(format!("not have fields of type matching '{pattern}'"));
+        let mut success = true;
+
+        for struct_ in self.subject.iter_sorted() {
+            let fields = struct_
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| field.has_type_matching(pattern));
+
+            for (idx, field) in fields {
+                success = false;
+                self.assertion_results.push_actual(
+                    &struct_.path.parent(),
+                    &struct_.real_path,
+                    field.span,
+                    StructRuleViolation::field_type(
+                        &struct_.real_path,
+                        struct_.ident.clone(),
+                        idx,
+                        field,
+                        format!("matching '{pattern}'"),
+                    ),
+                );
+            }
+        }
+
+        success
+    }
+
+    fn assert_field_types_in_modules(&mut self, modules: &[String]) -> bool {
+        self.assertion_results.push_expected(format!(
+            "only have fields of types in modules {}",
+            modules.join(", ")
+        ));
+        let mut success = true;
+
+        for struct_ in self.subject.iter_sorted() {
+            for (idx, field) in struct_.fields.iter().enumerate() {
+                let outside = field.type_paths.iter().find(|path| {
+                    path.contains("::")
+                        && !modules.iter().any(|module| {
+                            ItemPath::new((*path).to_owned()).match_struct_path(module)
+                        })
+                });
+
+                let Some(outside) = outside else {
+                    continue;
+                };
+
+                success = false;
+                self.assertion_results.push_actual(
+                    &struct_.path.parent(),
+                    &struct_.real_path,
+                    field.span,
+                    StructRuleViolation::field_type(
+                        &struct_.real_path,
+                        struct_.ident.clone(),
+                        idx,
+                        field,
+                        format!("referring to '{outside}' outside of {}", modules.join(", ")),
+                    ),
+                );
+            }
+        }
+
+        success
+    }
+
+    fn assert_kind(&mut self, kind: StructKind) -> bool {
+        self.assertion_results.push_expected(format!("be {kind}"));
+        let struct_of_other_kind = self
+            .subject
+            .iter_sorted()
+            .filter(|struct_| struct_.kind != kind)
+            .collect::<Vec<_>>();
+
+        for struct_ in &struct_of_other_kind {
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::shape(
+                    struct_.span,
+                    &struct_.real_path,
+                    struct_.ident.clone(),
+                    format!("be {kind}"),
+                    struct_.kind.to_string(),
+                ),
+            );
+        }
+
+        struct_of_other_kind.is_empty()
+    }
+
+    /// `violation` describes what the struct has instead of the expected shape.
+    fn assert_shape<F>(&mut self, expected: &str, violation: F) -> bool
+    where
+        F: Fn(&Struct) -> Option<String>,
+    {
+        self.assertion_results.push_expected(expected);
+        let mut success = true;
+
+        for struct_ in self.subject.iter_sorted() {
+            let Some(found) = violation(struct_) else {
+                continue;
+            };
+
+            success = false;
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::shape(
+                    struct_.span,
+                    &struct_.real_path,
+                    struct_.ident.clone(),
+                    expected.to_owned(),
+                    found,
+                ),
+            );
+        }
+
+        success
+    }
+
+    fn assert_exactly_fields(&mut self, count: usize) -> bool {
+        self.assertion_results
+            .push_expected(format!("have exactly {count} fields"));
+        let struct_with_other_field_count = self
+            .subject
+            .iter_sorted()
+            .filter(|struct_| struct_.fields.len() != count)
+            .collect::<Vec<_>>();
+
+        for struct_ in &struct_with_other_field_count {
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::shape(
+                    struct_.span,
+                    &struct_.real_path,
+                    struct_.ident.clone(),
+                    format!("have exactly {count} fields"),
+                    format!("{} fields", struct_.fields.len()),
+                ),
+            );
+        }
+
+        struct_with_other_field_count.is_empty()
+    }
+
+    fn assert_at_most_fields(&mut self, max: usize) -> bool {
+        self.assertion_results
+            .push_expected(format!("have at most {max} fields"));
+        let struct_with_too_many_fields = self
+            .subject
+            .iter_sorted()
+            .filter(|struct_| struct_.fields.len() > max)
+            .collect::<Vec<_>>();
+
+        for struct_ in &struct_with_too_many_fields {
+            self.assertion_results.push_actual(
+                &struct_.path.parent(),
+                &struct_.real_path,
+                struct_.span,
+                StructRuleViolation::have_at_most_fields(
+                    struct_.span,
+                    &struct_.real_path,
+                    struct_.ident.clone(),
+                    struct_.fields.len(),
+                    max,
+                ),
+            );
+        }
+
+        struct_with_too_many_fields.is_empty()
+    }
 }
 
 #[cfg(test)]
 mod condition_test {
+    use crate::assertion_result::AssertionResult;
     use crate::rule::structs::Structs;
     use crate::rule::{ArchRuleBuilder, CheckRule};
     use crate::ExludeModules;
+    use speculoos::prelude::*;
 
     #[test]
     #[should_panic]
@@ -536,4 +933,33 @@ mod condition_test {
             .implement("Subject")
             .check();
     }
+
+    #[test]
+    fn violations_should_be_sorted_in_source_order() {
+        let locations = |result: AssertionResult| {
+            result
+                .actual
+                .iter()
+                .map(|violation| (violation.location.clone(), violation.span))
+                .collect::<Vec<_>>()
+        };
+
+        let first = locations(
+            Structs::all_should(ExludeModules::default())
+                .be_private()
+                .evaluate(),
+        );
+        let second = locations(
+            Structs::all_should(ExludeModules::default())
+                .be_private()
+                .evaluate(),
+        );
+
+        let mut sorted = first.clone();
+        sorted.sort();
+
+        assert_that!(first).is_not_empty();
+        assert_that!(first).is_equal_to(&second);
+        assert_that!(first).is_equal_to(&sorted);
+    }
 }
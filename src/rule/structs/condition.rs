@@ -9,9 +9,12 @@ impl ModuleTree {
     ) -> StructMatches {
         let mut structs = HashSet::new();
 
-        self.structs.iter().for_each(|struct_| {
-            structs.insert(struct_);
-        });
+        self.structs
+            .iter()
+            .filter(|struct_| filters.is_enabled(struct_.cfg.as_ref()))
+            .for_each(|struct_| {
+                structs.insert(struct_);
+            });
 
         self.submodules
             .iter()
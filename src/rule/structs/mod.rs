@@ -1,10 +1,12 @@
-use crate::ast::module_tree;
-use crate::ast::structs::Struct;
+use crate::ast::structs::{Struct, StructKind};
+use crate::ast::Visibility;
+use crate::rule::naming::NameConvention;
 use crate::rule::{
     ArchRuleBuilder, Assertion, Condition, ConditionBuilder, ConditionConjunctionBuilder,
     PredicateBuilder, PredicateConjunctionBuilder, Subject,
 };
 use crate::ExludeModules;
+use crate::ModuleTree;
 use std::collections::HashSet;
 
 pub mod check;
@@ -52,6 +54,14 @@ impl StructMatches {
     pub fn extends(&mut self, other: StructMatches) {
         self.0.extend(other.0)
     }
+
+    /// Iterate over matching structs in source order (file path, then line).
+    pub fn iter_sorted(&self) -> impl Iterator<Item = &'static Struct> {
+        let mut structs: Vec<&'static Struct> = self.0.iter().copied().collect();
+        structs
+            .sort_by(|a, b| (&a.real_path, a.span, &a.path).cmp(&(&b.real_path, b.span, &b.path)));
+        structs.into_iter()
+    }
 }
 
 impl ArchRuleBuilder<ConditionToken, AssertionToken, StructMatches> for Structs {}
@@ -75,12 +85,12 @@ impl Condition for ConditionToken {}
 impl Assertion for AssertionToken {}
 
 impl Subject for StructMatches {
-    fn init(filters: &ExludeModules<'static>) -> Self {
-        module_tree().flatten_structs(filters)
+    fn init(tree: &'static ModuleTree, filters: &ExludeModules<'static>) -> Self {
+        tree.flatten_structs(filters)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ConditionToken {
     AreDeclaredPublic,
     ResidesInAModule(String),
@@ -89,6 +99,10 @@ pub enum ConditionToken {
     HaveNameMatching(String),
     Derives(String),
     Implement(String),
+    AreGatedByFeature(String),
+    HaveFieldOfType(String),
+    HaveKind(StructKind),
+    AreGeneric,
     And,
     Or,
     Should,
@@ -110,6 +124,20 @@ pub enum SimpleAssertions {
     ImplementOrDerive(String),
     OnlyHavePrivateFields,
     OnlyHavePublicFields,
+    HaveAtMostFields(usize),
+    HaveFieldOfType(String),
+    NotHaveFieldsOfTypeMatching(String),
+    OnlyHaveFieldsOfTypesInModules(Vec<String>),
+    HaveKind(StructKind),
+    HaveExactlyFields(usize),
+    HaveName(NameConvention),
+    BeGeneric,
+    NotBeGeneric,
+    NotHaveLifetimes,
+    NotHaveWhereClause,
+    BeCrateVisible,
+    HaveVisibilityAtMost(Visibility),
+    NotBeMoreVisibleThanParentModule,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -151,6 +179,15 @@ impl StructConditionBuilder {
         ConditionConjunctionBuilder(self.0)
     }
 
+    /// filter struct only compiled when `feature` is enabled, by its own `cfg` attribute or
+    /// the one of an enclosing module
+    pub fn are_gated_by_feature(mut self, feature: &str) -> StructConditionConjunctionBuilder {
+        self.0
+            .conditions
+            .push_front(ConditionToken::AreGatedByFeature(feature.to_owned()));
+        ConditionConjunctionBuilder(self.0)
+    }
+
     /// filter struct with the given name
     pub fn have_name_matching(mut self, pattern: &str) -> StructConditionConjunctionBuilder {
         self.0
@@ -174,6 +211,46 @@ impl StructConditionBuilder {
             .push_front(ConditionToken::Implement(trait_name.to_owned()));
         ConditionConjunctionBuilder(self.0)
     }
+
+    /// filter unit structs, like `struct Marker;`
+    pub fn are_unit_structs(mut self) -> StructConditionConjunctionBuilder {
+        self.0
+            .conditions
+            .push_front(ConditionToken::HaveKind(StructKind::Unit));
+        ConditionConjunctionBuilder(self.0)
+    }
+
+    /// filter tuple structs, like `struct Id(u64);`
+    pub fn are_tuple_structs(mut self) -> StructConditionConjunctionBuilder {
+        self.0
+            .conditions
+            .push_front(ConditionToken::HaveKind(StructKind::Tuple));
+        ConditionConjunctionBuilder(self.0)
+    }
+
+    /// filter structs with named fields, like `struct Point { x: f64, y: f64 }`
+    pub fn have_named_fields(mut self) -> StructConditionConjunctionBuilder {
+        self.0
+            .conditions
+            .push_front(ConditionToken::HaveKind(StructKind::Named));
+        ConditionConjunctionBuilder(self.0)
+    }
+
+    /// filter struct with type, const or lifetime parameters
+    pub fn are_generic(mut self) -> StructConditionConjunctionBuilder {
+        self.0.conditions.push_front(ConditionToken::AreGeneric);
+        ConditionConjunctionBuilder(self.0)
+    }
+
+    /// filter struct with a field whose type, or one of the types it refers to, matches
+    /// `pattern`. Imported names are resolved, `Mutex<T>` is matched as `std::sync::Mutex<T>`
+    /// when imported from `std::sync`.
+    pub fn have_field_of_type(mut self, pattern: &str) -> StructConditionConjunctionBuilder {
+        self.0
+            .conditions
+            .push_front(ConditionToken::HaveFieldOfType(pattern.to_owned()));
+        ConditionConjunctionBuilder(self.0)
+    }
 }
 
 impl StructConditionConjunctionBuilder {
@@ -207,6 +284,114 @@ impl StructPredicateBuilder {
         PredicateConjunctionBuilder(self.0)
     }
 
+    /// Predicate matching struct whose name matches the given regular expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is not a valid regular expression.
+    pub fn have_name_matching_regex(mut self, pattern: &str) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::regex(pattern),
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct whose name starts with `prefix`.
+    pub fn have_name_starting_with(mut self, prefix: &str) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::StartingWith(prefix.to_owned()),
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct whose name ends with `suffix`.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::structs::Structs;
+    ///
+    /// Structs::that(ExludeModules::cfg_test())
+    ///     .reside_in_a_module("archunit_rs::rule::*")
+    ///     .and()
+    ///     .implement("Subject")
+    ///     .should()
+    ///     .have_name_ending_with("Matches")
+    ///     .and_should()
+    ///     .have_upper_camel_case_name()
+    ///     .check();
+    /// ```
+    pub fn have_name_ending_with(mut self, suffix: &str) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::EndingWith(suffix.to_owned()),
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct whose name is `UpperCamelCase`.
+    pub fn have_upper_camel_case_name(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveName(
+                NameConvention::UpperCamelCase,
+            )));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct declared `pub(crate)`.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::structs::Structs;
+    ///
+    /// Structs::that(ExludeModules::cfg_test())
+    ///     .reside_in_a_module("archunit_rs::ast::types")
+    ///     .should()
+    ///     .be_crate_visible()
+    ///     .and_should()
+    ///     .not_be_more_visible_than_parent_module()
+    ///     .check();
+    /// ```
+    pub fn be_crate_visible(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::BeCrateVisible,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct visible to no more code than with `max`, e.g.
+    /// `Visibility::Crate` accepts `pub(crate)`, `pub(super)` and private struct.
+    pub fn have_visibility_at_most(mut self, max: Visibility) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveVisibilityAtMost(max),
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct not declared more visible than their parent module, the
+    /// crate root counts as public.
+    pub fn not_be_more_visible_than_parent_module(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::NotBeMoreVisibleThanParentModule,
+            ));
+        PredicateConjunctionBuilder(self.0)
+    }
+
     /// Predicate matching public structs.
     pub fn be_public(mut self) -> StructPredicateConjunctionBuilder {
         self.0
@@ -216,6 +401,9 @@ impl StructPredicateBuilder {
     }
 
     /// Predicate matching private structs.
+    ///
+    /// `pub(crate)`, `pub(super)` and `pub(in path)` are not private, use
+    /// [`Self::have_visibility_at_most`] to allow them.
     pub fn be_private(mut self) -> StructPredicateConjunctionBuilder {
         self.0
             .assertions
@@ -277,6 +465,178 @@ impl StructPredicateBuilder {
 
         PredicateConjunctionBuilder(self.0)
     }
+
+    /// Predicate matching struct with a field whose type, or one of the types it refers to,
+    /// matches `pattern`
+    pub fn have_field_of_type(mut self, pattern: &str) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveFieldOfType(pattern.to_owned()),
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct without any field whose type, or one of the types it refers
+    /// to, matches `pattern`
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::structs::Structs;
+    ///
+    /// Structs::that(ExludeModules::cfg_test())
+    ///     .reside_in_a_module("archunit_rs::rule::*")
+    ///     .should()
+    ///     .not_have_fields_of_type_matching("archunit_rs::report::*")
+    ///     .check();
+    /// ```
+    pub fn not_have_fields_of_type_matching(
+        mut self,
+        pattern: &str,
+    ) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::NotHaveFieldsOfTypeMatching(pattern.to_owned()),
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct whose field types only refer to types declared in modules
+    /// matching one of `modules`. Unqualified names like primitives, prelude types, generic
+    /// parameters or types of the same module are always allowed.
+    pub fn only_have_fields_of_types_in_modules(
+        mut self,
+        modules: &[&str],
+    ) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::OnlyHaveFieldsOfTypesInModules(
+                    modules.iter().map(|module| (*module).to_owned()).collect(),
+                ),
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching unit structs
+    pub fn be_unit_struct(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveKind(
+                StructKind::Unit,
+            )));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching tuple structs
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use archunit_rs::ExludeModules;
+    /// use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+    /// use archunit_rs::rule::structs::Structs;
+    ///
+    /// Structs::that(ExludeModules::cfg_test())
+    ///     .have_simple_name("ModuleMatches")
+    ///     .should()
+    ///     .be_tuple_struct()
+    ///     .and_should()
+    ///     .have_exactly_fields(1)
+    ///     .and_should()
+    ///     .not_be_generic()
+    ///     .check();
+    /// ```
+    pub fn be_tuple_struct(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveKind(
+                StructKind::Tuple,
+            )));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching structs with named fields
+    pub fn have_named_fields(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::HaveKind(
+                StructKind::Named,
+            )));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct with exactly `count` fields
+    pub fn have_exactly_fields(mut self, count: usize) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveExactlyFields(count),
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct with type, const or lifetime parameters
+    pub fn be_generic(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(SimpleAssertions::BeGeneric));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct without type, const or lifetime parameters
+    pub fn not_be_generic(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::NotBeGeneric,
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct without lifetime parameters
+    pub fn not_have_lifetimes(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::NotHaveLifetimes,
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct without a where clause
+    pub fn not_have_where_clause(mut self) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::NotHaveWhereClause,
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
+
+    /// Predicate matching struct with at most `max` fields
+    pub fn have_at_most_fields(mut self, max: usize) -> StructPredicateConjunctionBuilder {
+        self.0
+            .assertions
+            .push_front(AssertionToken::SimpleAssertion(
+                SimpleAssertions::HaveAtMostFields(max),
+            ));
+
+        PredicateConjunctionBuilder(self.0)
+    }
 }
 
 impl StructPredicateConjunctionBuilder {
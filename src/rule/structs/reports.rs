@@ -99,6 +99,78 @@ if that is not possible write the impl block `impl {trait_name} for {struct_name
         #[source_code]
         src: NamedSource,
     },
+    #[error("Struct '{struct_name}' name should {convention}")]
+    #[diagnostic(help("Try renaming '{struct_name}' accordingly"))]
+    NameConvention {
+        struct_name: String,
+        convention: String,
+        location: String,
+        #[label("does not {convention}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Struct '{struct_name}' should have a field of type '{pattern}'")]
+    #[diagnostic(help("Try adding a field of type '{pattern}' to '{struct_name}'"))]
+    HaveFieldOfType {
+        struct_name: String,
+        pattern: String,
+        location: String,
+        #[label("no field of type '{pattern}'")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Field '{field_name}' of struct '{struct_name}' has type '{type_name}' {reason}")]
+    #[diagnostic(help("Try removing the dependency of '{struct_name}' on '{type_name}'"))]
+    FieldType {
+        struct_name: String,
+        field_name: String,
+        type_name: String,
+        reason: String,
+        location: String,
+        #[label("forbidden type")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Struct '{struct_name}' should {expected}")]
+    #[diagnostic(help("Try changing the declaration of '{struct_name}' accordingly"))]
+    Shape {
+        struct_name: String,
+        expected: String,
+        location: String,
+        #[label("found {found}")]
+        span: SourceSpan,
+        found: String,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Struct '{struct_name}' should be {expected}, found {found}")]
+    #[diagnostic(help("Try declaring '{struct_name}' with `{allowed}` visibility"))]
+    VisibilityLevel {
+        struct_name: String,
+        expected: String,
+        found: String,
+        allowed: String,
+        location: String,
+        #[label("{found}")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
+    #[error("Struct '{struct_name}' has {actual} fields, at most {allowed} allowed")]
+    #[diagnostic(help("Try grouping related fields of '{struct_name}' into their own struct"))]
+    HaveAtMostFields {
+        struct_name: String,
+        actual: usize,
+        allowed: usize,
+        location: String,
+        #[label("{actual} fields")]
+        span: SourceSpan,
+        #[source_code]
+        src: NamedSource,
+    },
 }
 
 impl StructRuleViolation {
@@ -233,7 +305,7 @@ impl StructRuleViolation {
         location: &PathBuf,
         struct_name: String,
         struct_fields: &[Field],
-    ) -> Vec<Self> {
+    ) -> Vec<(CodeSpan, Self)> {
         let mut violations = vec![];
         let sample = fs::read_to_string(location).expect("path exists");
         let location = get_relative_location(location);
@@ -245,13 +317,16 @@ impl StructRuleViolation {
 
             let src = NamedSource::new(&location, sample);
             if field.visibility != Visibility::Public {
-                violations.push(StructRuleViolation::OnlyHavePublicFields {
-                    struct_name: struct_name.clone(),
-                    field_name: field.name.clone().unwrap_or_else(|| idx.to_string()),
-                    location: location.clone(),
-                    span,
-                    src,
-                })
+                violations.push((
+                    field.span,
+                    StructRuleViolation::OnlyHavePublicFields {
+                        struct_name: struct_name.clone(),
+                        field_name: field.name.clone().unwrap_or_else(|| idx.to_string()),
+                        location: location.clone(),
+                        span,
+                        src,
+                    },
+                ))
             }
         }
 
@@ -262,7 +337,7 @@ impl StructRuleViolation {
         location: &PathBuf,
         struct_name: String,
         struct_fields: &[Field],
-    ) -> Vec<Self> {
+    ) -> Vec<(CodeSpan, Self)> {
         let mut violations = vec![];
         let sample = fs::read_to_string(location).expect("path exists");
         let location = get_relative_location(location);
@@ -274,16 +349,157 @@ impl StructRuleViolation {
             let src = NamedSource::new(&location, sample);
 
             if field.visibility == Visibility::Public {
-                violations.push(StructRuleViolation::OnlyHavePrivateFields {
-                    struct_name: struct_name.clone(),
-                    field_name: field.name.clone().unwrap_or_else(|| idx.to_string()),
-                    location: location.clone(),
-                    span,
-                    src,
-                })
+                violations.push((
+                    field.span,
+                    StructRuleViolation::OnlyHavePrivateFields {
+                        struct_name: struct_name.clone(),
+                        field_name: field.name.clone().unwrap_or_else(|| idx.to_string()),
+                        location: location.clone(),
+                        span,
+                        src,
+                    },
+                ))
             }
         }
 
         violations
     }
+
+    pub(crate) fn have_at_most_fields(
+        span: CodeSpan,
+        location: &PathBuf,
+        struct_name: String,
+        actual: usize,
+        allowed: usize,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&struct_name).expect("struct name");
+        let span = (start_hint, struct_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        StructRuleViolation::HaveAtMostFields {
+            struct_name,
+            actual,
+            allowed,
+            location,
+            span,
+            src,
+        }
+    }
+
+    pub(crate) fn have_field_of_type(
+        span: CodeSpan,
+        location: &PathBuf,
+        struct_name: String,
+        pattern: String,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&struct_name).expect("struct name");
+        let span = (start_hint, struct_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        StructRuleViolation::HaveFieldOfType {
+            struct_name,
+            pattern,
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// `idx` names tuple struct fields.
+    pub(crate) fn field_type(
+        location: &PathBuf,
+        struct_name: String,
+        idx: usize,
+        field: &Field,
+        reason: String,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &field.span);
+        let span = get_field_span(field, &sample);
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        StructRuleViolation::FieldType {
+            struct_name,
+            field_name: field.name.clone().unwrap_or_else(|| idx.to_string()),
+            type_name: field.type_.clone(),
+            reason,
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// `found` describes the actual shape, e.g. `a tuple struct` or `<'a, T>`.
+    pub(crate) fn shape(
+        span: CodeSpan,
+        location: &PathBuf,
+        struct_name: String,
+        expected: String,
+        found: String,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&struct_name).expect("struct name");
+        let span = (start_hint, struct_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        StructRuleViolation::Shape {
+            struct_name,
+            expected,
+            location,
+            span,
+            found,
+            src,
+        }
+    }
+    pub(crate) fn name_convention(
+        span: CodeSpan,
+        location: &PathBuf,
+        struct_name: String,
+        convention: &crate::rule::naming::NameConvention,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&struct_name).expect("struct name");
+        let span = (start_hint, struct_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        StructRuleViolation::NameConvention {
+            struct_name,
+            convention: convention.to_string(),
+            location,
+            span,
+            src,
+        }
+    }
+
+    /// `expected` describes the allowed visibility, `allowed` is the one to suggest.
+    pub(crate) fn visibility_level(
+        span: CodeSpan,
+        location: &PathBuf,
+        struct_name: String,
+        expected: String,
+        found: &Visibility,
+        allowed: &Visibility,
+    ) -> Self {
+        let sample = fs::read_to_string(location).expect("path exists");
+        let sample = get_code_sample_region(&sample, &span);
+        let start_hint = sample.find(&struct_name).expect("struct name");
+        let span = (start_hint, struct_name.len()).into();
+        let location = get_relative_location(location);
+        let src = NamedSource::new(&location, sample);
+        StructRuleViolation::VisibilityLevel {
+            struct_name,
+            expected,
+            found: found.to_string(),
+            allowed: allowed.to_string(),
+            location,
+            span,
+            src,
+        }
+    }
 }
@@ -0,0 +1,23 @@
+use speculoos::prelude::*;
+use std::process::Command;
+
+#[test]
+fn should_check_the_crate_given_by_manifest_path() {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-archunit"))
+        .args([
+            "archunit",
+            "--manifest-path",
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/layered_crate/Cargo.toml"
+            ),
+        ])
+        .output()
+        .expect("cargo-archunit should run");
+
+    // archunit_rs itself derives Debug everywhere, only the fixture has a violation
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_that!(output.status.success()).is_false();
+    assert_that!(stdout.contains("Struct 'Db' should implement or derive 'Debug'")).is_true();
+    assert_that!(stdout.contains("1 rules checked, 1 failed")).is_true();
+}
@@ -0,0 +1,575 @@
+use archunit_rs::assertion_result::{AssertionResult, Violation};
+use archunit_rs::graph::{ClassDiagram, DependencyGraph};
+use archunit_rs::layer_rule::{
+    layered_architecture, LayerAssertionBuilder, LayerDefinitionBuilder,
+};
+use archunit_rs::metrics::CouplingMetrics;
+use archunit_rs::plantuml_rule::{adhere_to_plantuml_diagram, PlantUmlDiagram};
+use archunit_rs::public_api::{ApiSnapshot, PublicApi};
+use archunit_rs::rule::enums::Enums;
+use archunit_rs::rule::modules::Modules;
+use archunit_rs::rule::structs::Structs;
+use archunit_rs::rule::{ArchRuleBuilder, CheckRule};
+use archunit_rs::{CfgSet, ExludeModules, ModuleTree, TargetKind, Visibility};
+use once_cell::sync::Lazy;
+use speculoos::prelude::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The fixture crate in `tests/fixtures/<name>`, loaded once for all the tests.
+fn fixture(name: &'static str) -> &'static ModuleTree {
+    static FIXTURES: Lazy<Mutex<HashMap<&str, &'static ModuleTree>>> = Lazy::new(Default::default);
+    let mut fixtures = FIXTURES
+        .lock()
+        .expect("fixtures lock should not be poisoned");
+    fixtures.entry(name).or_insert_with(|| {
+        let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+        Box::leak(Box::new(ModuleTree::load_from(path, name)))
+    })
+}
+
+/// The violations of `result` as given by `describe`, sorted.
+fn violations(result: &AssertionResult, describe: impl Fn(&Violation) -> String) -> Vec<String> {
+    let mut violations: Vec<String> = result.actual.iter().map(describe).collect();
+    violations.sort();
+    violations
+}
+
+#[test]
+fn should_load_fixture_crate() {
+    assert_that!(fixture("layered_crate").ident.as_str()).is_equal_to("layered_crate");
+    assert_that!(fixture("layered_crate").submodules).has_length(2);
+}
+
+#[test]
+fn should_evaluate_struct_rule_against_fixture() {
+    let result = Structs::all_should_in(fixture("layered_crate"), ExludeModules::default())
+        .implement_or_derive("Debug")
+        .evaluate();
+
+    assert_that!(result.is_success()).is_false();
+    assert_that!(result.actual).has_length(1);
+    assert_that!(result.actual[0].module.to_string())
+        .is_equal_to("layered_crate::infra".to_owned());
+}
+
+#[test]
+fn should_resolve_crate_paths_against_fixture_name() {
+    let result = layered_architecture()
+        .layer("domain")
+        .defined_by("layered_crate::domain")
+        .layer("infra")
+        .defined_by("layered_crate::infra")
+        .where_layer("infra")
+        .may_not_be_accessed_by_any_layer()
+        .evaluate_in(fixture("layered_crate"));
+
+    assert_that!(result.is_success()).is_false();
+    assert_that!(result.actual).has_length(1);
+    assert_that!(result.actual[0].module.to_string())
+        .is_equal_to("layered_crate::domain".to_owned());
+}
+
+#[test]
+fn should_export_dependency_graph_with_layers_and_violations() {
+    let architecture = layered_architecture()
+        .layer("domain")
+        .defined_by("layered_crate::domain")
+        .layer("infra")
+        .defined_by("layered_crate::infra")
+        .where_layer("infra")
+        .may_not_be_accessed_by_any_layer();
+    let results = [architecture.evaluate_in(fixture("layered_crate"))];
+    let layers = layered_architecture()
+        .layer("domain")
+        .defined_by("layered_crate::domain")
+        .into();
+
+    let dot = DependencyGraph::builder(ExludeModules::default())
+        .group_by_layers(&layers)
+        .highlight_violations(&results)
+        .build_in(fixture("layered_crate"))
+        .to_dot();
+
+    assert_that!(dot.as_str()).contains("label=\"domain\";\n        \"layered_crate::domain\";");
+    assert_that!(dot.as_str())
+        .contains("\"layered_crate::domain\" -> \"layered_crate::infra\" [color=red];");
+}
+
+#[test]
+fn should_collapse_dependency_graph_to_depth() {
+    let graph = DependencyGraph::builder(ExludeModules::default())
+        .depth(0)
+        .build_in(fixture("layered_crate"));
+
+    assert_that!(graph.nodes.len()).is_equal_to(1);
+    assert_that!(graph.edges.len()).is_equal_to(0);
+}
+
+#[test]
+fn should_export_filtered_dependency_graph_as_plantuml() {
+    let uml = DependencyGraph::builder(ExludeModules::default())
+        .reside_in_a_module("layered_crate::*")
+        .build_in(fixture("layered_crate"))
+        .to_plantuml();
+
+    assert_that!(uml.as_str()).is_equal_to(
+        "@startuml\n\
+         [layered_crate::domain] as layered_crate__domain\n\
+         [layered_crate::infra] as layered_crate__infra\n\
+         layered_crate__domain --> layered_crate__infra\n\
+         @enduml\n",
+    );
+}
+
+#[test]
+fn should_report_dependencies_missing_from_component_diagram() {
+    let result = adhere_to_plantuml_diagram(ExludeModules::default(), "components.puml")
+        .evaluate_in(fixture("layered_crate"));
+
+    assert_that!(result.actual).has_length(1);
+    assert_that!(result.actual[0].message()).is_equal_to(
+        "Dependency of 'layered_crate::domain' on 'layered_crate::infra' is not declared in the diagram"
+            .to_owned(),
+    );
+    assert_that!(result.actual[0].help()).is_equal_to(Some(
+        "Add an arrow from [Domain] to [Infrastructure] to the diagram, or remove the dependency"
+            .to_owned(),
+    ));
+}
+
+#[test]
+fn should_report_functions_with_too_many_parameters() {
+    let result = Modules::that_in(fixture("layered_crate"), ExludeModules::default())
+        .reside_in_a_module("layered_crate::domain")
+        .should()
+        .only_have_functions_with_at_most_parameters(1)
+        .and_should()
+        .only_have_functions_with_at_most_lines(3)
+        .evaluate();
+
+    assert_that!(result.actual).has_length(1);
+    assert_that!(result.actual[0].message()).is_equal_to(
+        "Function 'layered_crate::domain::Order::save' has 2 parameters, at most 1 allowed"
+            .to_owned(),
+    );
+}
+
+#[test]
+fn should_report_oversized_modules_and_structs() {
+    let modules = Modules::that_in(fixture("layered_crate"), ExludeModules::default())
+        .reside_in_a_module("layered_crate::*")
+        .should()
+        .have_at_most_items(1)
+        .or_should()
+        .have_at_most_lines(10)
+        .evaluate();
+    let structs = Structs::all_should_in(fixture("layered_crate"), ExludeModules::default())
+        .have_at_most_fields(0)
+        .evaluate();
+
+    assert_that!(modules.is_success()).is_true();
+    assert_that!(modules.actual[0].message())
+        .is_equal_to("Module 'layered_crate::domain' has 2 items, at most 1 allowed".to_owned());
+    assert_that!(structs.actual).has_length(2);
+    assert_that!(structs.actual[0].message())
+        .is_equal_to("Struct 'Order' has 1 fields, at most 0 allowed".to_owned());
+}
+
+#[test]
+fn should_compute_coupling_metrics_of_fixture() {
+    let metrics = CouplingMetrics::of(fixture("layered_crate"), &ExludeModules::default());
+    let domain = metrics
+        .get("layered_crate::domain")
+        .expect("domain metrics");
+    let infra = metrics.get("layered_crate::infra").expect("infra metrics");
+
+    assert_that!(domain.efferent_coupling).is_equal_to(1);
+    assert_that!(domain.instability()).is_equal_to(1.0);
+    assert_that!(infra.afferent_coupling).is_equal_to(1);
+    assert_that!(infra.instability()).is_equal_to(0.0);
+    assert_that!(infra.distance_from_main_sequence()).is_equal_to(1.0);
+}
+
+#[test]
+fn should_report_modules_above_instability_threshold() {
+    let result = Modules::that_in(fixture("layered_crate"), ExludeModules::default())
+        .reside_in_a_module("layered_crate::*")
+        .should()
+        .have_instability_below(0.3)
+        .evaluate();
+
+    assert_that!(result.actual).has_length(1);
+    assert_that!(result.actual[0].message()).is_equal_to(
+        "Module 'layered_crate::domain' has instability 1.00, expected below 0.30".to_owned(),
+    );
+    assert_that!(result.actual[0].help()).is_equal_to(Some(
+        "Ca = 0, Ce = 1, 0 trait(s) and 1 struct(s) or enum(s) declared".to_owned(),
+    ));
+}
+
+#[test]
+fn should_locate_invalid_component_diagrams() {
+    let error = PlantUmlDiagram::load(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/layered_crate/invalid.puml"
+    ))
+    .expect_err("unknown component");
+
+    assert_that!(error.to_string())
+        .is_equal_to("Invalid component diagram: Unknown component infra".to_owned());
+}
+
+#[test]
+fn should_fail_rule_on_invalid_component_diagram() {
+    let result = adhere_to_plantuml_diagram(ExludeModules::default(), "invalid.puml")
+        .evaluate_in(fixture("layered_crate"));
+    let missing = adhere_to_plantuml_diagram(ExludeModules::default(), "missing.puml")
+        .evaluate_in(fixture("layered_crate"));
+
+    assert_that!(result.is_success()).is_false();
+    assert_that!(result.actual[0].message())
+        .is_equal_to("Invalid component diagram: Unknown component infra".to_owned());
+    assert_that!(missing.is_success()).is_false();
+    assert_that!(missing.actual[0].message()).starts_with("Unable to read");
+}
+
+static TARGETS_CRATE_BINARIES: Lazy<Vec<ModuleTree>> = Lazy::new(|| {
+    ModuleTree::load_targets(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/targets_crate"),
+        &[TargetKind::Bin],
+    )
+});
+
+#[test]
+fn should_evaluate_rules_against_binary_targets() {
+    let binaries_using_infra: Vec<&str> = TARGETS_CRATE_BINARIES
+        .iter()
+        .filter(|tree| {
+            !layered_architecture()
+                .layer("infra")
+                .defined_by("targets_crate::infra")
+                .where_layer("infra")
+                .may_not_be_accessed_by_any_layer()
+                .evaluate_in(tree)
+                .is_success()
+        })
+        .map(|tree| tree.ident.as_str())
+        .collect();
+
+    assert_that!(binaries_using_infra).is_equal_to(vec!["admin", "tool"]);
+}
+
+fn structs_without_debug(filters: ExludeModules<'static>) -> Vec<String> {
+    let result = Structs::all_should_in(fixture("cfg_crate"), filters)
+        .implement_or_derive("Debug")
+        .evaluate();
+
+    violations(&result, |violation| {
+        format!("{}:{}", violation.module, violation.span.start().line)
+    })
+}
+
+#[test]
+fn should_keep_items_depending_on_unknown_cfg_options() {
+    assert_that!(structs_without_debug(ExludeModules::default())).is_equal_to(vec![
+        "cfg_crate:11".to_owned(),
+        "cfg_crate:8".to_owned(),
+        "cfg_crate::mocks:1".to_owned(),
+        "cfg_crate::windows:1".to_owned(),
+    ]);
+}
+
+#[test]
+fn should_exclude_items_requiring_excluded_cfg_options() {
+    let without_test = structs_without_debug(ExludeModules::cfg_test());
+    let without_mocks =
+        structs_without_debug(ExludeModules::default().exclude_cfg(r#"feature = "mocks""#));
+
+    assert_that!(without_test).has_length(3);
+    assert_that!(without_test).is_equal_to(without_mocks);
+    assert_that!(without_test).does_not_contain("cfg_crate::mocks:1".to_owned());
+}
+
+#[test]
+fn should_only_keep_items_compiled_with_active_cfg() {
+    let unix_with_serde = CfgSet::new().flag("unix").feature("serde");
+    assert_that!(structs_without_debug(
+        ExludeModules::default().active_cfg(unix_with_serde)
+    ))
+    .is_equal_to(vec!["cfg_crate:8".to_owned()]);
+
+    let windows_tests = CfgSet::new().flag("test").feature("mocks");
+    assert_that!(structs_without_debug(
+        ExludeModules::default().active_cfg(windows_tests)
+    ))
+    .is_equal_to(vec![
+        "cfg_crate:11".to_owned(),
+        "cfg_crate::mocks:1".to_owned(),
+        "cfg_crate::windows:1".to_owned(),
+    ]);
+}
+
+#[test]
+fn should_require_adapters_to_be_feature_gated() {
+    let result = Modules::that_in(fixture("features_crate"), ExludeModules::default())
+        .reside_in_a_module("features_crate::adapters::*")
+        .should()
+        .be_feature_gated()
+        .evaluate();
+
+    // `pool` is gated by its parent module
+    assert_that!(violations(&result, |violation| violation
+        .module
+        .to_string()))
+    .is_equal_to(vec!["features_crate::adapters::memory".to_owned()]);
+}
+
+#[test]
+fn should_select_items_gated_by_feature() {
+    let modules = Modules::that_in(fixture("features_crate"), ExludeModules::default())
+        .are_gated_by_feature("postgres")
+        .should()
+        .be_gated_by_feature("redis")
+        .evaluate();
+    let structs = Structs::that_in(fixture("features_crate"), ExludeModules::default())
+        .are_gated_by_feature("postgres")
+        .should()
+        .have_simple_name("Pool")
+        .evaluate();
+
+    assert_that!(violations(&modules, |violation| violation
+        .module
+        .to_string()))
+    .is_equal_to(vec![
+        "features_crate::adapters::postgres".to_owned(),
+        "features_crate::adapters::postgres::pool".to_owned(),
+    ]);
+    assert_that!(violations(&structs, |violation| violation
+        .module
+        .to_string()))
+    .is_equal_to(vec!["features_crate::adapters::postgres".to_owned()]);
+}
+
+#[test]
+fn should_reject_undeclared_features() {
+    let result = Modules::all_should_in(fixture("features_crate"), ExludeModules::default())
+        .only_use_declared_features()
+        .evaluate();
+
+    let undeclared = violations(&result, Violation::message);
+    // `trace` inherits `tracing` from its impl block, which is reported once
+    assert_that!(undeclared).is_equal_to(vec![
+        "'MemoryStore' is gated by undeclared feature 'tracing'".to_owned(),
+        "'Metrics' is gated by undeclared feature 'metrics'".to_owned(),
+        "'TlsMode' is gated by undeclared feature 'tls'".to_owned(),
+        "'cached' is gated by undeclared feature 'cache'".to_owned(),
+    ]);
+}
+
+#[test]
+fn should_report_invalid_manifest_as_violation() {
+    let crate_dir = std::env::temp_dir().join("archunit_rs_invalid_manifest");
+    std::fs::create_dir_all(crate_dir.join("src")).expect("temp dir");
+    std::fs::write(crate_dir.join("src/lib.rs"), "pub mod app {}\n").expect("temp file");
+    std::fs::write(crate_dir.join("Cargo.toml"), "[package]\nname = \"app\"\n").expect("temp file");
+    let tree: &'static ModuleTree = Box::leak(Box::new(ModuleTree::load_from(&crate_dir, "app")));
+    std::fs::write(crate_dir.join("Cargo.toml"), "[features]\nfast = \"yes\"\n")
+        .expect("temp file");
+
+    let result = Modules::all_should_in(tree, ExludeModules::default())
+        .only_use_declared_features()
+        .evaluate();
+
+    assert_that!(result.is_success()).is_false();
+    assert_that!(result.actual).has_length(1);
+    assert_that!(result.actual[0].message()).starts_with("Invalid manifest");
+}
+
+#[test]
+fn should_only_snapshot_reachable_public_items() {
+    let snapshot = ApiSnapshot::of(fixture("api_crate"), &ExludeModules::cfg_test());
+
+    // `Canvas` lives in a private module, but the impl block of `Shape` next to it is public
+    assert_that!(snapshot.lines().any(|line| line.contains("Canvas"))).is_false();
+    assert_that!(snapshot.lines().any(|line| line.contains("Cache"))).is_false();
+    assert_that!(snapshot.lines().any(|line| line.contains("test_support"))).is_false();
+    assert_that!(snapshot
+        .lines()
+        .any(|line| line == "impl api_crate::shapes::Shape"))
+    .is_true();
+}
+
+#[test]
+fn should_match_committed_api_snapshot() {
+    let result = PublicApi::should_match_snapshot(ExludeModules::cfg_test(), "public_api.txt")
+        .evaluate_in(fixture("api_crate"));
+
+    assert_that!(result.is_success()).is_true();
+}
+
+#[test]
+fn should_report_missing_api_snapshot() {
+    let result = PublicApi::should_match_snapshot(ExludeModules::cfg_test(), "missing_api.txt")
+        .evaluate_in(fixture("api_crate"));
+
+    assert_that!(result.is_success()).is_false();
+    assert_that!(result.actual).has_length(1);
+    assert_that!(result.actual[0].message()).is_equal_to(
+        "API snapshot 'tests/fixtures/api_crate/missing_api.txt' does not exist".to_owned(),
+    );
+}
+
+#[test]
+fn should_report_api_changes_against_stale_snapshot() {
+    let result = PublicApi::should_match_snapshot(ExludeModules::cfg_test(), "stale_api.txt")
+        .evaluate_in(fixture("api_crate"));
+
+    let changes = violations(&result, Violation::message);
+    assert_that!(changes).is_equal_to(vec![
+        "'struct api_crate::shapes::Triangle' was removed from the public API".to_owned(),
+        "'variant api_crate::shapes::Shape::Empty' was added to the public API".to_owned(),
+    ]);
+}
+
+#[test]
+fn should_check_field_types_against_resolved_paths() {
+    let forbidden = Structs::that_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .have_field_of_type("api_crate::shapes::point::Point")
+        .should()
+        .not_have_fields_of_type_matching("*::point::*")
+        .evaluate();
+    let outside = Structs::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .only_have_fields_of_types_in_modules(&["api_crate::shapes"])
+        .evaluate();
+
+    assert_that!(forbidden.actual).has_length(1);
+    assert_that!(forbidden.actual[0].message()).is_equal_to(
+        "Field 'center' of struct 'Circle' has type 'api_crate::shapes::point::Point' matching '*::point::*'"
+            .to_owned(),
+    );
+    assert_that!(outside.actual).has_length(1);
+    assert_that!(
+        Structs::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+            .only_have_fields_of_types_in_modules(&["api_crate::shapes*"])
+            .evaluate()
+            .is_success()
+    )
+    .is_true();
+}
+
+#[test]
+fn should_check_struct_shapes() {
+    let unit = Structs::that_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .are_unit_structs()
+        .should()
+        .have_exactly_fields(0)
+        .and_should()
+        .not_be_generic()
+        .evaluate();
+    let tuple = Structs::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .be_tuple_struct()
+        .evaluate();
+
+    assert_that!(unit.is_success()).is_true();
+    assert_that!(tuple.actual).has_length(4);
+    assert_that!(tuple.actual[0].message())
+        .is_equal_to("Struct 'Registry' should be a tuple struct".to_owned());
+}
+
+#[test]
+fn should_label_offending_enum_variants() {
+    let fieldless = Enums::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .be_fieldless()
+        .evaluate();
+    let carrying = Enums::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .have_no_variant_carrying_type("api_crate::shapes::Circle")
+        .evaluate();
+
+    assert_that!(fieldless.actual).has_length(2);
+    assert_that!(fieldless.actual[1].message()).is_equal_to(
+        "Variant 'Square' of enum 'Shape' should be fieldless, found 1 fields".to_owned(),
+    );
+    assert_that!(carrying.actual).has_length(1);
+    assert_that!(carrying.actual[0].message()).is_equal_to(
+        "Variant 'Circle' of enum 'Shape' carries type 'api_crate::shapes::Circle' matching 'api_crate::shapes::Circle'"
+            .to_owned(),
+    );
+}
+
+#[test]
+fn should_check_naming_conventions() {
+    let structs = Structs::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .have_name_matching_regex("^C[a-z]+$")
+        .evaluate();
+    let enums = Enums::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .have_name_ending_with("Kind")
+        .evaluate();
+    let modules = Modules::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .have_snake_case_name()
+        .evaluate();
+
+    assert_that!(violations(&structs, Violation::message)).is_equal_to(vec![
+        "Struct 'Point' name should match regex '^C[a-z]+$'".to_owned(),
+        "Struct 'Registry' name should match regex '^C[a-z]+$'".to_owned(),
+    ]);
+    assert_that!(enums.actual[0].message())
+        .is_equal_to("Enum 'Shape' name should end with 'Kind'".to_owned());
+    assert_that!(modules.is_success()).is_true();
+}
+
+#[test]
+fn should_compare_visibility_levels() {
+    let at_most_crate = Structs::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .have_visibility_at_most(Visibility::Crate)
+        .evaluate();
+    let parent = Structs::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .not_be_more_visible_than_parent_module()
+        .evaluate();
+    let modules = Modules::all_should_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .not_be_more_visible_than_parent_module()
+        .evaluate();
+
+    assert_that!(at_most_crate.actual).has_length(3);
+    assert_that!(violations(&parent, Violation::message)).is_equal_to(vec![
+        "Struct 'Canvas' should be at most private like its module, found pub".to_owned(),
+    ]);
+    assert_that!(modules.is_success()).is_true();
+    assert_that!(
+        Structs::that_in(fixture("api_crate"), ExludeModules::cfg_test())
+            .have_simple_name("Registry")
+            .should()
+            .be_crate_visible()
+            .evaluate()
+            .is_success()
+    )
+    .is_true();
+}
+
+#[test]
+fn should_not_treat_crate_visible_items_as_private() {
+    let private = Structs::that_in(fixture("api_crate"), ExludeModules::cfg_test())
+        .have_simple_name("Registry")
+        .should()
+        .be_private()
+        .evaluate();
+
+    assert_that!(violations(&private, Violation::message))
+        .is_equal_to(vec!["Struct 'Registry' should be private".to_owned()]);
+}
+
+#[test]
+fn should_export_class_diagram_with_implemented_traits() {
+    let diagram = ClassDiagram::builder(ExludeModules::cfg_test())
+        .reside_in_a_module("api_crate::shapes")
+        .build_in(fixture("api_crate"));
+
+    let mermaid = diagram.to_mermaid();
+    assert_that!(mermaid.as_str()).contains(
+        "class api_crate__shapes__Circle[\"Circle\"] {\n        +radius\n        -center\n    }",
+    );
+    assert_that!(mermaid.as_str()).contains("api_crate__shapes__Shape ..|> fmt__Display");
+    assert_that!(diagram.to_plantuml().as_str()).contains(
+        "enum \"Shape\" as api_crate__shapes__Shape {\n    Circle\n    Square\n    Empty\n}",
+    );
+}
@@ -0,0 +1,9 @@
+pub mod shapes;
+mod render;
+
+pub(crate) struct Registry;
+
+#[cfg(test)]
+pub mod test_support {
+    pub struct Sample;
+}
@@ -0,0 +1,34 @@
+pub mod point;
+
+use point::Point;
+use std::fmt;
+
+pub struct Circle {
+    pub radius: f64,
+    center: Point,
+}
+
+pub enum Shape {
+    Circle(Circle),
+    Square { side: f64 },
+    Empty,
+}
+
+struct Cache;
+
+impl Circle {
+    pub fn new(radius: f64) -> Self {
+        Circle {
+            radius,
+            center: Point(0.0, 0.0),
+        }
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "shape")
+    }
+}
+
+impl Cache {}
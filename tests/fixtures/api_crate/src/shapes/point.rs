@@ -0,0 +1 @@
+pub struct Point(pub f64, pub f64);
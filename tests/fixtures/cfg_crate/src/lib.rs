@@ -0,0 +1,11 @@
+pub mod common;
+#[cfg(all(test, feature = "mocks"))]
+pub mod mocks;
+#[cfg(not(unix))]
+pub mod windows;
+
+#[cfg(unix)]
+pub struct UnixHandle;
+
+#[cfg_attr(feature = "serde", cfg(not(unix)))]
+pub struct PortableHandle;
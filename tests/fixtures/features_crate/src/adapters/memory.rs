@@ -0,0 +1,11 @@
+pub struct MemoryStore;
+
+#[cfg(feature = "tracing")]
+impl MemoryStore {
+    pub fn trace(&self) {}
+}
+
+impl MemoryStore {
+    #[cfg(feature = "cache")]
+    pub fn cached(&self) {}
+}
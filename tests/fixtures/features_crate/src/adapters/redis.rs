@@ -0,0 +1,7 @@
+pub struct RedisStore;
+
+#[cfg(all(feature = "redis", feature = "tls"))]
+pub enum TlsMode {
+    Native,
+    Rustls,
+}
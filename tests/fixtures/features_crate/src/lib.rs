@@ -0,0 +1,4 @@
+pub mod adapters;
+
+#[cfg(feature = "metrics")]
+pub struct Metrics;
@@ -0,0 +1,12 @@
+use crate::infra::Db;
+
+#[derive(Debug)]
+pub struct Order {
+    pub id: u32,
+}
+
+impl Order {
+    pub fn save(&self, db: &mut Db) {
+        db.insert(self.id);
+    }
+}
@@ -0,0 +1,9 @@
+pub struct Db {
+    rows: Vec<u32>,
+}
+
+impl Db {
+    pub fn insert(&mut self, id: u32) {
+        self.rows.push(id);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod config;
+pub mod nested;
+pub mod store;
+
+#[path = "other/custom_file.rs"]
+pub mod custom;
+
+#[cfg(feature = "disabled")]
+pub mod disabled;
@@ -0,0 +1,5 @@
+pub mod child;
+
+pub mod inline {
+    pub mod deep;
+}
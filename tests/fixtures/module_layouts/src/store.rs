@@ -0,0 +1,4 @@
+pub mod inner {
+    #[path = "backend.rs"]
+    pub mod backend;
+}
@@ -0,0 +1,5 @@
+use targets_crate::infra::Db;
+
+fn main() {
+    println!("{:?}", Db);
+}
@@ -0,0 +1,5 @@
+use targets_crate::domain::Order;
+
+fn main() {
+    println!("{:?}", Order { id: 3 });
+}
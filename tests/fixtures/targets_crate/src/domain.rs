@@ -0,0 +1,4 @@
+#[derive(Debug)]
+pub struct Order {
+    pub id: u32,
+}
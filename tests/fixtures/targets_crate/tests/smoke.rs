@@ -0,0 +1,6 @@
+use targets_crate::infra::Db;
+
+#[test]
+fn smoke() {
+    let _ = Db;
+}
@@ -0,0 +1,5 @@
+// Produced by a code generator emitting invalid Rust
+pub struct Broken {
+    field: u32
+    other: u32,
+}
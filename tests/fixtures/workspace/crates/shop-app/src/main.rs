@@ -0,0 +1,9 @@
+use shop_domain::Order;
+use store;
+use store::Db;
+
+fn main() {
+    let mut db = Db::default();
+    store::Db::insert(&mut db, 0);
+    Order { id: 1 }.save(&mut db);
+}
@@ -0,0 +1,43 @@
+use archunit_rs::layer_rule::{
+    layered_architecture, LayerArchitectureAssertionChainBuilder, LayerAssertionBuilder,
+    LayerDefinitionBuilder,
+};
+use archunit_rs::rule::enums::{EnumPredicateConjunctionBuilder, Enums};
+use archunit_rs::rule::structs::{StructPredicateConjunctionBuilder, Structs};
+use archunit_rs::rule::ArchRuleBuilder;
+use archunit_rs::ExludeModules;
+
+#[archunit_rs::test]
+fn enums_should_derive_debug() -> EnumPredicateConjunctionBuilder {
+    Enums::all_should(ExludeModules::cfg_test()).implement_or_derive("Debug")
+}
+
+#[archunit_rs::archunit_test]
+fn reports_should_be_public() -> StructPredicateConjunctionBuilder {
+    Structs::that(ExludeModules::cfg_test())
+        .reside_in_a_module("archunit_rs::report::*")
+        .and()
+        .have_name_matching("*Report")
+        .should()
+        .be_public()
+}
+
+#[archunit_rs::test]
+#[should_panic(expected = "Architecture rule 'reports_should_be_private' failed")]
+fn reports_should_be_private() -> StructPredicateConjunctionBuilder {
+    Structs::that(ExludeModules::cfg_test())
+        .reside_in_a_module("archunit_rs::report::*")
+        .should()
+        .be_private()
+}
+
+#[archunit_rs::test]
+fn config_layer_should_not_be_accessed() -> LayerArchitectureAssertionChainBuilder {
+    layered_architecture()
+        .layer("config")
+        .defined_by("archunit_rs::config")
+        .layer("report")
+        .defined_by("archunit_rs::report")
+        .where_layer("config")
+        .may_not_be_accessed_by_any_layer()
+}